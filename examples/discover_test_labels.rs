@@ -30,7 +30,7 @@ fn main() {
 
     for (idx, image_path) in images.iter().enumerate() {
         let filename = image_path.file_name().unwrap().to_str().unwrap();
-        let metadata = fs::metadata(&image_path).unwrap();
+        let metadata = fs::metadata(image_path).unwrap();
         let size_kb = metadata.len() / 1024;
         let size_mb = metadata.len() as f64 / (1024.0 * 1024.0);
 
@@ -38,7 +38,7 @@ fn main() {
         println!("Size: {:.2} MB ({} KB)", size_mb, size_kb);
 
         // Try to load image and get dimensions
-        match image::open(&image_path) {
+        match image::open(image_path) {
             Ok(img) => {
                 let (width, height) = img.dimensions();
                 println!("Dimensions: {}x{} pixels", width, height);
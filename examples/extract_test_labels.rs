@@ -54,18 +54,19 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         println!("## [{}/{}] Processing: {}", idx + 1, test_images.len(), filename);
 
         // Get file size
-        let metadata = fs::metadata(&image_path)?;
+        let metadata = fs::metadata(image_path)?;
         let size_mb = metadata.len() as f64 / (1024.0 * 1024.0);
         println!("Size: {:.2} MB", size_mb);
 
         // Read image
-        let image_bytes = fs::read(&image_path)?;
+        let image_bytes = fs::read(image_path)?;
 
         // Extract fields via OCR
         println!("🔄 Calling Workers AI...");
         match ocr_client.extract_label_fields(&image_bytes).await {
-            Ok(extracted) => {
-                println!("✅ OCR successful");
+            Ok(extraction) => {
+                let extracted = extraction.fields;
+                println!("✅ OCR successful ({}, {})", extraction.model, extraction.prompt);
                 println!("   Brand: {}", extracted.brand_name);
                 println!("   Class: {}", extracted.class_type);
                 println!("   ABV: {:.1}%", extracted.abv);
@@ -9,7 +9,7 @@
 //! Prerequisites:
 //!   - .env file with CF_ACCOUNT_ID and CF_API_TOKEN
 
-use label_verify_hw::services::ocr::WorkersAIClient;
+use label_verify_hw::services::{ocr::WorkersAiClient, prompts};
 use std::env;
 
 #[tokio::main]
@@ -30,7 +30,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Initialize Workers AI client
     println!("🔌 Connecting to Workers AI...");
-    let client = WorkersAIClient::new(&account_id, &api_token)?;
+    let client = WorkersAiClient::new(&account_id, &api_token)?;
     println!("✅ Client initialized\n");
 
     // Create a minimal test image (1x1 red pixel PNG, base64-encoded)
@@ -42,19 +42,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test API call
     println!("🔄 Sending inference request...");
-    println!("   Model: @cf/llava-hf/llava-1.5-7b-hf");
-    println!("   Prompt: Describe this image");
+    println!("   Model: {}", client.model());
+    println!("   Prompt: {}", prompts::registry().active(prompts::LABEL_FIELDS));
 
-    match client.extract_fields(test_image_base64).await {
-        Ok(result) => {
+    use base64::Engine;
+    let test_image = base64::engine::general_purpose::STANDARD.decode(test_image_base64)?;
+
+    match client.extract_label_fields(&test_image).await {
+        Ok(extraction) => {
+            let result = extraction.fields;
             println!("✅ API call successful\n");
             println!("📊 Response:");
             println!("   Brand: {:?}", result.brand_name);
-            println!("   Class: {:?}", result.beverage_class);
-            println!("   Type: {:?}", result.type_designation);
-            println!("   ABV: {:?}", result.alcohol_content);
+            println!("   Class/Type: {:?}", result.class_type);
+            println!("   ABV: {:?}", result.abv);
             println!("   Volume: {:?}", result.net_contents);
-            println!("   Origin: {:?}", result.origin);
+            println!("   Origin: {:?}", result.country_of_origin);
             println!();
             println!("✨ Workers AI is responding correctly!");
             println!("\n⚠️  Note: The actual OCR results may be inaccurate for this test image.");
@@ -67,7 +70,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("   2. Verify CF_API_TOKEN has Workers AI → Read permission");
             println!("   3. Check token hasn't expired");
             println!("   4. Ensure account has Workers AI enabled");
-            return Err(e);
+            return Err(e.into());
         }
     }
 
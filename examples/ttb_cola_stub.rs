@@ -0,0 +1,67 @@
+//! Example: Local TTB COLA Stub Server
+//!
+//! Serves recorded TTB COLA pages so the API and worker can run against a
+//! stand-in for ttbonline.gov. Requests are matched to fixture files with the
+//! same key the client uses in record/replay mode.
+//!
+//! Usage:
+//!   cargo run --example ttb_cola_stub -- [FIXTURES_DIR] [BIND_ADDR]
+//!
+//! Defaults to tests/fixtures/ttb_cola on 127.0.0.1:8089. Then run the app with:
+//!   TTB_COLA_BASE_URL=http://127.0.0.1:8089/colasonline cargo run
+
+use axum::{
+    extract::State,
+    http::{Method, StatusCode, Uri},
+    response::{Html, IntoResponse},
+    Form, Router,
+};
+use label_verify_hw::services::ttb_cola;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let fixtures_dir = PathBuf::from(args.next().unwrap_or_else(|| "tests/fixtures/ttb_cola".to_string()));
+    let bind_addr = args.next().unwrap_or_else(|| "127.0.0.1:8089".to_string());
+
+    println!("🍷 TTB COLA stub server");
+    println!("   Fixtures: {}", fixtures_dir.display());
+    println!("   Base URL: http://{}/colasonline\n", bind_addr);
+
+    let app = Router::new()
+        .fallback(replay)
+        .with_state(Arc::new(fixtures_dir));
+
+    let listener = tokio::net::TcpListener::bind(&bind_addr).await?;
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+/// Answer any request with the recorded page for its URL and form parameters.
+async fn replay(
+    State(fixtures_dir): State<Arc<PathBuf>>,
+    method: Method,
+    uri: Uri,
+    Form(form): Form<Vec<(String, String)>>,
+) -> impl IntoResponse {
+    // For GET requests `Form` reads the query string, which is already in the URI
+    let form: Vec<(&str, String)> = if method == Method::POST {
+        form.iter().map(|(k, v)| (k.as_str(), v.clone())).collect()
+    } else {
+        Vec::new()
+    };
+
+    let file_name = ttb_cola::fixture_file_name(&uri.to_string(), &form);
+    match tokio::fs::read_to_string(fixtures_dir.join(&file_name)).await {
+        Ok(html) => {
+            println!("✅ {} {} → {}", method, uri, file_name);
+            Html(html).into_response()
+        }
+        Err(_) => {
+            println!("❌ {} {} → {} (not recorded)", method, uri, file_name);
+            (StatusCode::NOT_FOUND, format!("No recorded response {}", file_name)).into_response()
+        }
+    }
+}
@@ -0,0 +1,7 @@
+-- Trigram index for fuzzy brand lookups in the local known_beverages cache
+-- Lets OCR typos ("Stone Creak Vineyards") match without a TTB COLA scrape
+
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_known_beverages_brand_trgm
+    ON known_beverages USING GIN (LOWER(brand_name) gin_trgm_ops);
@@ -0,0 +1,5 @@
+-- Ranked candidate matches (local cache and TTB COLA) considered for each job
+-- Lets reviewers pick a different reference product than the automatic match
+
+ALTER TABLE beverage_match_history
+    ADD COLUMN IF NOT EXISTS candidates JSONB NOT NULL DEFAULT '[]'::jsonb;
@@ -0,0 +1,15 @@
+-- Replace VARCHAR + CHECK constraints with Postgres enum types
+-- Mirrors JobStatus and MatchType in the Rust models (sqlx::Type); adding a
+-- variant now means ALTER TYPE ... ADD VALUE instead of rewriting a CHECK
+
+CREATE TYPE job_status AS ENUM ('pending', 'processing', 'completed', 'failed');
+
+ALTER TABLE verification_jobs DROP CONSTRAINT IF EXISTS verification_jobs_status_check;
+ALTER TABLE verification_jobs
+    ALTER COLUMN status TYPE job_status USING status::job_status;
+
+CREATE TYPE match_type AS ENUM ('exact', 'fuzzy', 'category_only', 'no_match', 'ttb_cola_lookup');
+
+ALTER TABLE beverage_match_history DROP CONSTRAINT IF EXISTS match_type_valid;
+ALTER TABLE beverage_match_history
+    ALTER COLUMN match_type TYPE match_type USING match_type::match_type;
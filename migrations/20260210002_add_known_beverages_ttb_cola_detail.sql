@@ -0,0 +1,6 @@
+-- Cache COLA detail page data (approval status/date, applicant, label images)
+-- alongside TTB-sourced rows; producer is filled from the applicant name
+
+ALTER TABLE known_beverages ADD COLUMN IF NOT EXISTS ttb_cola_detail JSONB;
+
+COMMENT ON COLUMN known_beverages.ttb_cola_detail IS 'Parsed TTB COLA detail page (status, approval date, applicant, alcohol content, label image URLs)';
@@ -0,0 +1,21 @@
+-- Negative cache of TTB COLA brand searches that returned no records
+-- Consulted before querying TTB so an unknown brand isn't re-scraped on every label;
+-- an entry expires once checked_at is older than the miss TTL
+
+CREATE TABLE IF NOT EXISTS ttb_cola_lookup_cache (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    brand_key TEXT NOT NULL,
+    category TEXT NOT NULL DEFAULT '',
+    searched_brand TEXT NOT NULL,
+    miss_count INTEGER NOT NULL DEFAULT 1,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_ttb_cola_lookup_cache_key UNIQUE (brand_key, category)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ttb_cola_lookup_cache_checked_at
+    ON ttb_cola_lookup_cache (checked_at);
+
+COMMENT ON TABLE ttb_cola_lookup_cache IS 'TTB COLA brand searches that returned no records (negative cache)';
+COMMENT ON COLUMN ttb_cola_lookup_cache.brand_key IS 'Searched brand, trimmed, lowercased, whitespace collapsed';
+COMMENT ON COLUMN ttb_cola_lookup_cache.checked_at IS 'When TTB last confirmed the miss';
@@ -0,0 +1,28 @@
+-- Track when cached rows were last confirmed and the worker's TTB COLA refresh outcome
+-- Staleness is measured from last_verified_at (updated_at moves on every UPDATE);
+-- rows whose COLA no longer exists are retired and skipped by lookups
+
+CREATE TYPE ttb_cola_refresh_outcome AS ENUM ('refreshed', 'retired', 'failed');
+
+ALTER TABLE known_beverages
+    ADD COLUMN IF NOT EXISTS last_verified_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS retired_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS ttb_cola_refreshed_at TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS ttb_cola_refresh_outcome ttb_cola_refresh_outcome,
+    ADD COLUMN IF NOT EXISTS ttb_cola_refresh_error TEXT;
+
+UPDATE known_beverages SET last_verified_at = COALESCE(created_at, NOW()) WHERE last_verified_at IS NULL;
+
+ALTER TABLE known_beverages
+    ALTER COLUMN last_verified_at SET DEFAULT NOW(),
+    ALTER COLUMN last_verified_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_known_beverages_ttb_cola_refresh
+    ON known_beverages (last_verified_at)
+    WHERE source = 'ttb_cola' AND retired_at IS NULL;
+
+COMMENT ON COLUMN known_beverages.last_verified_at IS 'When the row was last confirmed against its source (drives cache staleness)';
+COMMENT ON COLUMN known_beverages.retired_at IS 'Set when the source no longer has the product; retired rows are not matched';
+COMMENT ON COLUMN known_beverages.ttb_cola_refreshed_at IS 'Last TTB COLA refresh attempt by the worker';
+COMMENT ON COLUMN known_beverages.ttb_cola_refresh_outcome IS 'Outcome of the last TTB COLA refresh attempt';
+COMMENT ON COLUMN known_beverages.ttb_cola_refresh_error IS 'Error from the last failed TTB COLA refresh attempt';
@@ -0,0 +1,19 @@
+-- Store the TTB approval status of cached COLA rows (from the COLA detail page)
+-- NULL means unknown (detail page not fetched, or a non-COLA source); existing
+-- rows are backfilled from the "Status: ..." suffix upsert_from_ttb_cola writes to notes
+
+CREATE TYPE ttb_cola_status AS ENUM ('approved', 'surrendered', 'revoked', 'expired');
+
+ALTER TABLE known_beverages
+    ADD COLUMN IF NOT EXISTS ttb_cola_status ttb_cola_status;
+
+UPDATE known_beverages
+SET ttb_cola_status = CASE
+        WHEN notes ~* 'Status: [^,]*REVOKED' THEN 'revoked'
+        WHEN notes ~* 'Status: [^,]*SURRENDER' THEN 'surrendered'
+        WHEN notes ~* 'Status: [^,]*EXPIRED' THEN 'expired'
+        WHEN notes ~* 'Status: [^,]*APPROVED' THEN 'approved'
+    END::ttb_cola_status
+WHERE source = 'ttb_cola' AND ttb_cola_status IS NULL;
+
+COMMENT ON COLUMN known_beverages.ttb_cola_status IS 'TTB approval status of the COLA (NULL if unknown); non-approved COLAs are flagged by validation';
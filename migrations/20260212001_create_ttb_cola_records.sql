@@ -0,0 +1,89 @@
+-- Store scraped TTB COLA records in their own table, one row per TTB ID, and
+-- link the known_beverages rows cached from them; abv_source tells a measured
+-- ABV (label, approved alcohol content) from one inferred from the COLA class
+
+CREATE TYPE abv_source AS ENUM ('measured', 'inferred');
+
+CREATE TABLE IF NOT EXISTS ttb_cola_records (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    ttb_id VARCHAR(20) NOT NULL,
+    permit_no VARCHAR(50),
+    serial_number VARCHAR(50),
+    completed_date DATE,
+    fanciful_name VARCHAR(300),
+    brand_name VARCHAR(200) NOT NULL,
+    origin_code VARCHAR(10),
+    origin_desc VARCHAR(100),
+    class_type_code VARCHAR(10),
+    class_type_desc VARCHAR(200) NOT NULL,
+    beverage_category VARCHAR(50) NOT NULL,
+    inferred_abv DECIMAL(4,2),
+    source_url TEXT NOT NULL,
+    status ttb_cola_status,
+    detail JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT ttb_cola_records_ttb_id_key UNIQUE (ttb_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ttb_cola_records_brand ON ttb_cola_records(LOWER(brand_name));
+
+CREATE TRIGGER update_ttb_cola_records_updated_at
+    BEFORE UPDATE ON ttb_cola_records
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
+
+ALTER TABLE known_beverages
+    ADD COLUMN IF NOT EXISTS ttb_cola_record_id UUID REFERENCES ttb_cola_records(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS abv_source abv_source NOT NULL DEFAULT 'measured';
+
+CREATE INDEX IF NOT EXISTS idx_known_beverages_ttb_cola_record ON known_beverages(ttb_cola_record_id);
+
+-- Backfill from existing ttb_cola rows: the TTB ID is in source_url (ttbid=...)
+-- or the notes upsert_from_ttb_cola wrote ("TTB COLA ID: ..., Permit: ...,
+-- Origin: DESC (CODE)"). The most recently verified row wins per TTB ID.
+INSERT INTO ttb_cola_records
+    (ttb_id, permit_no, fanciful_name, brand_name, origin_code, origin_desc, class_type_desc,
+     beverage_category, source_url, status, detail)
+SELECT DISTINCT ON (ttb_id)
+       ttb_id,
+       NULLIF(substring(notes FROM 'Permit: ([^,]*)'), ''),
+       product_name,
+       brand_name,
+       NULLIF(substring(notes FROM 'Origin: [^,]*\(([^)]*)\)'), ''),
+       NULLIF(substring(notes FROM 'Origin: ([^,(]*[^ ,(])'), ''),
+       class_type,
+       beverage_category,
+       source_url,
+       ttb_cola_status,
+       ttb_cola_detail
+FROM (
+    SELECT kb.*,
+           COALESCE(substring(source_url FROM 'ttbid=(\d+)'), substring(notes FROM 'TTB COLA ID: (\d+)')) AS ttb_id
+    FROM known_beverages kb
+    WHERE source = 'ttb_cola' AND source_url IS NOT NULL
+) cached
+WHERE ttb_id IS NOT NULL
+ORDER BY ttb_id, last_verified_at DESC
+ON CONFLICT (ttb_id) DO NOTHING;
+
+UPDATE known_beverages kb
+SET ttb_cola_record_id = r.id,
+    abv_source = CASE
+        WHEN kb.ttb_cola_detail->>'alcohol_content' IS NOT NULL THEN 'measured'
+        ELSE 'inferred'
+    END::abv_source
+FROM ttb_cola_records r
+WHERE kb.source = 'ttb_cola'
+  AND r.ttb_id = COALESCE(substring(kb.source_url FROM 'ttbid=(\d+)'), substring(kb.notes FROM 'TTB COLA ID: (\d+)'));
+
+-- The detail page now lives on ttb_cola_records
+ALTER TABLE known_beverages DROP COLUMN IF EXISTS ttb_cola_detail;
+
+COMMENT ON TABLE ttb_cola_records IS 'TTB COLA records scraped from COLA Online, one per TTB ID';
+COMMENT ON COLUMN ttb_cola_records.inferred_abv IS 'ABV inferred from the class/type (COLAs have no ABV column); see detail for the approved alcohol content';
+COMMENT ON COLUMN ttb_cola_records.detail IS 'Parsed COLA detail page (status, approval date, applicant, alcohol content, label image URLs)';
+COMMENT ON COLUMN known_beverages.ttb_cola_record_id IS 'COLA this row was cached from (source = ttb_cola)';
+COMMENT ON COLUMN known_beverages.abv_source IS 'measured (label or approved alcohol content) or inferred (from the COLA class/type)';
+COMMENT ON COLUMN known_beverages.ttb_cola_status IS 'Copy of the linked COLA status, kept for match ordering (NULL if unknown)';
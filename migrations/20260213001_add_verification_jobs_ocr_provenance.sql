@@ -0,0 +1,18 @@
+-- Record which model and prompt version produced each job's extraction, and
+-- the model's raw output (encrypted in app like extracted_fields), so changed
+-- results can be traced back to a prompt or model change
+
+ALTER TABLE verification_jobs
+    ADD COLUMN IF NOT EXISTS ocr_model VARCHAR(200),
+    ADD COLUMN IF NOT EXISTS ocr_prompt_name VARCHAR(100),
+    ADD COLUMN IF NOT EXISTS ocr_prompt_version INTEGER,
+    ADD COLUMN IF NOT EXISTS ocr_raw_output BYTEA;
+
+CREATE INDEX IF NOT EXISTS idx_jobs_ocr_prompt
+    ON verification_jobs(ocr_prompt_name, ocr_prompt_version)
+    WHERE ocr_prompt_name IS NOT NULL;
+
+COMMENT ON COLUMN verification_jobs.ocr_model IS 'Workers AI model ID used for OCR extraction';
+COMMENT ON COLUMN verification_jobs.ocr_prompt_name IS 'Extraction prompt name (services::prompts)';
+COMMENT ON COLUMN verification_jobs.ocr_prompt_version IS 'Extraction prompt version';
+COMMENT ON COLUMN verification_jobs.ocr_raw_output IS 'Raw model output before JSON extraction (AES-256-GCM encrypted in app)';
@@ -0,0 +1,12 @@
+-- Persist each job's OCR extraction so it can be re-validated after a rules
+-- change without re-running OCR. extracted_fields was never written, so it is
+-- retyped to hold ciphertext like ocr_raw_output.
+
+ALTER TABLE verification_jobs
+    ALTER COLUMN extracted_fields TYPE BYTEA USING NULL,
+    ADD COLUMN IF NOT EXISTS ocr_duration_ms INTEGER,
+    ADD COLUMN IF NOT EXISTS ocr_completed_at TIMESTAMPTZ;
+
+COMMENT ON COLUMN verification_jobs.extracted_fields IS 'OCR-extracted fields as JSON (AES-256-GCM encrypted in app)';
+COMMENT ON COLUMN verification_jobs.ocr_duration_ms IS 'Wall-clock time of the Workers AI extraction call';
+COMMENT ON COLUMN verification_jobs.ocr_completed_at IS 'When the extraction was stored';
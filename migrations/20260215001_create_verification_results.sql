@@ -0,0 +1,45 @@
+-- Keep every validation of a job as a numbered revision (the worker's run, then
+-- each revalidation against the stored extraction) instead of overwriting
+-- verification_result, and record the submission inputs revalidation reuses
+
+ALTER TABLE verification_jobs
+    ADD COLUMN IF NOT EXISTS expected_brand VARCHAR(200),
+    ADD COLUMN IF NOT EXISTS expected_class VARCHAR(200),
+    ADD COLUMN IF NOT EXISTS expected_abv DECIMAL(5,2),
+    ADD COLUMN IF NOT EXISTS ttb_id VARCHAR(20),
+    ADD COLUMN IF NOT EXISTS profile_name VARCHAR(100),
+    ADD COLUMN IF NOT EXISTS tenant VARCHAR(100),
+    ADD COLUMN IF NOT EXISTS profile_overrides TEXT;
+
+CREATE TYPE verification_trigger AS ENUM ('worker', 'revalidate');
+
+CREATE TABLE IF NOT EXISTS verification_results (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    job_id UUID NOT NULL REFERENCES verification_jobs(id) ON DELETE CASCADE,
+    revision INTEGER NOT NULL,
+    trigger verification_trigger NOT NULL,
+    expected_brand VARCHAR(200),
+    expected_class VARCHAR(200),
+    expected_abv DECIMAL(5,2),
+    ttb_id VARCHAR(20),
+    profile_name VARCHAR(100),
+    passed BOOLEAN NOT NULL,
+    result JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+
+    CONSTRAINT verification_results_job_revision_key UNIQUE (job_id, revision)
+);
+
+-- Existing results become revision 1 (their inputs were never stored)
+INSERT INTO verification_results (job_id, revision, trigger, profile_name, passed, result, created_at)
+SELECT id, 1, 'worker', verification_result->>'profile_name',
+       COALESCE((verification_result->>'passed')::boolean, false), verification_result,
+       COALESCE(processing_completed_at, updated_at)
+FROM verification_jobs
+WHERE verification_result IS NOT NULL
+ON CONFLICT (job_id, revision) DO NOTHING;
+
+COMMENT ON TABLE verification_results IS 'Every validation of a job; verification_jobs.verification_result is the latest revision';
+COMMENT ON COLUMN verification_results.trigger IS 'worker (after OCR) or revalidate (POST /api/v1/verify/{job_id}/revalidate)';
+COMMENT ON COLUMN verification_results.profile_name IS 'Resolved validation profile name (e.g. strict, default+request)';
+COMMENT ON COLUMN verification_jobs.profile_overrides IS 'Request profile_overrides spec as submitted (key=value,...)';
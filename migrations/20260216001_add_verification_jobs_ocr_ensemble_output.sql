@@ -0,0 +1,7 @@
+-- Raw output of the other OCR ensemble members (ocr_raw_output holds the first
+-- member's), so a disputed field can be traced to what each model returned
+
+ALTER TABLE verification_jobs
+    ADD COLUMN IF NOT EXISTS ocr_ensemble_output BYTEA;
+
+COMMENT ON COLUMN verification_jobs.ocr_ensemble_output IS 'JSON array of {model, prompt_name, prompt_version, raw_output} for the other ensemble members (AES-256-GCM encrypted in app; NULL for a single model)';
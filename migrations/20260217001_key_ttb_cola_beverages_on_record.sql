@@ -0,0 +1,30 @@
+-- Key known_beverages rows cached from a COLA on their ttb_cola_records row
+-- rather than brand/product/ABV, so a COLA whose ABV goes from inferred to
+-- measured keeps one row and COLAs sharing a brand and ABV keep their own
+
+-- A COLA cached under an inferred and later a measured ABV got two rows; keep
+-- the measured one (else the most recently verified) and drop the others
+DELETE FROM known_beverages kb
+USING (
+    SELECT id,
+           ROW_NUMBER() OVER (
+               PARTITION BY ttb_cola_record_id
+               ORDER BY abv_source = 'measured' DESC, last_verified_at DESC NULLS LAST
+           ) AS rank
+    FROM known_beverages
+    WHERE ttb_cola_record_id IS NOT NULL
+) ranked
+WHERE kb.id = ranked.id AND ranked.rank > 1;
+
+DROP INDEX IF EXISTS idx_known_beverages_unique;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_known_beverages_unique
+    ON known_beverages(LOWER(brand_name), LOWER(COALESCE(product_name, '')), abv)
+    WHERE ttb_cola_record_id IS NULL;
+
+DROP INDEX IF EXISTS idx_known_beverages_ttb_cola_record;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_known_beverages_ttb_cola_record
+    ON known_beverages(ttb_cola_record_id)
+    WHERE ttb_cola_record_id IS NOT NULL;
+
+COMMENT ON INDEX idx_known_beverages_unique IS 'Identity of imported and manual rows (COLA rows are keyed on ttb_cola_record_id)';
+COMMENT ON INDEX idx_known_beverages_ttb_cola_record IS 'One cached known_beverages row per COLA';
@@ -0,0 +1,8 @@
+-- Keep the model's output when no label fields could be read from it: the
+-- provenance and raw output are stored with the parse error instead of being
+-- lost with the failed job. extracted_fields stays NULL, so a retry runs OCR again.
+
+ALTER TABLE verification_jobs
+    ADD COLUMN IF NOT EXISTS ocr_parse_error TEXT;
+
+COMMENT ON COLUMN verification_jobs.ocr_parse_error IS 'Why no fields could be read from ocr_raw_output (NULL when extracted_fields was stored)';
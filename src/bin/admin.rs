@@ -0,0 +1,264 @@
+//! `label-verify-admin`: import and export `known_beverages` reference data.
+//!
+//! Only `DATABASE_URL` is required (read from the environment or `.env`).
+//!
+//! ```text
+//! label-verify-admin import data/wine.csv --dry-run
+//! label-verify-admin import colas.json --format cola-json
+//! label-verify-admin import-cola-csv SearchResults.csv --batch-size 5000
+//! label-verify-admin export --format json --source ttb_cola -o ttb_cola.json
+//! label-verify-admin lookup-cola --permit CA-I-1234 --from 2025-01-01 --cache
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use chrono::NaiveDate;
+use clap::{ArgGroup, Parser, Subcommand};
+use label_verify_hw::{
+    db::{self, beverage_queries},
+    services::{
+        reference_import::{self, ExportFormat, ImportFormat, ImportPlan, PlannedAction},
+        ttb_cola::{self, TtbColaClient, TtbColaConfig, TtbColaRecord},
+        ttb_cola_csv::{self, ColaCsvError, ColaCsvReader},
+    },
+};
+use tracing_subscriber::EnvFilter;
+
+#[derive(Parser)]
+#[command(name = "label-verify-admin", about = "Manage label verification reference data")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Import known beverages, updating rows with the same brand, product name and ABV (COLAs: TTB ID)
+    Import {
+        /// File to import
+        file: PathBuf,
+        /// csv, json or cola-json (default: from the file extension)
+        #[arg(long)]
+        format: Option<ImportFormat>,
+        /// Show what would change without writing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Bulk import a TTB COLA search results CSV export into the TTB COLA cache
+    ImportColaCsv {
+        /// CSV saved from COLA Online search results
+        file: PathBuf,
+        /// Records upserted per transaction
+        #[arg(long, default_value_t = ttb_cola_csv::DEFAULT_BATCH_SIZE)]
+        batch_size: usize,
+        /// Only validate the rows
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Export known beverages (retired rows excluded)
+    Export {
+        /// csv or json (rows not cached from a COLA), or cola-json (the cached COLAs)
+        #[arg(long, default_value = "csv")]
+        format: ExportFormat,
+        /// Only rows from this source (e.g. ttb_cola, manual)
+        #[arg(long)]
+        source: Option<String>,
+        /// Output file (default: stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Look up COLAs on TTB by TTB ID, permit number or serial number
+    #[command(group(ArgGroup::new("key").required(true).args(["ttb_id", "permit", "serial"])))]
+    LookupCola {
+        /// TTB ID (e.g. 23001001000123)
+        #[arg(long)]
+        ttb_id: Option<String>,
+        /// Permit number (e.g. BWN-CA-12345)
+        #[arg(long)]
+        permit: Option<String>,
+        /// Applicant serial number (e.g. 250001); unique only per permit
+        #[arg(long)]
+        serial: Option<String>,
+        /// Earliest completed date for a permit search (default: 5 years ago)
+        #[arg(long, conflicts_with_all = ["ttb_id", "serial"])]
+        from: Option<NaiveDate>,
+        /// Latest completed date for a permit search (default: today)
+        #[arg(long, conflicts_with_all = ["ttb_id", "serial"])]
+        to: Option<NaiveDate>,
+        /// Maximum records returned by a search
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+        /// Store the records in the TTB COLA cache
+        #[arg(long)]
+        cache: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    dotenvy::dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("warn")),
+        )
+        .with_writer(io::stderr)
+        .init();
+
+    let cli = Cli::parse();
+    match run(cli.command).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(command: Command) -> Result<(), Box<dyn std::error::Error>> {
+    let database_url = std::env::var("DATABASE_URL").map_err(|_| "DATABASE_URL is not set")?;
+    let pool = db::init_pool(&database_url).await?;
+
+    match command {
+        Command::Import { file, format, dry_run } => {
+            let format = format
+                .or_else(|| ImportFormat::from_path(&file))
+                .ok_or("Cannot tell the format from the file extension; pass --format")?;
+
+            let parsed = reference_import::parse(format, BufReader::new(File::open(&file)?))?;
+            let plan = reference_import::plan_import(&pool, parsed).await?;
+            print_plan(&plan)?;
+
+            let summary = if dry_run {
+                plan.summary()
+            } else {
+                reference_import::apply_import(&pool, &plan).await?
+            };
+            println!(
+                "{}{} inserted, {} updated, {} unchanged, {} duplicates, {} invalid",
+                if dry_run { "Dry run: " } else { "" },
+                summary.inserted,
+                summary.updated,
+                summary.unchanged,
+                summary.duplicates,
+                summary.invalid
+            );
+        }
+        Command::ImportColaCsv { file, batch_size, dry_run } => {
+            let base_url =
+                std::env::var("TTB_COLA_BASE_URL").unwrap_or_else(|_| ttb_cola::DEFAULT_BASE_URL.to_string());
+            let reader = ColaCsvReader::new(BufReader::new(File::open(&file)?), &base_url)?;
+
+            if dry_run {
+                let (mut valid, mut invalid) = (0, 0);
+                for record in reader {
+                    match record {
+                        Ok(_) => valid += 1,
+                        Err(e @ ColaCsvError::InvalidRow { .. }) => {
+                            println!("! {}", e);
+                            invalid += 1;
+                        }
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+                println!("Dry run: {} valid records, {} invalid", valid, invalid);
+            } else {
+                let summary = ttb_cola_csv::import(&pool, reader, batch_size).await?;
+                println!(
+                    "{} inserted, {} updated, {} skipped, {} without ABV",
+                    summary.inserted, summary.updated, summary.skipped, summary.uncached
+                );
+            }
+        }
+        Command::Export { format, source, output } => {
+            let count = match output {
+                Some(path) => {
+                    let writer = BufWriter::new(File::create(&path)?);
+                    reference_import::export(&pool, format, source.as_deref(), writer).await?
+                }
+                None => reference_import::export(&pool, format, source.as_deref(), io::stdout().lock()).await?,
+            };
+            eprintln!("Exported {} rows", count);
+        }
+        Command::LookupCola { ttb_id, permit, serial, from, to, limit, cache } => {
+            let config = TtbColaConfig::from_settings(
+                std::env::var("TTB_COLA_BASE_URL").ok().as_deref(),
+                std::env::var("TTB_COLA_MODE").ok().as_deref(),
+                std::env::var("TTB_COLA_FIXTURES_DIR").ok().as_deref(),
+                std::env::var("TTB_COLA_THROTTLE").ok().as_deref(),
+            )?;
+            let client = TtbColaClient::with_config(config)?;
+
+            let records = match (ttb_id, permit, serial) {
+                (Some(ttb_id), _, _) => client.fetch_by_ttb_id(&ttb_id).await?.into_iter().collect(),
+                (_, Some(permit), _) => {
+                    let (default_from, default_to) = ttb_cola::default_date_range();
+                    let (from, to) = (from.unwrap_or(default_from), to.unwrap_or(default_to));
+                    client.search_by_permit(&permit, from, to, limit).await?
+                }
+                (_, _, Some(serial)) => client.search_by_serial_number(&serial, limit).await?,
+                (None, None, None) => unreachable!("clap requires one lookup key"),
+            };
+            print_cola_records(&records)?;
+
+            if cache && !records.is_empty() {
+                let batch = beverage_queries::upsert_batch_from_ttb_cola(&pool, &records).await?;
+                println!(
+                    "{} inserted, {} updated, {} skipped, {} without ABV",
+                    batch.inserted, batch.updated, batch.skipped, batch.uncached
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print one line per COLA: TTB ID, permit, serial number, completed date,
+/// brand (and fanciful name), class/type and reference ABV.
+fn print_cola_records(records: &[TtbColaRecord]) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for record in records {
+        let name = match &record.fanciful_name {
+            Some(fanciful) => format!("{} / {}", record.brand_name, fanciful),
+            None => record.brand_name.clone(),
+        };
+        let completed = record.completed_date.map(|d| d.to_string()).unwrap_or_default();
+        let abv = record.reference_abv().map(|abv| format!("{}%", abv)).unwrap_or_else(|| "-".to_string());
+        writeln!(
+            out,
+            "{}  {}  {}  {}  {}  {}  {}",
+            record.ttb_id, record.permit_no, record.serial_number, completed, name, record.class_type_desc, abv
+        )?;
+    }
+    writeln!(out, "{} records", records.len())
+}
+
+/// Print one line per inserted or updated row, with changed fields, and the
+/// invalid records.
+fn print_plan(plan: &ImportPlan) -> io::Result<()> {
+    let mut out = io::stdout().lock();
+    for row in &plan.rows {
+        let beverage = &row.beverage;
+        let label = match &beverage.product_name {
+            Some(product) => format!("{} / {} ({}%)", beverage.brand_name, product, beverage.abv),
+            None => format!("{} ({}%)", beverage.brand_name, beverage.abv),
+        };
+        match &row.action {
+            PlannedAction::Insert => writeln!(out, "+ {}", label)?,
+            PlannedAction::Update(changes) => {
+                writeln!(out, "~ {}", label)?;
+                for change in changes {
+                    writeln!(out, "    {}: {:?} -> {:?}", change.field, change.old, change.new)?;
+                }
+            }
+            PlannedAction::Unchanged => {}
+        }
+    }
+    for invalid in &plan.invalid {
+        writeln!(out, "! record {}: {}", invalid.record, invalid.reason)?;
+    }
+    Ok(())
+}
@@ -3,9 +3,19 @@ use label_verify_hw::{
     config::AppConfig,
     db::{self, beverage_queries, queries},
     models::beverage::NewMatchHistory,
-    models::job::JobStatus,
+    models::job::{JobStatus, VerificationTrigger},
     services::{
-        encryption::EncryptionService, ocr::WorkersAiClient, queue::JobQueue, storage::R2Client,
+        encryption::EncryptionService,
+        job_extraction,
+        ocr::{self, OcrError, WorkersAiClient},
+        ocr_ensemble,
+        queue::JobQueue,
+        profile::{self, ProfileRegistry},
+        prompts::{self, PromptRegistry},
+        scoring::{self, ScoringModel},
+        storage::R2Client,
+        ttb_cola::{self, TtbColaClient, TtbColaConfig},
+        ttb_cola_refresh::{self, RefreshSettings},
         validation,
     },
 };
@@ -31,6 +41,18 @@ async fn main() {
     // Load configuration
     let config = AppConfig::from_env().expect("Failed to load configuration");
 
+    // Expose worker metrics (TTB COLA client health) on METRICS_PORT
+    if let Some(port) = config.metrics_port {
+        tracing::info!(port, "Starting Prometheus metrics listener");
+        metrics_exporter_prometheus::PrometheusBuilder::new()
+            .with_http_listener(([0, 0, 0, 0], port))
+            .install()
+            .expect("Failed to install Prometheus metrics exporter");
+        ocr::describe_metrics();
+        ttb_cola::describe_metrics();
+        ttb_cola_refresh::describe_metrics();
+    }
+
     // Initialize database
     tracing::info!("Connecting to PostgreSQL");
     let db_pool = db::init_pool(&config.database_url)
@@ -53,8 +75,65 @@ async fn main() {
 
     let queue = JobQueue::new(&config.redis_url).expect("Failed to initialize job queue");
 
-    let ocr_client = WorkersAiClient::new(&config.cf_account_id, &config.cf_api_token)
+    let mut ocr_client = WorkersAiClient::new(&config.cf_account_id, &config.cf_api_token)
         .expect("Failed to initialize Workers AI client");
+    if let Some(model) = config.ocr_model.as_deref() {
+        ocr_client = ocr_client.with_model(model);
+    }
+
+    // Install extraction prompt versions
+    prompts::init(
+        PromptRegistry::from_spec(config.ocr_prompt_versions.as_deref())
+            .expect("Invalid OCR_PROMPT_VERSIONS"),
+    );
+
+    // Configure the OCR ensemble (entries without a version use the active prompt)
+    let members = ocr_ensemble::members_from_spec(ocr_client.model(), config.ocr_ensemble.as_deref())
+        .expect("Invalid OCR_ENSEMBLE");
+    ocr_client = ocr_client.with_ensemble(members);
+
+    // Install confidence scoring weights
+    if let Some(spec) = config.confidence_weights.as_deref() {
+        let model = ScoringModel::from_spec(spec).expect("Invalid CONFIDENCE_WEIGHTS");
+        scoring::init(model);
+    }
+
+    // Install validation profiles
+    let profiles = ProfileRegistry::from_specs(
+        config.validation_profiles.as_deref(),
+        config.validation_tenant_profiles.as_deref(),
+        config.validation_default_profile.as_deref(),
+        config.validation_override_limits.as_deref(),
+    )
+    .expect("Invalid validation profile configuration");
+    profile::init(profiles);
+
+    // Install the TTB COLA client (live, record or replay)
+    let ttb_config = TtbColaConfig::from_settings(
+        config.ttb_cola_base_url.as_deref(),
+        config.ttb_cola_mode.as_deref(),
+        config.ttb_cola_fixtures_dir.as_deref(),
+        config.ttb_cola_throttle.as_deref(),
+    )
+    .expect("Invalid TTB COLA configuration");
+    tracing::info!(base_url = %ttb_config.base_url, mode = ?ttb_config.mode, "Initializing TTB COLA client");
+    ttb_cola::init(TtbColaClient::with_config(ttb_config).expect("Failed to initialize TTB COLA client"));
+
+    // Refresh stale TTB COLA cache rows in the background
+    let defaults = RefreshSettings::default();
+    let refresh_settings = RefreshSettings {
+        interval: config
+            .ttb_cola_refresh_interval_secs
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.interval),
+        batch_size: config.ttb_cola_refresh_batch_size.unwrap_or(defaults.batch_size),
+        ..defaults
+    };
+    if refresh_settings.interval.is_zero() {
+        tracing::info!("TTB COLA cache refresh disabled");
+    } else {
+        tokio::spawn(ttb_cola_refresh::run_scheduler(db_pool.clone(), refresh_settings));
+    }
 
     let state = AppState::new(db_pool, r2_client, encryption, queue, ocr_client);
 
@@ -105,14 +184,18 @@ async fn process_next_job(state: &AppState) -> Result<bool, Box<dyn std::error::
     // Process the job
     match process_job_inner(state, &job).await {
         Ok(result) => {
-            // Store results in database
-            let result_json = serde_json::to_value(&result)?;
-            queries::update_job_result(
+            // Store results in database as the job's first revision, with
+            // everything submitted with the image (profile selection included)
+            let inputs = queries::get_job(&state.db, job.job_id)
+                .await?
+                .ok_or_else(|| format!("Job {} not found", job.job_id))?
+                .inputs;
+            queries::record_verification(
                 &state.db,
                 job.job_id,
-                JobStatus::Completed,
-                Some(result_json),
-                None,
+                VerificationTrigger::Worker,
+                &inputs,
+                &result,
             )
             .await?;
 
@@ -176,57 +259,47 @@ async fn process_job_inner(
     state: &AppState,
     job: &label_verify_hw::services::queue::QueuedJob,
 ) -> Result<label_verify_hw::models::label::VerificationResult, Box<dyn std::error::Error>> {
-    // Download encrypted image from R2
-    tracing::info!(job_id = %job.job_id, "Downloading image from R2");
-    let encrypted_image = state.storage.download(&job.image_key).await
-        .map_err(|e| {
-            tracing::error!(job_id = %job.job_id, error = %e, "R2 download failed");
-            e
-        })?;
-
-    tracing::info!(
-        job_id = %job.job_id,
-        encrypted_size = encrypted_image.len(),
-        first_bytes = ?&encrypted_image[..std::cmp::min(16, encrypted_image.len())],
-        "Downloaded encrypted image from R2"
-    );
-
-    // Decrypt image in memory
-    tracing::info!(job_id = %job.job_id, "Decrypting image");
-    let image_bytes = state.encryption.decrypt(&encrypted_image)
-        .map_err(|e| {
-            tracing::error!(
+    // A retried job reuses the extraction stored by an earlier attempt
+    let stored = match job_extraction::load(&state.db, &state.encryption, job.job_id).await {
+        Ok(stored) => stored,
+        Err(e) => {
+            tracing::warn!(
                 job_id = %job.job_id,
                 error = %e,
-                data_len = encrypted_image.len(),
-                "Decryption failed — data may be corrupted or key mismatch"
+                "Failed to load stored extraction, running OCR again"
             );
-            e
-        })?;
-
-    // Call Workers AI for OCR
-    tracing::debug!(job_id = %job.job_id, "Calling Workers AI for OCR");
-    let start = std::time::Instant::now();
-    let extracted_fields = state.ocr.extract_label_fields(&image_bytes).await?;
-    let ocr_duration = start.elapsed();
+            None
+        }
+    };
 
-    tracing::info!(
-        job_id = %job.job_id,
-        ocr_duration_ms = ocr_duration.as_millis(),
-        brand = %extracted_fields.brand_name,
-        class = %extracted_fields.class_type,
-        abv = extracted_fields.abv,
-        "OCR extraction complete"
-    );
+    let extracted_fields = match stored {
+        Some(stored) => {
+            tracing::info!(
+                job_id = %job.job_id,
+                model = %stored.provenance.model,
+                prompt_version = stored.provenance.prompt_version,
+                ocr_completed_at = %stored.completed_at,
+                "Reusing stored OCR extraction"
+            );
+            stored.fields
+        }
+        None => extract_label_fields(state, job).await?,
+    };
 
     // Validate extracted fields with database-backed checks
     tracing::debug!(job_id = %job.job_id, "Validating fields (with database cross-reference)");
+    let validation_profile = job
+        .profile
+        .clone()
+        .unwrap_or_else(|| profile::registry().default_profile().clone());
     let verification_result = validation::verify_label_with_database(
         &state.db,
         &extracted_fields,
         job.expected_brand.as_deref(),
         job.expected_class.as_deref(),
         job.expected_abv,
+        job.ttb_id.as_deref(),
+        &validation_profile,
     )
     .await?;
 
@@ -235,6 +308,7 @@ async fn process_job_inner(
         passed = verification_result.passed,
         confidence = verification_result.confidence_score,
         match_type = %verification_result.match_type,
+        profile = %verification_result.profile_name,
         matched_beverage = ?verification_result.matched_beverage_id,
         issues_count = verification_result.field_results.iter().filter(|f| !f.matches).count(),
         "Validation complete"
@@ -244,9 +318,10 @@ async fn process_job_inner(
     let match_history = NewMatchHistory {
         job_id: job.job_id,
         matched_beverage_id: verification_result.matched_beverage_id,
-        match_type: verification_result.match_type.clone(),
+        match_type: verification_result.match_type,
         match_confidence: Some(verification_result.match_confidence),
         abv_deviation: verification_result.abv_deviation,
+        candidates: verification_result.candidates.clone(),
     };
 
     if let Err(e) = beverage_queries::record_match_history(&state.db, match_history).await {
@@ -259,3 +334,79 @@ async fn process_job_inner(
 
     Ok(verification_result)
 }
+
+/// Download and decrypt the job's image, run OCR, and store the extraction.
+async fn extract_label_fields(
+    state: &AppState,
+    job: &label_verify_hw::services::queue::QueuedJob,
+) -> Result<label_verify_hw::models::label::ExtractedLabelFields, Box<dyn std::error::Error>> {
+    // Download encrypted image from R2
+    tracing::info!(job_id = %job.job_id, "Downloading image from R2");
+    let encrypted_image = state.storage.download(&job.image_key).await
+        .map_err(|e| {
+            tracing::error!(job_id = %job.job_id, error = %e, "R2 download failed");
+            e
+        })?;
+
+    tracing::info!(
+        job_id = %job.job_id,
+        encrypted_size = encrypted_image.len(),
+        first_bytes = ?&encrypted_image[..std::cmp::min(16, encrypted_image.len())],
+        "Downloaded encrypted image from R2"
+    );
+
+    // Decrypt image in memory
+    tracing::info!(job_id = %job.job_id, "Decrypting image");
+    let image_bytes = state.encryption.decrypt(&encrypted_image)
+        .map_err(|e| {
+            tracing::error!(
+                job_id = %job.job_id,
+                error = %e,
+                data_len = encrypted_image.len(),
+                "Decryption failed — data may be corrupted or key mismatch"
+            );
+            e
+        })?;
+
+    // Call Workers AI for OCR
+    tracing::debug!(job_id = %job.job_id, "Calling Workers AI for OCR");
+    let start = std::time::Instant::now();
+    let extraction = match state.ocr.extract_label_fields(&image_bytes).await {
+        Ok(extraction) => extraction,
+        Err(OcrError::UnreadableOutput(output)) => {
+            // Keep what the model said, so the failure can be traced to the model or prompt
+            let ocr_duration = start.elapsed();
+            job_extraction::save_unreadable(&state.db, &state.encryption, job.job_id, &output, ocr_duration)
+                .await?;
+            tracing::warn!(
+                job_id = %job.job_id,
+                model = %output.model,
+                prompt = %output.prompt,
+                reason = %output.reason,
+                "No label fields in OCR output; raw output stored"
+            );
+            return Err(OcrError::UnreadableOutput(output).into());
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let ocr_duration = start.elapsed();
+
+    // Store the fields, raw output and timing (encrypted) with the model and prompt version
+    let provenance =
+        job_extraction::save(&state.db, &state.encryption, job.job_id, &extraction, ocr_duration).await?;
+
+    tracing::info!(
+        job_id = %job.job_id,
+        ocr_duration_ms = ocr_duration.as_millis(),
+        model = %provenance.model,
+        prompt = %extraction.prompt,
+        brand = %extraction.fields.brand_name,
+        class = %extraction.fields.class_type,
+        abv = extraction.fields.abv,
+        ensemble_members = extraction.members.len() + 1,
+        disputed_fields = extraction.fields.consensus.iter().filter(|c| c.is_disputed()).count(),
+        "OCR extraction complete"
+    );
+
+    Ok(extraction.fields)
+}
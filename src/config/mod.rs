@@ -32,6 +32,68 @@ pub struct AppConfig {
 
     /// AES-256-GCM encryption key (base64-encoded, 32 bytes)
     pub encryption_key: String,
+
+    /// Workers AI model ID for OCR extraction (default: LLaVA 1.5 7B). Optional.
+    #[serde(default)]
+    pub ocr_model: Option<String>,
+
+    /// Pinned extraction prompt versions ("name=version,..."). Optional.
+    #[serde(default)]
+    pub ocr_prompt_versions: Option<String>,
+
+    /// OCR ensemble members ("model[:prompt_version],..."); fields are voted on
+    /// when two or more are listed. Optional.
+    #[serde(default)]
+    pub ocr_ensemble: Option<String>,
+
+    /// Confidence score weight overrides ("rule_code=weight,..."). Optional.
+    #[serde(default)]
+    pub confidence_weights: Option<String>,
+
+    /// Named validation profiles ("name: key=value, ...; ..."). Optional.
+    #[serde(default)]
+    pub validation_profiles: Option<String>,
+
+    /// Tenant to validation profile mapping ("tenant=profile,..."). Optional.
+    #[serde(default)]
+    pub validation_tenant_profiles: Option<String>,
+
+    /// Profile used when neither the request nor its tenant selects one.
+    #[serde(default)]
+    pub validation_default_profile: Option<String>,
+
+    /// Loosest value a request may override each profile key to
+    /// ("key=value,..."); keys not listed can only be tightened. Optional.
+    #[serde(default)]
+    pub validation_override_limits: Option<String>,
+
+    /// TTB COLA Online base URL (e.g. a local stub server). Optional.
+    #[serde(default)]
+    pub ttb_cola_base_url: Option<String>,
+
+    /// TTB COLA client mode: "live" (default), "record" or "replay". Optional.
+    #[serde(default)]
+    pub ttb_cola_mode: Option<String>,
+
+    /// Directory of recorded TTB COLA responses for record/replay modes. Optional.
+    #[serde(default)]
+    pub ttb_cola_fixtures_dir: Option<String>,
+
+    /// TTB COLA rate limit/retry/circuit breaker settings ("key=value,..."). Optional.
+    #[serde(default)]
+    pub ttb_cola_throttle: Option<String>,
+
+    /// Seconds between worker TTB COLA cache refresh runs (0 disables). Optional.
+    #[serde(default)]
+    pub ttb_cola_refresh_interval_secs: Option<u64>,
+
+    /// Stale TTB COLA rows refreshed per run. Optional.
+    #[serde(default)]
+    pub ttb_cola_refresh_batch_size: Option<i64>,
+
+    /// Port for the worker's Prometheus metrics listener. Optional.
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
 }
 
 fn default_bind_addr() -> String {
@@ -1,54 +1,74 @@
-use sqlx::PgPool;
+use sqlx::types::Json;
+use sqlx::{PgConnection, PgExecutor, PgPool};
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use tracing::{info, warn};
 
-use crate::models::beverage::{BeverageCategoryRule, KnownBeverage, NewMatchHistory};
-use crate::services::ttb_cola::TtbColaRecord;
+use crate::models::beverage::{
+    BeverageCategoryRule, KnownBeverage, NewKnownBeverage, NewMatchHistory, SimilarBeverage,
+    TtbColaBatchUpsert, TtbColaMiss, TtbColaRefreshOutcome, UpsertedBeverage,
+};
+use crate::services::ttb_cola::{self, TtbColaDetail, TtbColaRecord};
 
-/// Find known beverages by brand and class/type (case-insensitive)
+/// Find known beverages by brand and class/type (case-insensitive, not retired).
+/// Approved or non-COLA rows come before surrendered, revoked or expired COLAs,
+/// and measured ABVs before ones inferred from a COLA class/type.
 pub async fn find_known_beverage(
     pool: &PgPool,
     brand: &str,
     class_type: &str,
 ) -> Result<Vec<KnownBeverage>, sqlx::Error> {
-    sqlx::query_as!(
-        KnownBeverage,
+    sqlx::query_as::<_, KnownBeverage>(
         r#"
-        SELECT id, brand_name as "brand_name!", product_name, class_type as "class_type!", beverage_category as "beverage_category!",
-               abv::float8 as "abv!", standard_size_ml, country_of_origin, producer,
-               is_verified as "is_verified!", source as "source!", source_url, notes, created_at as "created_at!", updated_at as "updated_at!"
+        SELECT id, brand_name, product_name, class_type, beverage_category,
+               abv::float8 as abv, standard_size_ml, country_of_origin, producer,
+               is_verified, source, source_url, notes, created_at, updated_at, last_verified_at, ttb_cola_status,
+               ttb_cola_record_id, abv_source
         FROM known_beverages
         WHERE LOWER(brand_name) = LOWER($1)
           AND LOWER(class_type) = LOWER($2)
-        ORDER BY is_verified DESC, abv ASC
+          AND retired_at IS NULL
+        ORDER BY is_verified DESC, COALESCE(ttb_cola_status <> 'approved', FALSE) ASC,
+                 abv_source = 'measured' DESC, abv ASC
         LIMIT 10
         "#,
-        brand,
-        class_type
     )
+    .bind(brand)
+    .bind(class_type)
     .fetch_all(pool)
     .await
 }
 
-/// Find known beverages by brand only (for fuzzy class matching)
-pub async fn find_known_beverage_by_brand(
+/// Find the top-N known beverages with brands similar to `brand` (pg_trgm).
+///
+/// Uses the `idx_known_beverages_brand_trgm` index via the `%` operator (which
+/// applies pg_trgm's own 0.3 similarity limit), then filters on `min_similarity`.
+/// Results are ordered by similarity, verified entries first on ties.
+pub async fn find_similar_brands(
     pool: &PgPool,
     brand: &str,
-) -> Result<Vec<KnownBeverage>, sqlx::Error> {
-    sqlx::query_as!(
-        KnownBeverage,
+    min_similarity: f64,
+    limit: i64,
+) -> Result<Vec<SimilarBeverage>, sqlx::Error> {
+    // Untyped query: similarity() needs the pg_trgm extension at compile time otherwise
+    sqlx::query_as::<_, SimilarBeverage>(
         r#"
-        SELECT id, brand_name as "brand_name!", product_name, class_type as "class_type!", beverage_category as "beverage_category!",
-               abv::float8 as "abv!", standard_size_ml, country_of_origin, producer,
-               is_verified as "is_verified!", source as "source!", source_url, notes, created_at as "created_at!", updated_at as "updated_at!"
+        SELECT id, brand_name, product_name, class_type, beverage_category,
+               abv::float8 as abv, standard_size_ml, country_of_origin, producer,
+               is_verified, source, source_url, notes, created_at, updated_at, last_verified_at, ttb_cola_status,
+               ttb_cola_record_id, abv_source,
+               similarity(LOWER(brand_name), LOWER($1))::float8 as similarity
         FROM known_beverages
-        WHERE LOWER(brand_name) = LOWER($1)
-        ORDER BY is_verified DESC
-        LIMIT 10
+        WHERE LOWER(brand_name) % LOWER($1)
+          AND similarity(LOWER(brand_name), LOWER($1)) >= $2
+          AND retired_at IS NULL
+        ORDER BY similarity DESC, is_verified DESC
+        LIMIT $3
         "#,
-        brand
     )
+    .bind(brand)
+    .bind(min_similarity as f32)
+    .bind(limit)
     .fetch_all(pool)
     .await
 }
@@ -67,7 +87,7 @@ pub fn is_cache_stale(created_at: DateTime<Utc>, threshold_days: i64) -> bool {
 /// Find known beverage with cache freshness information
 ///
 /// Returns tuple of (beverage, is_stale) where is_stale indicates if the cache entry
-/// is older than 30 days and should be refreshed.
+/// was last verified more than `staleness_threshold_days` ago and should be refreshed.
 pub async fn find_known_beverage_with_staleness(
     pool: &PgPool,
     brand: &str,
@@ -77,7 +97,7 @@ pub async fn find_known_beverage_with_staleness(
     let beverages = find_known_beverage(pool, brand, class_type).await?;
 
     if let Some(beverage) = beverages.first() {
-        let is_stale = is_cache_stale(beverage.created_at, staleness_threshold_days);
+        let is_stale = is_cache_stale(beverage.last_verified_at, staleness_threshold_days);
         Ok(Some((beverage.clone(), is_stale)))
     } else {
         Ok(None)
@@ -166,33 +186,84 @@ fn infer_category_from_class(class_type: &str) -> String {
 /// Upsert a single TTB COLA record into the known_beverages cache.
 ///
 /// Uses INSERT...ON CONFLICT DO UPDATE to update existing entries with fresh TTB data.
-/// The unique constraint is on (LOWER(brand_name), LOWER(COALESCE(product_name, '')), abv).
+/// The record itself is stored in `ttb_cola_records` and the cached row is keyed
+/// on it (one row per COLA, whatever its ABV).
+/// When the record's detail page was fetched, its alcohol content replaces the
+/// inferred ABV (`abv_source` tells which one the row holds), the applicant
+/// becomes the producer and its approval status is copied to the row.
+///
+/// Returns None if the record has neither an approved nor an inferred ABV: it
+/// is kept in `ttb_cola_records` but there is nothing to cross-check a label
+/// against, so no beverage is cached.
 pub async fn upsert_from_ttb_cola(
     pool: &PgPool,
     record: &TtbColaRecord,
-) -> Result<KnownBeverage, sqlx::Error> {
-    let product_name = record.fanciful_name.clone();
-    let abv = record.inferred_abv.unwrap_or(0.0);
-    let notes = format!(
-        "TTB COLA ID: {}, Permit: {}, Origin: {} ({})",
-        record.ttb_id, record.permit_no, record.origin_desc, record.origin_code
+) -> Result<Option<KnownBeverage>, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let row = cache_ttb_cola_record(&mut tx, record).await?;
+    tx.commit().await?;
+
+    info!(
+        brand = %record.brand_name,
+        ttb_id = %record.ttb_id,
+        cached = row.is_some(),
+        "Cached TTB COLA record"
     );
 
-    // Use untyped query to handle expression-based ON CONFLICT
-    let row = sqlx::query_as::<_, KnownBeverage>(
+    Ok(row.map(|row| row.beverage))
+}
+
+/// Cache a COLA (its `ttb_cola_records` row and beverage) on `conn`; see
+/// `upsert_from_ttb_cola`.
+pub async fn cache_ttb_cola_record(
+    conn: &mut PgConnection,
+    record: &TtbColaRecord,
+) -> Result<Option<UpsertedBeverage>, sqlx::Error> {
+    let cola_id = upsert_ttb_cola_record(&mut *conn, record).await?;
+
+    let Some(abv) = record.reference_abv() else {
+        return Ok(None);
+    };
+    let product_name = record.fanciful_name.clone();
+    let producer = record.detail.as_ref().and_then(|d| d.applicant_name.clone());
+    let notes = record.cache_notes();
+
+    // Keyed on the COLA (partial unique index). A measured ABV already on the
+    // row is kept when the COLA is seen again without its detail page.
+    sqlx::query_as::<_, UpsertedBeverage>(
         r#"
         INSERT INTO known_beverages
-            (brand_name, product_name, class_type, beverage_category, abv, source, source_url, notes)
-        VALUES ($1, $2, $3, $4, $5, 'ttb_cola', $6, $7)
-        ON CONFLICT (LOWER(brand_name), LOWER(COALESCE(product_name, '')), abv)
+            (brand_name, product_name, class_type, beverage_category, abv, source, source_url, notes,
+             producer, ttb_cola_status, ttb_cola_record_id, abv_source)
+        VALUES ($1, $2, $3, $4, $5, 'ttb_cola', $6, $7, $8, $9, $10, $11)
+        ON CONFLICT (ttb_cola_record_id) WHERE ttb_cola_record_id IS NOT NULL
         DO UPDATE SET
+            brand_name = EXCLUDED.brand_name,
+            product_name = EXCLUDED.product_name,
+            class_type = EXCLUDED.class_type,
+            beverage_category = EXCLUDED.beverage_category,
             source = 'ttb_cola',
             source_url = EXCLUDED.source_url,
             notes = EXCLUDED.notes,
+            producer = COALESCE(EXCLUDED.producer, known_beverages.producer),
+            ttb_cola_status = COALESCE(EXCLUDED.ttb_cola_status, known_beverages.ttb_cola_status),
+            abv = CASE
+                WHEN EXCLUDED.abv_source = 'inferred' AND known_beverages.abv_source = 'measured'
+                    THEN known_beverages.abv
+                ELSE EXCLUDED.abv
+            END,
+            abv_source = CASE
+                WHEN known_beverages.abv_source = 'measured' THEN known_beverages.abv_source
+                ELSE EXCLUDED.abv_source
+            END,
+            last_verified_at = NOW(),
+            retired_at = NULL,
             updated_at = NOW()
         RETURNING id, brand_name, product_name, class_type, beverage_category,
                   abv::float8 as abv, standard_size_ml, country_of_origin, producer,
-                  is_verified, source, source_url, notes, created_at, updated_at
+                  is_verified, source, source_url, notes, created_at, updated_at, last_verified_at, ttb_cola_status,
+                  ttb_cola_record_id, abv_source,
+                  (xmax = 0) AS inserted
         "#,
     )
     .bind(&record.brand_name)
@@ -202,32 +273,198 @@ pub async fn upsert_from_ttb_cola(
     .bind(abv)
     .bind(&record.source_url)
     .bind(&notes)
-    .fetch_one(pool)
+    .bind(&producer)
+    .bind(record.approval_status())
+    .bind(cola_id)
+    .bind(record.abv_source())
+    .fetch_one(conn)
+    .await
+    .map(Some)
+}
+
+/// Insert or update a scraped COLA in `ttb_cola_records`, keyed on its TTB ID.
+///
+/// Columns the record doesn't have (empty search result cells, detail page not
+/// fetched) keep their stored values. Returns the row id.
+pub async fn upsert_ttb_cola_record<'e>(
+    executor: impl PgExecutor<'e>,
+    record: &TtbColaRecord,
+) -> Result<Uuid, sqlx::Error> {
+    let non_empty = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+
+    let (id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO ttb_cola_records
+            (ttb_id, permit_no, serial_number, completed_date, fanciful_name, brand_name, origin_code,
+             origin_desc, class_type_code, class_type_desc, beverage_category, inferred_abv, source_url,
+             status, detail)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+        ON CONFLICT (ttb_id)
+        DO UPDATE SET
+            permit_no = COALESCE(EXCLUDED.permit_no, ttb_cola_records.permit_no),
+            serial_number = COALESCE(EXCLUDED.serial_number, ttb_cola_records.serial_number),
+            completed_date = COALESCE(EXCLUDED.completed_date, ttb_cola_records.completed_date),
+            fanciful_name = COALESCE(EXCLUDED.fanciful_name, ttb_cola_records.fanciful_name),
+            brand_name = EXCLUDED.brand_name,
+            origin_code = COALESCE(EXCLUDED.origin_code, ttb_cola_records.origin_code),
+            origin_desc = COALESCE(EXCLUDED.origin_desc, ttb_cola_records.origin_desc),
+            class_type_code = COALESCE(EXCLUDED.class_type_code, ttb_cola_records.class_type_code),
+            class_type_desc = EXCLUDED.class_type_desc,
+            beverage_category = EXCLUDED.beverage_category,
+            inferred_abv = EXCLUDED.inferred_abv,
+            source_url = EXCLUDED.source_url,
+            status = COALESCE(EXCLUDED.status, ttb_cola_records.status),
+            detail = COALESCE(EXCLUDED.detail, ttb_cola_records.detail)
+        RETURNING id
+        "#,
+    )
+    .bind(&record.ttb_id)
+    .bind(non_empty(&record.permit_no))
+    .bind(non_empty(&record.serial_number))
+    .bind(record.completed_date)
+    .bind(&record.fanciful_name)
+    .bind(&record.brand_name)
+    .bind(non_empty(&record.origin_code))
+    .bind(non_empty(&record.origin_desc))
+    .bind(non_empty(&record.class_type_code))
+    .bind(&record.class_type_desc)
+    .bind(&record.beverage_category)
+    .bind(record.inferred_abv)
+    .bind(&record.source_url)
+    .bind(record.approval_status())
+    .bind(record.detail.as_ref().map(Json))
+    .fetch_one(executor)
     .await?;
 
-    info!(
-        brand = %record.brand_name,
-        ttb_id = %record.ttb_id,
-        "Cached TTB COLA record in known_beverages"
-    );
+    Ok(id)
+}
 
-    Ok(row)
+/// `ttb_cola_records` columns, read back into a `TtbColaRecord`.
+#[derive(sqlx::FromRow)]
+struct TtbColaRecordRow {
+    ttb_id: String,
+    permit_no: Option<String>,
+    serial_number: Option<String>,
+    completed_date: Option<NaiveDate>,
+    fanciful_name: Option<String>,
+    brand_name: String,
+    origin_code: Option<String>,
+    origin_desc: Option<String>,
+    class_type_code: Option<String>,
+    class_type_desc: String,
+    beverage_category: String,
+    inferred_abv: Option<f64>,
+    source_url: String,
+    detail: Option<Json<TtbColaDetail>>,
+}
+
+impl From<TtbColaRecordRow> for TtbColaRecord {
+    fn from(row: TtbColaRecordRow) -> Self {
+        Self {
+            ttb_id: row.ttb_id,
+            permit_no: row.permit_no.unwrap_or_default(),
+            serial_number: row.serial_number.unwrap_or_default(),
+            completed_date: row.completed_date,
+            fanciful_name: row.fanciful_name,
+            brand_name: row.brand_name,
+            origin_code: row.origin_code.unwrap_or_default(),
+            origin_desc: row.origin_desc.unwrap_or_default(),
+            class_type_code: row.class_type_code.unwrap_or_default(),
+            class_type_desc: row.class_type_desc,
+            source_url: row.source_url,
+            inferred_abv: row.inferred_abv,
+            beverage_category: row.beverage_category,
+            detail: row.detail.map(|Json(detail)| detail),
+        }
+    }
+}
+
+/// A stored COLA and the non-retired beverage cached from it, if any.
+#[derive(sqlx::FromRow)]
+struct StoredTtbColaRow {
+    #[sqlx(flatten)]
+    record: TtbColaRecordRow,
+    beverage_id: Option<Uuid>,
+}
+
+/// Find stored COLAs for a brand (case-insensitive), most recently completed
+/// first, with the id of the beverage cached from each.
+pub async fn find_ttb_cola_records_by_brand(
+    pool: &PgPool,
+    brand: &str,
+    limit: i64,
+) -> Result<Vec<(TtbColaRecord, Option<Uuid>)>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, StoredTtbColaRow>(
+        r#"
+        SELECT r.ttb_id, r.permit_no, r.serial_number, r.completed_date, r.fanciful_name, r.brand_name,
+               r.origin_code, r.origin_desc, r.class_type_code, r.class_type_desc, r.beverage_category,
+               r.inferred_abv::float8 as inferred_abv, r.source_url, r.detail,
+               kb.id as beverage_id
+        FROM ttb_cola_records r
+        LEFT JOIN known_beverages kb ON kb.ttb_cola_record_id = r.id AND kb.retired_at IS NULL
+        WHERE LOWER(r.brand_name) = LOWER($1)
+        ORDER BY r.completed_date DESC NULLS LAST
+        LIMIT $2
+        "#,
+    )
+    .bind(brand)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| (row.record.into(), row.beverage_id)).collect())
+}
+
+/// List the stored COLAs that have a non-retired cached beverage, for export.
+pub async fn list_cached_ttb_cola_records(pool: &PgPool) -> Result<Vec<TtbColaRecord>, sqlx::Error> {
+    let rows = sqlx::query_as::<_, TtbColaRecordRow>(
+        r#"
+        SELECT r.ttb_id, r.permit_no, r.serial_number, r.completed_date, r.fanciful_name, r.brand_name,
+               r.origin_code, r.origin_desc, r.class_type_code, r.class_type_desc, r.beverage_category,
+               r.inferred_abv::float8 as inferred_abv, r.source_url, r.detail
+        FROM ttb_cola_records r
+        JOIN known_beverages kb ON kb.ttb_cola_record_id = r.id AND kb.retired_at IS NULL
+        ORDER BY LOWER(r.brand_name), r.ttb_id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(TtbColaRecord::from).collect())
 }
 
 /// Batch upsert TTB COLA records into the known_beverages cache.
 ///
-/// Processes each record individually with per-record error handling.
-/// Individual failures are logged as warnings but don't abort the batch.
+/// The batch is written in one transaction, each record (its
+/// `ttb_cola_records` row and cached beverage) under its own
+/// savepoint: a failing record is logged, rolled back and counted as skipped
+/// without aborting the rest.
 pub async fn upsert_batch_from_ttb_cola(
     pool: &PgPool,
     records: &[TtbColaRecord],
-) -> Result<Vec<KnownBeverage>, sqlx::Error> {
-    let mut cached = Vec::with_capacity(records.len());
+) -> Result<TtbColaBatchUpsert, sqlx::Error> {
+    let mut result = TtbColaBatchUpsert::default();
 
+    let mut tx = pool.begin().await?;
     for record in records {
-        match upsert_from_ttb_cola(pool, record).await {
-            Ok(beverage) => cached.push(beverage),
+        let mut savepoint = sqlx::Connection::begin(&mut *tx).await?;
+        match cache_ttb_cola_record(&mut savepoint, record).await {
+            Ok(None) => {
+                savepoint.commit().await?;
+                result.uncached += 1;
+            }
+            Ok(Some(row)) => {
+                savepoint.commit().await?;
+                if row.inserted {
+                    result.inserted += 1;
+                } else {
+                    result.updated += 1;
+                }
+                result.cached.insert(record.ttb_id.clone(), row.beverage);
+            }
             Err(e) => {
+                savepoint.rollback().await?;
+                result.skipped += 1;
                 warn!(
                     brand = %record.brand_name,
                     ttb_id = %record.ttb_id,
@@ -237,36 +474,370 @@ pub async fn upsert_batch_from_ttb_cola(
             }
         }
     }
+    tx.commit().await?;
 
     info!(
-        cached = cached.len(),
+        inserted = result.inserted,
+        updated = result.updated,
+        skipped = result.skipped,
+        uncached = result.uncached,
         total = records.len(),
         "Batch cached TTB COLA records"
     );
 
-    Ok(cached)
+    Ok(result)
+}
+
+/// Claim up to `limit` stale TTB COLA rows for refresh.
+///
+/// Selects non-retired `source = 'ttb_cola'` rows last verified more than
+/// `stale_after_days` ago, oldest first, skipping rows attempted within the
+/// last `retry_after_hours`. Claiming stamps `ttb_cola_refreshed_at`, so
+/// concurrent workers (via SKIP LOCKED) and later runs don't pick them again
+/// until the retry window passes.
+pub async fn claim_stale_ttb_cola_beverages(
+    pool: &PgPool,
+    stale_after_days: i64,
+    retry_after_hours: i64,
+    limit: i64,
+) -> Result<Vec<KnownBeverage>, sqlx::Error> {
+    sqlx::query_as::<_, KnownBeverage>(
+        r#"
+        UPDATE known_beverages
+        SET ttb_cola_refreshed_at = NOW()
+        WHERE id IN (
+            SELECT id FROM known_beverages
+            WHERE source = 'ttb_cola'
+              AND retired_at IS NULL
+              AND last_verified_at < NOW() - make_interval(days => $1::int)
+              AND (ttb_cola_refreshed_at IS NULL
+                   OR ttb_cola_refreshed_at < NOW() - make_interval(hours => $2::int))
+            ORDER BY last_verified_at ASC
+            LIMIT $3
+            FOR UPDATE SKIP LOCKED
+        )
+        RETURNING id, brand_name, product_name, class_type, beverage_category,
+                  abv::float8 as abv, standard_size_ml, country_of_origin, producer,
+                  is_verified, source, source_url, notes, created_at, updated_at, last_verified_at, ttb_cola_status,
+                  ttb_cola_record_id, abv_source
+        "#,
+    )
+    .bind(stale_after_days)
+    .bind(retry_after_hours)
+    .bind(limit)
+    .fetch_all(pool)
+    .await
+}
+
+/// Update a cached row from a freshly fetched TTB COLA record.
+///
+/// Stores the record in `ttb_cola_records`, links it, and marks the row
+/// verified now with outcome `refreshed`. The ABV becomes the record's
+/// approved alcohol content when known, else its inferred value.
+pub async fn refresh_ttb_cola_beverage(
+    pool: &PgPool,
+    id: Uuid,
+    record: &TtbColaRecord,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    let cola_id = upsert_ttb_cola_record(&mut *tx, record).await?;
+
+    sqlx::query(
+        r#"
+        UPDATE known_beverages
+        SET brand_name = $2,
+            class_type = $3,
+            beverage_category = $4,
+            abv = COALESCE($5, abv),
+            abv_source = CASE WHEN $5 IS NULL THEN abv_source ELSE $6 END,
+            source_url = $7,
+            producer = COALESCE($8, producer),
+            ttb_cola_status = COALESCE($9, ttb_cola_status),
+            ttb_cola_record_id = $10,
+            last_verified_at = NOW(),
+            ttb_cola_refreshed_at = NOW(),
+            ttb_cola_refresh_outcome = 'refreshed',
+            ttb_cola_refresh_error = NULL
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(&record.brand_name)
+    .bind(&record.class_type_desc)
+    .bind(&record.beverage_category)
+    .bind(record.reference_abv())
+    .bind(record.abv_source())
+    .bind(&record.source_url)
+    .bind(record.detail.as_ref().and_then(|d| d.applicant_name.clone()))
+    .bind(record.approval_status())
+    .bind(cola_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await
+}
+
+/// Record a refresh that did not update the row: `retired` (TTB no longer has
+/// the COLA; the row stops matching) or `failed` (retried after the window).
+pub async fn record_ttb_cola_refresh_outcome(
+    pool: &PgPool,
+    id: Uuid,
+    outcome: TtbColaRefreshOutcome,
+    error: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE known_beverages
+        SET ttb_cola_refreshed_at = NOW(),
+            ttb_cola_refresh_outcome = $2,
+            ttb_cola_refresh_error = $3,
+            retired_at = CASE WHEN $2 = 'retired'::ttb_cola_refresh_outcome THEN NOW() ELSE retired_at END
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(outcome)
+    .bind(error)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Find the cached TTB COLA miss for a brand search, with freshness information
+///
+/// Returns tuple of (miss, is_stale) where is_stale indicates the miss was last
+/// confirmed more than `ttl_days` ago and TTB should be queried again.
+pub async fn find_ttb_cola_miss_with_staleness(
+    pool: &PgPool,
+    brand: &str,
+    category: &str,
+    ttl_days: i64,
+) -> Result<Option<(TtbColaMiss, bool)>, sqlx::Error> {
+    let miss = sqlx::query_as::<_, TtbColaMiss>(
+        r#"
+        SELECT id, brand_key, category, searched_brand, miss_count, created_at, checked_at
+        FROM ttb_cola_lookup_cache
+        WHERE brand_key = $1 AND category = $2
+        "#,
+    )
+    .bind(ttb_cola::brand_search_key(brand))
+    .bind(category)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(miss.map(|miss| {
+        let is_stale = is_cache_stale(miss.checked_at, ttl_days);
+        (miss, is_stale)
+    }))
+}
+
+/// Record that a TTB COLA brand search returned no records.
+///
+/// Repeated misses refresh `checked_at` and increment `miss_count`.
+pub async fn record_ttb_cola_miss(
+    pool: &PgPool,
+    brand: &str,
+    category: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO ttb_cola_lookup_cache (brand_key, category, searched_brand)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (brand_key, category)
+        DO UPDATE SET
+            searched_brand = EXCLUDED.searched_brand,
+            miss_count = ttb_cola_lookup_cache.miss_count + 1,
+            checked_at = NOW()
+        "#,
+    )
+    .bind(ttb_cola::brand_search_key(brand))
+    .bind(category)
+    .bind(brand.trim())
+    .execute(pool)
+    .await?;
+
+    info!(brand = %brand, category = %category, "Cached TTB COLA miss");
+    Ok(())
+}
+
+/// Remove cached TTB COLA misses.
+///
+/// With a brand, removes that brand's misses (all categories); without one,
+/// removes every miss, or only those older than `older_than_days` if given.
+/// Returns the number of entries removed.
+pub async fn purge_ttb_cola_misses(
+    pool: &PgPool,
+    brand: Option<&str>,
+    older_than_days: Option<i64>,
+) -> Result<u64, sqlx::Error> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM ttb_cola_lookup_cache
+        WHERE ($1::text IS NULL OR brand_key = $1)
+          AND ($2::bigint IS NULL OR checked_at < NOW() - make_interval(days => $2::int))
+        "#,
+    )
+    .bind(brand.map(ttb_cola::brand_search_key))
+    .bind(older_than_days)
+    .execute(pool)
+    .await?;
+
+    info!(
+        brand = brand.unwrap_or("*"),
+        purged = result.rows_affected(),
+        "Purged TTB COLA negative cache"
+    );
+
+    Ok(result.rows_affected())
+}
+
+/// Find the row an import would conflict with on `idx_known_beverages_unique`
+/// (case-insensitive brand and product name, exact ABV, not cached from a
+/// COLA), retired or not.
+pub async fn find_known_beverage_by_key(
+    pool: &PgPool,
+    brand: &str,
+    product_name: Option<&str>,
+    abv: f64,
+) -> Result<Option<KnownBeverage>, sqlx::Error> {
+    sqlx::query_as::<_, KnownBeverage>(
+        r#"
+        SELECT id, brand_name, product_name, class_type, beverage_category,
+               abv::float8 as abv, standard_size_ml, country_of_origin, producer,
+               is_verified, source, source_url, notes, created_at, updated_at, last_verified_at, ttb_cola_status,
+               ttb_cola_record_id, abv_source
+        FROM known_beverages
+        WHERE LOWER(brand_name) = LOWER($1)
+          AND LOWER(COALESCE(product_name, '')) = LOWER(COALESCE($2, ''))
+          AND abv = $3::numeric(4,2)
+          AND ttb_cola_record_id IS NULL
+        "#,
+    )
+    .bind(brand)
+    .bind(product_name)
+    .bind(abv)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Find the beverage cached from the COLA with `ttb_id`, retired or not.
+pub async fn find_ttb_cola_beverage(pool: &PgPool, ttb_id: &str) -> Result<Option<KnownBeverage>, sqlx::Error> {
+    sqlx::query_as::<_, KnownBeverage>(
+        r#"
+        SELECT kb.id, kb.brand_name, kb.product_name, kb.class_type, kb.beverage_category,
+               kb.abv::float8 as abv, kb.standard_size_ml, kb.country_of_origin, kb.producer,
+               kb.is_verified, kb.source, kb.source_url, kb.notes, kb.created_at, kb.updated_at,
+               kb.last_verified_at, kb.ttb_cola_status, kb.ttb_cola_record_id, kb.abv_source
+        FROM known_beverages kb
+        JOIN ttb_cola_records r ON r.id = kb.ttb_cola_record_id
+        WHERE r.ttb_id = $1
+        "#,
+    )
+    .bind(ttb_id)
+    .fetch_optional(pool)
+    .await
+}
+
+/// Insert or update an imported reference row, keyed on `idx_known_beverages_unique`.
+///
+/// Imported values replace stored ones, except that empty optional fields keep
+/// the stored value and `is_verified` is never cleared. The row is marked as
+/// verified now and un-retired. Returns `true` if a new row was inserted.
+pub async fn upsert_known_beverage<'e>(
+    executor: impl PgExecutor<'e>,
+    beverage: &NewKnownBeverage,
+) -> Result<bool, sqlx::Error> {
+    let (inserted,): (bool,) = sqlx::query_as(
+        r#"
+        INSERT INTO known_beverages
+            (brand_name, product_name, class_type, beverage_category, abv, standard_size_ml,
+             country_of_origin, producer, is_verified, source, source_url, notes, abv_source)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        ON CONFLICT (LOWER(brand_name), LOWER(COALESCE(product_name, '')), abv)
+            WHERE ttb_cola_record_id IS NULL
+        DO UPDATE SET
+            class_type = EXCLUDED.class_type,
+            beverage_category = EXCLUDED.beverage_category,
+            standard_size_ml = COALESCE(EXCLUDED.standard_size_ml, known_beverages.standard_size_ml),
+            country_of_origin = COALESCE(EXCLUDED.country_of_origin, known_beverages.country_of_origin),
+            producer = COALESCE(EXCLUDED.producer, known_beverages.producer),
+            is_verified = known_beverages.is_verified OR EXCLUDED.is_verified,
+            source = EXCLUDED.source,
+            source_url = COALESCE(EXCLUDED.source_url, known_beverages.source_url),
+            notes = COALESCE(EXCLUDED.notes, known_beverages.notes),
+            abv_source = EXCLUDED.abv_source,
+            last_verified_at = NOW(),
+            retired_at = NULL
+        RETURNING (xmax = 0) AS inserted
+        "#,
+    )
+    .bind(beverage.brand_name.trim())
+    .bind(beverage.product_name.as_deref().map(str::trim))
+    .bind(&beverage.class_type)
+    .bind(&beverage.beverage_category)
+    .bind(beverage.abv)
+    .bind(beverage.standard_size_ml)
+    .bind(&beverage.country_of_origin)
+    .bind(&beverage.producer)
+    .bind(beverage.is_verified)
+    .bind(&beverage.source)
+    .bind(&beverage.source_url)
+    .bind(&beverage.notes)
+    .bind(beverage.abv_source)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(inserted)
+}
+
+/// List non-retired known beverages for export, optionally from one source.
+///
+/// Rows cached from a COLA are left out: they are keyed on the COLA and
+/// exported with `list_cached_ttb_cola_records` instead.
+pub async fn list_known_beverages(
+    pool: &PgPool,
+    source: Option<&str>,
+) -> Result<Vec<KnownBeverage>, sqlx::Error> {
+    sqlx::query_as::<_, KnownBeverage>(
+        r#"
+        SELECT id, brand_name, product_name, class_type, beverage_category,
+               abv::float8 as abv, standard_size_ml, country_of_origin, producer,
+               is_verified, source, source_url, notes, created_at, updated_at, last_verified_at, ttb_cola_status,
+               ttb_cola_record_id, abv_source
+        FROM known_beverages
+        WHERE retired_at IS NULL
+          AND ttb_cola_record_id IS NULL
+          AND ($1::text IS NULL OR source = $1)
+        ORDER BY LOWER(brand_name), LOWER(COALESCE(product_name, '')), abv
+        "#,
+    )
+    .bind(source)
+    .fetch_all(pool)
+    .await
 }
 
-/// Record match history for analytics
+/// Record match history (including ranked candidates) for analytics
 pub async fn record_match_history(
     pool: &PgPool,
     match_history: NewMatchHistory,
 ) -> Result<Uuid, sqlx::Error> {
     let id = Uuid::new_v4();
 
-    sqlx::query!(
+    sqlx::query(
         r#"
         INSERT INTO beverage_match_history
-            (id, job_id, matched_beverage_id, match_type, match_confidence, abv_deviation)
-        VALUES ($1, $2, $3, $4, $5::float8, $6::float8)
+            (id, job_id, matched_beverage_id, match_type, match_confidence, abv_deviation, candidates)
+        VALUES ($1, $2, $3, $4, $5::float8, $6::float8, $7)
         "#,
-        id,
-        match_history.job_id,
-        match_history.matched_beverage_id,
-        match_history.match_type,
-        match_history.match_confidence,
-        match_history.abv_deviation
     )
+    .bind(id)
+    .bind(match_history.job_id)
+    .bind(match_history.matched_beverage_id)
+    .bind(match_history.match_type)
+    .bind(match_history.match_confidence)
+    .bind(match_history.abv_deviation)
+    .bind(Json(&match_history.candidates))
     .execute(pool)
     .await?;
 
@@ -1,36 +1,79 @@
+use sqlx::postgres::PgRow;
+use sqlx::types::Json;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
-use crate::models::job::{JobStatus, VerificationJob};
+use crate::models::job::{
+    EncryptedExtraction, ExtractionProvenance, JobStatus, VerificationInputs, VerificationJob,
+    VerificationRevision, VerificationTrigger,
+};
+use crate::models::label::VerificationResult;
 
 /// Insert a new verification job
 pub async fn create_job(
     pool: &PgPool,
     image_key: &str,
     user_id: Option<&str>,
+    inputs: &VerificationInputs,
 ) -> Result<VerificationJob, sqlx::Error> {
     let row = sqlx::query(
         r#"
-        INSERT INTO verification_jobs (status, image_key, user_id)
-        VALUES ('pending', $1, $2)
+        INSERT INTO verification_jobs
+            (status, image_key, user_id, expected_brand, expected_class, expected_abv, ttb_id,
+             profile_name, tenant, profile_overrides)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         RETURNING id, status, image_key, created_at, updated_at, retry_count, error,
-                  extracted_fields, verification_result
+                  verification_result, ocr_model, ocr_prompt_name, ocr_prompt_version,
+                  expected_brand, expected_class, expected_abv::float8 AS expected_abv, ttb_id,
+                  profile_name, tenant, profile_overrides
         "#,
     )
+    .bind(JobStatus::Pending)
     .bind(image_key)
     .bind(user_id)
+    .bind(&inputs.expected_brand)
+    .bind(&inputs.expected_class)
+    .bind(inputs.expected_abv)
+    .bind(&inputs.ttb_id)
+    .bind(&inputs.profile)
+    .bind(&inputs.tenant)
+    .bind(&inputs.profile_overrides)
     .fetch_one(pool)
     .await?;
 
+    job_from_row(&row)
+}
+
+/// Map a `verification_jobs` row selected with the columns above.
+fn job_from_row(row: &PgRow) -> Result<VerificationJob, sqlx::Error> {
+    let extraction = match row.try_get::<Option<String>, _>("ocr_model")? {
+        Some(model) => Some(ExtractionProvenance {
+            model,
+            prompt_name: row.try_get("ocr_prompt_name")?,
+            prompt_version: row.try_get("ocr_prompt_version")?,
+        }),
+        None => None,
+    };
+
     Ok(VerificationJob {
         id: row.try_get("id")?,
-        status: JobStatus::Pending,
+        status: row.try_get("status")?,
         image_key: row.try_get("image_key")?,
         created_at: row.try_get("created_at")?,
         updated_at: row.try_get("updated_at")?,
         result: row.try_get("verification_result")?,
         error: row.try_get("error")?,
         retry_count: row.try_get("retry_count")?,
+        extraction,
+        inputs: VerificationInputs {
+            expected_brand: row.try_get("expected_brand")?,
+            expected_class: row.try_get("expected_class")?,
+            expected_abv: row.try_get("expected_abv")?,
+            ttb_id: row.try_get("ttb_id")?,
+            profile: row.try_get("profile_name")?,
+            tenant: row.try_get("tenant")?,
+            profile_overrides: row.try_get("profile_overrides")?,
+        },
     })
 }
 
@@ -39,7 +82,9 @@ pub async fn get_job(pool: &PgPool, job_id: Uuid) -> Result<Option<VerificationJ
     let row = sqlx::query(
         r#"
         SELECT id, status, image_key, created_at, updated_at, retry_count, error,
-               extracted_fields, verification_result
+               verification_result, ocr_model, ocr_prompt_name, ocr_prompt_version,
+               expected_brand, expected_class, expected_abv::float8 AS expected_abv, ttb_id,
+               profile_name, tenant, profile_overrides
         FROM verification_jobs
         WHERE id = $1
         "#,
@@ -48,30 +93,7 @@ pub async fn get_job(pool: &PgPool, job_id: Uuid) -> Result<Option<VerificationJ
     .fetch_optional(pool)
     .await?;
 
-    Ok(match row {
-        Some(r) => {
-            let status_str: String = r.try_get("status")?;
-            let status = match status_str.as_str() {
-                "pending" => JobStatus::Pending,
-                "processing" => JobStatus::Processing,
-                "completed" => JobStatus::Completed,
-                "failed" => JobStatus::Failed,
-                _ => JobStatus::Pending,
-            };
-
-            Some(VerificationJob {
-                id: r.try_get("id")?,
-                status,
-                image_key: r.try_get("image_key")?,
-                created_at: r.try_get("created_at")?,
-                updated_at: r.try_get("updated_at")?,
-                result: r.try_get("verification_result")?,
-                error: r.try_get("error")?,
-                retry_count: r.try_get("retry_count")?,
-            })
-        }
-        None => None,
-    })
+    row.as_ref().map(job_from_row).transpose()
 }
 
 /// Update job status
@@ -80,23 +102,16 @@ pub async fn update_job_status(
     job_id: Uuid,
     status: JobStatus,
 ) -> Result<(), sqlx::Error> {
-    let status_str = match status {
-        JobStatus::Pending => "pending",
-        JobStatus::Processing => "processing",
-        JobStatus::Completed => "completed",
-        JobStatus::Failed => "failed",
-    };
-
     sqlx::query(
         r#"
         UPDATE verification_jobs
         SET status = $1,
-            processing_started_at = CASE WHEN $1 = 'processing' THEN NOW() ELSE processing_started_at END,
-            processing_completed_at = CASE WHEN $1 IN ('completed', 'failed') THEN NOW() ELSE processing_completed_at END
+            processing_started_at = CASE WHEN $1 = 'processing'::job_status THEN NOW() ELSE processing_started_at END,
+            processing_completed_at = CASE WHEN $1 IN ('completed'::job_status, 'failed'::job_status) THEN NOW() ELSE processing_completed_at END
         WHERE id = $2
         "#,
     )
-    .bind(status_str)
+    .bind(status)
     .bind(job_id)
     .execute(pool)
     .await?;
@@ -112,13 +127,6 @@ pub async fn update_job_result(
     result: Option<serde_json::Value>,
     error: Option<&str>,
 ) -> Result<(), sqlx::Error> {
-    let status_str = match status {
-        JobStatus::Pending => "pending",
-        JobStatus::Processing => "processing",
-        JobStatus::Completed => "completed",
-        JobStatus::Failed => "failed",
-    };
-
     sqlx::query(
         r#"
         UPDATE verification_jobs
@@ -129,7 +137,7 @@ pub async fn update_job_result(
         WHERE id = $4
         "#,
     )
-    .bind(status_str)
+    .bind(status)
     .bind(result)
     .bind(error)
     .bind(job_id)
@@ -139,6 +147,205 @@ pub async fn update_job_result(
     Ok(())
 }
 
+/// Record a job's OCR extraction: model, prompt version, encrypted fields and
+/// raw output (plus the other ensemble members' output), and how long the
+/// extraction took.
+pub async fn record_ocr_extraction(
+    pool: &PgPool,
+    job_id: Uuid,
+    provenance: &ExtractionProvenance,
+    encrypted_fields: &[u8],
+    encrypted_raw_output: &[u8],
+    encrypted_ensemble_output: Option<&[u8]>,
+    duration_ms: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE verification_jobs
+        SET ocr_model = $1,
+            ocr_prompt_name = $2,
+            ocr_prompt_version = $3,
+            extracted_fields = $4,
+            ocr_raw_output = $5,
+            ocr_ensemble_output = $6,
+            ocr_duration_ms = $7,
+            ocr_completed_at = NOW(),
+            ocr_parse_error = NULL
+        WHERE id = $8
+        "#,
+    )
+    .bind(&provenance.model)
+    .bind(&provenance.prompt_name)
+    .bind(provenance.prompt_version)
+    .bind(encrypted_fields)
+    .bind(encrypted_raw_output)
+    .bind(encrypted_ensemble_output)
+    .bind(duration_ms)
+    .bind(job_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Store the provenance and raw output (already encrypted) of an OCR call
+/// whose output had no readable label fields, with the parse error.
+///
+/// Any previously stored fields are cleared, so the job isn't validated from
+/// them and a retry runs OCR again.
+pub async fn record_ocr_parse_failure(
+    pool: &PgPool,
+    job_id: Uuid,
+    provenance: &ExtractionProvenance,
+    encrypted_raw_output: &[u8],
+    parse_error: &str,
+    duration_ms: i32,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        UPDATE verification_jobs
+        SET ocr_model = $1,
+            ocr_prompt_name = $2,
+            ocr_prompt_version = $3,
+            extracted_fields = NULL,
+            ocr_raw_output = $4,
+            ocr_ensemble_output = NULL,
+            ocr_duration_ms = $5,
+            ocr_completed_at = NOW(),
+            ocr_parse_error = $6
+        WHERE id = $7
+        "#,
+    )
+    .bind(&provenance.model)
+    .bind(&provenance.prompt_name)
+    .bind(provenance.prompt_version)
+    .bind(encrypted_raw_output)
+    .bind(duration_ms)
+    .bind(parse_error)
+    .bind(job_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Get a job's stored OCR extraction (None if the job has not been through OCR).
+pub async fn get_ocr_extraction(
+    pool: &PgPool,
+    job_id: Uuid,
+) -> Result<Option<EncryptedExtraction>, sqlx::Error> {
+    let row = sqlx::query(
+        r#"
+        SELECT ocr_model, ocr_prompt_name, ocr_prompt_version, extracted_fields,
+               ocr_raw_output, ocr_ensemble_output, ocr_duration_ms, ocr_completed_at
+        FROM verification_jobs
+        WHERE id = $1 AND extracted_fields IS NOT NULL
+        "#,
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await?;
+
+    row.map(|row| {
+        Ok(EncryptedExtraction {
+            provenance: ExtractionProvenance {
+                model: row.try_get("ocr_model")?,
+                prompt_name: row.try_get("ocr_prompt_name")?,
+                prompt_version: row.try_get("ocr_prompt_version")?,
+            },
+            extracted_fields: row.try_get("extracted_fields")?,
+            raw_output: row.try_get("ocr_raw_output")?,
+            ensemble_output: row.try_get("ocr_ensemble_output")?,
+            duration_ms: row.try_get("ocr_duration_ms")?,
+            completed_at: row.try_get("ocr_completed_at")?,
+        })
+    })
+    .transpose()
+}
+
+/// Record a validation of a job as its next revision and make it the job's
+/// current result. Returns the revision number.
+///
+/// A worker revision also marks the job completed; a revalidation keeps the
+/// original processing time.
+pub async fn record_verification(
+    pool: &PgPool,
+    job_id: Uuid,
+    trigger: VerificationTrigger,
+    inputs: &VerificationInputs,
+    result: &VerificationResult,
+) -> Result<i32, sqlx::Error> {
+    let mut tx = pool.begin().await?;
+
+    // Lock the job so concurrent revalidations get distinct revisions
+    sqlx::query("SELECT id FROM verification_jobs WHERE id = $1 FOR UPDATE")
+        .bind(job_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+    let revision: i32 = sqlx::query_scalar(
+        r#"
+        INSERT INTO verification_results
+            (job_id, revision, trigger, expected_brand, expected_class, expected_abv, ttb_id,
+             profile_name, passed, result)
+        SELECT $1, COALESCE(MAX(revision), 0) + 1, $2, $3, $4, $5, $6, $7, $8, $9
+        FROM verification_results
+        WHERE job_id = $1
+        RETURNING revision
+        "#,
+    )
+    .bind(job_id)
+    .bind(trigger)
+    .bind(&inputs.expected_brand)
+    .bind(&inputs.expected_class)
+    .bind(inputs.expected_abv)
+    .bind(&inputs.ttb_id)
+    .bind(&result.profile_name)
+    .bind(result.passed)
+    .bind(Json(result))
+    .fetch_one(&mut *tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE verification_jobs
+        SET status = 'completed',
+            verification_result = $1,
+            error = NULL,
+            processing_completed_at = CASE WHEN $2 = 'worker'::verification_trigger
+                                           THEN NOW() ELSE processing_completed_at END
+        WHERE id = $3
+        "#,
+    )
+    .bind(Json(result))
+    .bind(trigger)
+    .bind(job_id)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(revision)
+}
+
+/// Get every validation of a job, oldest first.
+pub async fn get_verification_history(
+    pool: &PgPool,
+    job_id: Uuid,
+) -> Result<Vec<VerificationRevision>, sqlx::Error> {
+    sqlx::query_as::<_, VerificationRevision>(
+        r#"
+        SELECT revision, trigger, expected_brand, expected_class, expected_abv::float8 AS expected_abv,
+               ttb_id, profile_name, passed, result, created_at
+        FROM verification_results
+        WHERE job_id = $1
+        ORDER BY revision
+        "#,
+    )
+    .bind(job_id)
+    .fetch_all(pool)
+    .await
+}
+
 /// Increment retry count
 pub async fn increment_retry_count(pool: &PgPool, job_id: Uuid) -> Result<i32, sqlx::Error> {
     let row = sqlx::query(
@@ -153,7 +360,7 @@ pub async fn increment_retry_count(pool: &PgPool, job_id: Uuid) -> Result<i32, s
     .fetch_one(pool)
     .await?;
 
-    Ok(row.try_get("retry_count")?)
+    row.try_get("retry_count")
 }
 
 /// Get pending jobs (for queue processor)
@@ -164,7 +371,9 @@ pub async fn get_pending_jobs(
     let rows = sqlx::query(
         r#"
         SELECT id, status, image_key, created_at, updated_at, retry_count, error,
-               extracted_fields, verification_result
+               verification_result, ocr_model, ocr_prompt_name, ocr_prompt_version,
+               expected_brand, expected_class, expected_abv::float8 AS expected_abv, ttb_id,
+               profile_name, tenant, profile_overrides
         FROM verification_jobs
         WHERE status = 'pending'
         ORDER BY created_at ASC
@@ -175,18 +384,5 @@ pub async fn get_pending_jobs(
     .fetch_all(pool)
     .await?;
 
-    rows.into_iter()
-        .map(|r| {
-            Ok(VerificationJob {
-                id: r.try_get("id")?,
-                status: JobStatus::Pending,
-                image_key: r.try_get("image_key")?,
-                created_at: r.try_get("created_at")?,
-                updated_at: r.try_get("updated_at")?,
-                result: r.try_get("verification_result")?,
-                error: r.try_get("error")?,
-                retry_count: r.try_get("retry_count")?,
-            })
-        })
-        .collect()
+    rows.iter().map(job_from_row).collect()
 }
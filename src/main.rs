@@ -1,11 +1,4 @@
-mod app_state;
-mod config;
-mod db;
-mod models;
-mod routes;
-mod services;
-
-use axum::{extract::DefaultBodyLimit, routing::get, routing::post, Router};
+use axum::{extract::DefaultBodyLimit, routing::delete, routing::get, routing::post, Router};
 use axum::response::Html;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use std::sync::Arc;
@@ -14,13 +7,21 @@ use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 use tracing_subscriber::EnvFilter;
 
-use app_state::AppState;
-use config::AppConfig;
-use services::{
-    encryption::EncryptionService,
-    ocr::WorkersAiClient,
-    queue::JobQueue,
-    storage::R2Client,
+use label_verify_hw::{
+    app_state::AppState,
+    config::AppConfig,
+    db, routes,
+    services::{
+        encryption::EncryptionService,
+        ocr::WorkersAiClient,
+        ocr_ensemble,
+        queue::JobQueue,
+        profile::{self, ProfileRegistry},
+        prompts::{self, PromptRegistry},
+        scoring::{self, ScoringModel},
+        storage::R2Client,
+        ttb_cola::{self, TtbColaClient, TtbColaConfig},
+    },
 };
 
 #[tokio::main]
@@ -53,6 +54,10 @@ async fn main() {
         "verification_jobs_total",
         "Total verification jobs submitted"
     );
+    metrics::describe_counter!(
+        "verification_revalidations_total",
+        "Total revalidations of stored extractions"
+    );
     metrics::describe_counter!(
         "verification_jobs_completed",
         "Total verification jobs completed"
@@ -65,6 +70,7 @@ async fn main() {
         "verification_queue_depth",
         "Current number of pending jobs in the queue"
     );
+    ttb_cola::describe_metrics();
 
     // Initialize database connection pool
     tracing::info!("Connecting to PostgreSQL database");
@@ -99,8 +105,49 @@ async fn main() {
 
     // Initialize Workers AI client
     tracing::info!("Initializing Cloudflare Workers AI client");
-    let ocr_client = WorkersAiClient::new(&config.cf_account_id, &config.cf_api_token)
+    let mut ocr_client = WorkersAiClient::new(&config.cf_account_id, &config.cf_api_token)
         .expect("Failed to initialize Workers AI client");
+    if let Some(model) = config.ocr_model.as_deref() {
+        ocr_client = ocr_client.with_model(model);
+    }
+
+    // Install extraction prompt versions
+    prompts::init(
+        PromptRegistry::from_spec(config.ocr_prompt_versions.as_deref())
+            .expect("Invalid OCR_PROMPT_VERSIONS"),
+    );
+
+    // Configure the OCR ensemble (entries without a version use the active prompt)
+    let members = ocr_ensemble::members_from_spec(ocr_client.model(), config.ocr_ensemble.as_deref())
+        .expect("Invalid OCR_ENSEMBLE");
+    ocr_client = ocr_client.with_ensemble(members);
+
+    // Install confidence scoring weights
+    if let Some(spec) = config.confidence_weights.as_deref() {
+        let model = ScoringModel::from_spec(spec).expect("Invalid CONFIDENCE_WEIGHTS");
+        scoring::init(model);
+    }
+
+    // Install validation profiles
+    let profiles = ProfileRegistry::from_specs(
+        config.validation_profiles.as_deref(),
+        config.validation_tenant_profiles.as_deref(),
+        config.validation_default_profile.as_deref(),
+        config.validation_override_limits.as_deref(),
+    )
+    .expect("Invalid validation profile configuration");
+    profile::init(profiles);
+
+    // Install the TTB COLA client (live, record or replay)
+    let ttb_config = TtbColaConfig::from_settings(
+        config.ttb_cola_base_url.as_deref(),
+        config.ttb_cola_mode.as_deref(),
+        config.ttb_cola_fixtures_dir.as_deref(),
+        config.ttb_cola_throttle.as_deref(),
+    )
+    .expect("Invalid TTB COLA configuration");
+    tracing::info!(base_url = %ttb_config.base_url, mode = ?ttb_config.mode, "Initializing TTB COLA client");
+    ttb_cola::init(TtbColaClient::with_config(ttb_config).expect("Failed to initialize TTB COLA client"));
 
     // Create shared application state
     let state = AppState::new(db_pool, r2_client, encryption, queue, ocr_client);
@@ -116,6 +163,18 @@ async fn main() {
             "/api/v1/verify/{job_id}",
             get(routes::verify::get_job_status),
         )
+        .route(
+            "/api/v1/verify/{job_id}/revalidate",
+            post(routes::verify::revalidate_job),
+        )
+        .route(
+            "/api/v1/verify/{job_id}/history",
+            get(routes::verify::get_job_history),
+        )
+        .route(
+            "/api/v1/ttb-cola/misses",
+            delete(routes::ttb_cola::purge_misses),
+        )
         .with_state(state)
         // Prometheus metrics endpoint (separate state)
         .route(
@@ -1,7 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use sqlx::types::chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use strum::{Display, EnumString};
 use uuid::Uuid;
 
+use crate::models::label::{MatchCandidate, MatchType};
+
 /// Known beverage from reference database
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct KnownBeverage {
@@ -20,6 +25,225 @@ pub struct KnownBeverage {
     pub notes: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// When the row was last confirmed against its source (cache staleness).
+    pub last_verified_at: DateTime<Utc>,
+    /// TTB approval status for `ttb_cola` rows, if known.
+    pub ttb_cola_status: Option<ColaStatus>,
+    /// The `ttb_cola_records` row this was cached from.
+    pub ttb_cola_record_id: Option<Uuid>,
+    /// Whether `abv` was measured or inferred from a COLA class/type.
+    pub abv_source: AbvSource,
+}
+
+#[cfg(test)]
+impl KnownBeverage {
+    /// An unverified STONE CREEK table red wine (12.0%, measured) cached from
+    /// a COLA; tests override the fields they care about.
+    pub fn test_fixture() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            brand_name: "STONE CREEK".to_string(),
+            product_name: None,
+            class_type: "TABLE RED WINE".to_string(),
+            beverage_category: "wine".to_string(),
+            abv: 12.0,
+            standard_size_ml: None,
+            country_of_origin: None,
+            producer: None,
+            is_verified: false,
+            source: "ttb_cola".to_string(),
+            source_url: None,
+            notes: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_verified_at: Utc::now(),
+            ttb_cola_status: None,
+            ttb_cola_record_id: None,
+            abv_source: AbvSource::Measured,
+        }
+    }
+}
+
+/// Where a reference ABV came from.
+///
+/// Stored as the Postgres `abv_source` enum.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Display, EnumString, sqlx::Type,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "abv_source", rename_all = "snake_case")]
+pub enum AbvSource {
+    /// From a label, product listing or approved COLA alcohol content.
+    #[default]
+    Measured,
+    /// Typical ABV for the COLA class/type (`infer_abv_from_class_type`).
+    Inferred,
+}
+
+/// TTB approval status of a COLA, from its detail page.
+///
+/// Stored as the Postgres `ttb_cola_status` enum.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Display, EnumString, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "ttb_cola_status", rename_all = "snake_case")]
+pub enum ColaStatus {
+    Approved,
+    /// Voluntarily surrendered by the permittee.
+    Surrendered,
+    /// Revoked by TTB.
+    Revoked,
+    Expired,
+}
+
+impl ColaStatus {
+    /// Parse the status text shown by TTB (e.g. "APPROVED", "SURRENDERED",
+    /// "VOLUNTARILY SURRENDERED"). Returns `None` for anything else.
+    pub fn from_ttb_text(text: &str) -> Option<Self> {
+        let text = text.to_uppercase();
+        if text.contains("REVOKED") {
+            Some(Self::Revoked)
+        } else if text.contains("SURRENDER") {
+            Some(Self::Surrendered)
+        } else if text.contains("EXPIRED") {
+            Some(Self::Expired)
+        } else if text.contains("APPROVED") {
+            Some(Self::Approved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the COLA is a valid, current approval.
+    pub fn is_active(self) -> bool {
+        self == Self::Approved
+    }
+}
+
+/// A `known_beverages` row as imported and exported by `label-verify-admin`.
+///
+/// Rows are identified by `idx_known_beverages_unique`: brand, product name
+/// and ABV (see `dedupe_key`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NewKnownBeverage {
+    pub brand_name: String,
+    #[serde(default)]
+    pub product_name: Option<String>,
+    pub class_type: String,
+    pub beverage_category: String,
+    pub abv: f64,
+    #[serde(default)]
+    pub standard_size_ml: Option<i32>,
+    #[serde(default)]
+    pub country_of_origin: Option<String>,
+    #[serde(default)]
+    pub producer: Option<String>,
+    #[serde(default)]
+    pub is_verified: bool,
+    #[serde(default = "default_import_source")]
+    pub source: String,
+    #[serde(default)]
+    pub source_url: Option<String>,
+    #[serde(default)]
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub abv_source: AbvSource,
+}
+
+fn default_import_source() -> String {
+    "manual".to_string()
+}
+
+impl NewKnownBeverage {
+    /// Key matching `idx_known_beverages_unique`: lowercased brand and product
+    /// name, and ABV in hundredths (the column is `DECIMAL(4,2)`).
+    pub fn dedupe_key(&self) -> (String, String, i64) {
+        (
+            self.brand_name.trim().to_lowercase(),
+            self.product_name.as_deref().unwrap_or("").trim().to_lowercase(),
+            (self.abv * 100.0).round() as i64,
+        )
+    }
+}
+
+impl From<&KnownBeverage> for NewKnownBeverage {
+    fn from(row: &KnownBeverage) -> Self {
+        Self {
+            brand_name: row.brand_name.clone(),
+            product_name: row.product_name.clone(),
+            class_type: row.class_type.clone(),
+            beverage_category: row.beverage_category.clone(),
+            abv: row.abv,
+            standard_size_ml: row.standard_size_ml,
+            country_of_origin: row.country_of_origin.clone(),
+            producer: row.producer.clone(),
+            is_verified: row.is_verified,
+            source: row.source.clone(),
+            source_url: row.source_url.clone(),
+            notes: row.notes.clone(),
+            abv_source: row.abv_source,
+        }
+    }
+}
+
+/// Outcome of the worker's last TTB COLA refresh of a cached row.
+///
+/// Stored as the Postgres `ttb_cola_refresh_outcome` enum.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Display, EnumString, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "ttb_cola_refresh_outcome", rename_all = "snake_case")]
+pub enum TtbColaRefreshOutcome {
+    /// TTB still has the COLA; the row was updated from it.
+    Refreshed,
+    /// TTB no longer has the COLA; the row is no longer matched.
+    Retired,
+    /// The refresh failed (TTB error, missing TTB ID); retried later.
+    Failed,
+}
+
+/// Known beverage returned by a trigram brand search, with its similarity.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SimilarBeverage {
+    #[sqlx(flatten)]
+    #[serde(flatten)]
+    pub beverage: KnownBeverage,
+    /// `pg_trgm` similarity between the stored and searched brand (0.0 - 1.0).
+    pub similarity: f64,
+}
+
+/// A row returned by an upsert, with whether it was newly inserted.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct UpsertedBeverage {
+    #[sqlx(flatten)]
+    pub beverage: KnownBeverage,
+    pub inserted: bool,
+}
+
+/// Result of caching a batch of TTB COLA records.
+#[derive(Debug, Clone, Default)]
+pub struct TtbColaBatchUpsert {
+    /// Rows written, keyed by the TTB ID of the COLA each was cached from.
+    pub cached: HashMap<String, KnownBeverage>,
+    pub inserted: usize,
+    pub updated: usize,
+    /// Records that failed to upsert (logged and rolled back individually).
+    pub skipped: usize,
+    /// Records stored without a cached beverage (no approved or inferred ABV).
+    pub uncached: usize,
+}
+
+/// TTB COLA brand search that returned no records (negative cache entry)
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct TtbColaMiss {
+    pub id: Uuid,
+    pub brand_key: String,
+    pub category: String,
+    pub searched_brand: String,
+    pub miss_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub checked_at: DateTime<Utc>,
 }
 
 /// TTB-compliant ABV ranges for beverage categories
@@ -42,9 +266,10 @@ pub struct BeverageMatchHistory {
     pub id: Uuid,
     pub job_id: Uuid,
     pub matched_beverage_id: Option<Uuid>,
-    pub match_type: String,
+    pub match_type: MatchType,
     pub match_confidence: Option<f64>,
     pub abv_deviation: Option<f64>,
+    pub candidates: Json<Vec<MatchCandidate>>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -53,7 +278,8 @@ pub struct BeverageMatchHistory {
 pub struct NewMatchHistory {
     pub job_id: Uuid,
     pub matched_beverage_id: Option<Uuid>,
-    pub match_type: String,
+    pub match_type: MatchType,
     pub match_confidence: Option<f64>,
     pub abv_deviation: Option<f64>,
+    pub candidates: Vec<MatchCandidate>,
 }
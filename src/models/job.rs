@@ -1,10 +1,15 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString};
 use uuid::Uuid;
 
 /// Status of a label verification job in the async queue.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+///
+/// Stored as the Postgres `job_status` enum; unknown values fail to decode.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Display, EnumString, sqlx::Type)]
 #[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "job_status", rename_all = "snake_case")]
 pub enum JobStatus {
     Pending,
     Processing,
@@ -23,4 +28,74 @@ pub struct VerificationJob {
     pub result: Option<serde_json::Value>,
     pub error: Option<String>,
     pub retry_count: i32,
+    /// Model and prompt that extracted the label fields, once OCR has run.
+    pub extraction: Option<ExtractionProvenance>,
+    /// Expected values and profile selection submitted with the image.
+    pub inputs: VerificationInputs,
+}
+
+/// What a label is validated against, besides its extracted fields.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerificationInputs {
+    pub expected_brand: Option<String>,
+    pub expected_class: Option<String>,
+    pub expected_abv: Option<f64>,
+    pub ttb_id: Option<String>,
+    /// Validation profile name (`services::profile`).
+    pub profile: Option<String>,
+    pub tenant: Option<String>,
+    /// Per-request profile overrides (`key=value,...`).
+    pub profile_overrides: Option<String>,
+}
+
+/// What produced a verification result revision.
+///
+/// Stored as the Postgres `verification_trigger` enum.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Display, EnumString, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "verification_trigger", rename_all = "snake_case")]
+pub enum VerificationTrigger {
+    /// The worker, after OCR.
+    Worker,
+    /// A revalidation of the stored extraction.
+    Revalidate,
+}
+
+/// One validation of a job, kept in `verification_results`.
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct VerificationRevision {
+    pub revision: i32,
+    pub trigger: VerificationTrigger,
+    pub expected_brand: Option<String>,
+    pub expected_class: Option<String>,
+    pub expected_abv: Option<f64>,
+    pub ttb_id: Option<String>,
+    pub profile_name: Option<String>,
+    pub passed: bool,
+    pub result: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Which model and prompt version produced a job's extraction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionProvenance {
+    /// Workers AI model ID.
+    pub model: String,
+    pub prompt_name: String,
+    pub prompt_version: i32,
+}
+
+/// A job's stored OCR extraction, as encrypted on `verification_jobs`.
+#[derive(Debug, Clone)]
+pub struct EncryptedExtraction {
+    pub provenance: ExtractionProvenance,
+    /// `ExtractedLabelFields` as JSON, encrypted.
+    pub extracted_fields: Vec<u8>,
+    /// Raw model output, encrypted.
+    pub raw_output: Vec<u8>,
+    /// Other OCR ensemble members' raw output as JSON, encrypted.
+    pub ensemble_output: Option<Vec<u8>>,
+    pub duration_ms: i32,
+    pub completed_at: DateTime<Utc>,
 }
@@ -32,35 +32,195 @@ pub struct ExtractedLabelFields {
 
     #[garde(skip)]
     pub government_warning: Option<String>,
+
+    /// Bottler, packer, or importer statement (e.g. "Bottled by X, City, ST").
+    #[garde(skip)]
+    #[serde(default)]
+    pub name_address_statement: Option<String>,
+
+    /// Allergen/additive disclosure statements (e.g. "Contains Sulfites").
+    #[garde(skip)]
+    #[serde(default)]
+    pub disclosure_statements: Vec<String>,
+
+    /// How OCR ensemble members agreed on each field (empty for a single model).
+    #[garde(skip)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub consensus: Vec<FieldConsensus>,
+}
+
+/// Agreement between OCR ensemble members on one extracted field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldConsensus {
+    /// `ExtractedLabelFields` field name (e.g. "abv").
+    pub field_name: String,
+    /// The value kept in the extracted fields.
+    pub value: String,
+    /// Share of members that read `value` (1.0 = unanimous).
+    pub confidence: f64,
+    /// Each member's reading, in ensemble order.
+    pub readings: Vec<OcrReading>,
+}
+
+impl FieldConsensus {
+    /// Whether any member read the field differently.
+    pub fn is_disputed(&self) -> bool {
+        self.confidence < 1.0
+    }
+}
+
+/// One ensemble member's reading of a field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrReading {
+    /// Model and prompt version (e.g. "@cf/llava-hf/llava-1.5-7b-hf (label_fields@v1)").
+    pub source: String,
+    pub value: String,
 }
 
 /// Result of verifying extracted label fields against TTB rules.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VerificationResult {
+    /// True unless an error-severity check failed.
     pub passed: bool,
     pub field_results: Vec<FieldVerification>,
+    /// Weighted confidence (see `services::scoring`); equals the sum of `score_breakdown` contributions.
     pub confidence_score: f64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub score_breakdown: Vec<ScoreContribution>,
 
     // Database matching information
     #[serde(skip_serializing_if = "Option::is_none")]
     pub matched_beverage_id: Option<Uuid>,
-    pub match_type: String, // "exact", "fuzzy", "category_only", "no_match"
+    pub match_type: MatchType,
     pub match_confidence: f64,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub abv_deviation: Option<f64>, // Difference from database ABV
     #[serde(skip_serializing_if = "Option::is_none")]
     pub category_rule_applied: Option<String>, // Which category rule was used
+    /// Top reference candidates considered (best first), for reviewer selection.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub candidates: Vec<MatchCandidate>,
+    /// Validation profile whose thresholds were applied (see `services::profile`).
+    #[serde(default)]
+    pub profile_name: String,
+    /// Per-field OCR ensemble agreement (see `services::ocr_ensemble`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ocr_consensus: Vec<FieldConsensus>,
 
     // Warnings (non-fatal issues like stale cache)
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     pub warnings: Vec<String>,
 }
 
+/// How the label was matched against reference data.
+///
+/// Stored as the Postgres `match_type` enum in `beverage_match_history`.
+#[derive(
+    Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Display, EnumString, sqlx::Type,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+#[sqlx(type_name = "match_type", rename_all = "snake_case")]
+pub enum MatchType {
+    /// Brand and class/type found in the local cache.
+    Exact,
+    /// Similar brand found in the local cache.
+    Fuzzy,
+    /// Only the category ABV range could be checked.
+    CategoryOnly,
+    #[default]
+    NoMatch,
+    /// Matched a record from the TTB COLA public database.
+    TtbColaLookup,
+}
+
+/// How a failed check affects the overall verification result.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Hard violation — fails the label.
+    #[default]
+    Error,
+    /// Likely problem that a reviewer should look at; does not fail the label.
+    Warning,
+    /// Informational note (e.g. unusual but valid values).
+    Info,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldVerification {
     pub field_name: String,
+    /// Stable rule identifier (see `services::rules`).
+    #[serde(default)]
+    pub rule_code: String,
+    #[serde(default)]
+    pub severity: Severity,
+    /// Regulation requiring this check, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfr_citation: Option<String>,
     pub expected: Option<String>,
     pub extracted: String,
     pub matches: bool,
     pub similarity_score: f64,
+    /// Score components for name comparisons (brand, bottler).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_components: Option<MatchComponents>,
+}
+
+/// Breakdown of a brand/producer name comparison (see `services::matching`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchComponents {
+    /// Weighted blend of the components below; this is the similarity score.
+    pub score: f64,
+    pub token_set: f64,
+    pub jaro_winkler: f64,
+    pub phonetic: f64,
+    pub normalized_extracted: String,
+    pub normalized_reference: String,
+}
+
+/// Where a candidate reference product came from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateSource {
+    LocalCache,
+    TtbCola,
+}
+
+/// A reference product considered while matching a label.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchCandidate {
+    pub source: CandidateSource,
+    /// `known_beverages` row, if the product is cached locally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beverage_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttb_id: Option<String>,
+    pub brand_name: String,
+    pub class_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abv: Option<f64>,
+    pub brand_similarity: f64,
+    pub class_similarity: f64,
+    /// `None` when the reference has no ABV to compare.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abv_similarity: Option<f64>,
+    /// brand_similarity × 0.7 + class_similarity × 0.3 (used for ranking).
+    pub score: f64,
+    /// Whether this candidate was chosen as `matched_beverage_id` / the TTB reference.
+    pub selected: bool,
+}
+
+/// One rule's contribution to the weighted confidence score.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreContribution {
+    pub rule_code: String,
+    pub field_name: String,
+    /// Configured weight for this rule.
+    pub weight: f64,
+    /// Fraction of the total weight (the most this result could contribute).
+    pub share: f64,
+    pub similarity_score: f64,
+    /// `share × similarity_score`.
+    pub contribution: f64,
 }
@@ -1,5 +1,8 @@
 use garde::Validate;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::models::job::{ExtractionProvenance, JobStatus, VerificationInputs, VerificationRevision};
+use crate::models::label::VerificationResult;
 
 /// Request to submit a label for verification (metadata portion).
 #[derive(Debug, Deserialize, Validate)]
@@ -18,7 +21,7 @@ pub struct VerifyRequest {
 #[derive(Debug, Serialize)]
 pub struct VerifyResponse {
     pub job_id: uuid::Uuid,
-    pub status: String,
+    pub status: JobStatus,
     pub message: String,
 }
 
@@ -26,7 +29,86 @@ pub struct VerifyResponse {
 #[derive(Debug, Serialize)]
 pub struct JobStatusResponse {
     pub job_id: uuid::Uuid,
-    pub status: String,
+    pub status: JobStatus,
     pub result: Option<serde_json::Value>,
     pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extraction: Option<ExtractionProvenance>,
+}
+
+/// Request to revalidate a job's stored extraction.
+///
+/// Every field is optional; omitted fields keep the value submitted with the
+/// image, and an explicit `null` clears it.
+#[derive(Debug, Default, Deserialize, Validate)]
+pub struct RevalidateRequest {
+    #[garde(length(min = 1, max = 200))]
+    #[serde(default, deserialize_with = "double_option")]
+    pub brand_name: Option<Option<String>>,
+
+    #[garde(length(min = 1, max = 200))]
+    #[serde(default, deserialize_with = "double_option")]
+    pub class_type: Option<Option<String>>,
+
+    #[garde(range(min = 0.0, max = 100.0))]
+    #[serde(default, deserialize_with = "double_option")]
+    pub expected_abv: Option<Option<f64>>,
+
+    #[garde(length(min = 1, max = 20))]
+    #[serde(default, deserialize_with = "double_option")]
+    pub ttb_id: Option<Option<String>>,
+
+    #[garde(length(min = 1, max = 100))]
+    #[serde(default, deserialize_with = "double_option")]
+    pub profile: Option<Option<String>>,
+
+    #[garde(length(min = 1, max = 100))]
+    #[serde(default, deserialize_with = "double_option")]
+    pub tenant: Option<Option<String>>,
+
+    #[garde(skip)]
+    #[serde(default, deserialize_with = "double_option")]
+    pub profile_overrides: Option<Option<String>>,
+}
+
+impl RevalidateRequest {
+    /// The job's submitted inputs with this request's fields replacing them.
+    pub fn apply(self, inputs: VerificationInputs) -> VerificationInputs {
+        VerificationInputs {
+            expected_brand: self.brand_name.unwrap_or(inputs.expected_brand),
+            expected_class: self.class_type.unwrap_or(inputs.expected_class),
+            expected_abv: self.expected_abv.unwrap_or(inputs.expected_abv),
+            ttb_id: self.ttb_id.unwrap_or(inputs.ttb_id),
+            profile: self.profile.unwrap_or(inputs.profile),
+            tenant: self.tenant.unwrap_or(inputs.tenant),
+            profile_overrides: self.profile_overrides.unwrap_or(inputs.profile_overrides),
+        }
+    }
+}
+
+/// Deserialize a field that is present (even as `null`) as `Some`, so that an
+/// omitted field (`None`) can be told apart from a cleared one (`Some(None)`).
+fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+/// Response after revalidating a job.
+#[derive(Debug, Serialize)]
+pub struct RevalidateResponse {
+    pub job_id: uuid::Uuid,
+    /// Revision number of this result in the job's history.
+    pub revision: i32,
+    pub inputs: VerificationInputs,
+    pub result: VerificationResult,
+}
+
+/// Every validation of a job, oldest first.
+#[derive(Debug, Serialize)]
+pub struct JobHistoryResponse {
+    pub job_id: uuid::Uuid,
+    pub revisions: Vec<VerificationRevision>,
 }
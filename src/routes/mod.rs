@@ -1,3 +1,4 @@
 pub mod health;
 pub mod metrics;
+pub mod ttb_cola;
 pub mod verify;
@@ -0,0 +1,49 @@
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::Json;
+use serde::{Deserialize, Serialize};
+
+use crate::app_state::AppState;
+use crate::db::beverage_queries;
+
+/// Largest accepted `older_than_days` (100 years). Bigger values overflow the
+/// interval arithmetic in Postgres.
+const MAX_OLDER_THAN_DAYS: i64 = 36_500;
+
+#[derive(Deserialize)]
+pub struct PurgeMissesParams {
+    /// Only purge misses for this brand.
+    pub brand: Option<String>,
+    /// Only purge misses last confirmed more than this many days ago.
+    pub older_than_days: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct PurgeMissesResponse {
+    pub purged: u64,
+}
+
+/// DELETE /api/v1/ttb-cola/misses — Purge the TTB COLA negative cache.
+pub async fn purge_misses(
+    State(state): State<AppState>,
+    Query(params): Query<PurgeMissesParams>,
+) -> Result<Json<PurgeMissesResponse>, (StatusCode, String)> {
+    if params
+        .older_than_days
+        .is_some_and(|days| !(0..=MAX_OLDER_THAN_DAYS).contains(&days))
+    {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("older_than_days must be between 0 and {}", MAX_OLDER_THAN_DAYS),
+        ));
+    }
+
+    let brand = params.brand.as_deref().map(str::trim).filter(|b| !b.is_empty());
+    let purged = beverage_queries::purge_ttb_cola_misses(&state.db, brand, params.older_than_days)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+
+    tracing::info!(brand = brand.unwrap_or("*"), purged, "TTB COLA negative cache purged");
+
+    Ok(Json(PurgeMissesResponse { purged }))
+}
@@ -1,13 +1,17 @@
 use axum::extract::{Multipart, Path, State};
 use axum::http::StatusCode;
 use axum::Json;
+use garde::Validate;
 use uuid::Uuid;
 
 use crate::app_state::AppState;
 use crate::db::queries;
-use crate::models::job::JobStatus;
-use crate::models::verification::{JobStatusResponse, VerifyResponse};
+use crate::models::job::{JobStatus, VerificationInputs, VerificationTrigger};
+use crate::models::verification::{
+    JobHistoryResponse, JobStatusResponse, RevalidateRequest, RevalidateResponse, VerifyResponse,
+};
 use crate::services::queue::QueuedJob;
+use crate::services::{job_extraction, profile, validation};
 
 const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024; // 10MB
 const MIN_IMAGE_SIZE: usize = 1024; // 1KB
@@ -22,6 +26,10 @@ pub async fn submit_verification(
     let mut metadata_brand: Option<String> = None;
     let mut metadata_class: Option<String> = None;
     let mut metadata_abv: Option<f64> = None;
+    let mut ttb_id: Option<String> = None;
+    let mut profile_name: Option<String> = None;
+    let mut tenant: Option<String> = None;
+    let mut profile_overrides: Option<String> = None;
 
     while let Some(field) = multipart
         .next_field()
@@ -97,6 +105,38 @@ pub async fn submit_verification(
                     .map_err(|_| (StatusCode::BAD_REQUEST, "expected_abv must be a number".to_string()))?;
                 metadata_abv = Some(abv);
             }
+            Some("ttb_id") => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid ttb_id field".to_string()))?;
+                let text = text.trim().to_string();
+                if text.is_empty() || !text.chars().all(|c| c.is_ascii_digit()) {
+                    return Err((StatusCode::BAD_REQUEST, "ttb_id must be numeric".to_string()));
+                }
+                ttb_id = Some(text);
+            }
+            Some("profile") => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid profile field".to_string()))?;
+                profile_name = Some(text);
+            }
+            Some("tenant") => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid tenant field".to_string()))?;
+                tenant = Some(text);
+            }
+            Some("profile_overrides") => {
+                let text = field
+                    .text()
+                    .await
+                    .map_err(|_| (StatusCode::BAD_REQUEST, "Invalid profile_overrides field".to_string()))?;
+                profile_overrides = Some(text);
+            }
             _ => {}
         }
     }
@@ -106,6 +146,11 @@ pub async fn submit_verification(
         "Missing 'image' field in multipart upload".to_string(),
     ))?;
 
+    // Resolve validation thresholds before storing anything
+    let validation_profile = profile::registry()
+        .resolve(profile_name.as_deref(), tenant.as_deref(), profile_overrides.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
     // Encrypt the image using AES-256-GCM
     let encrypted_image = state
         .encryption
@@ -123,8 +168,17 @@ pub async fn submit_verification(
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Storage upload failed: {}", e)))?;
 
-    // Create job record in database
-    let job = queries::create_job(&state.db, &image_key, None)
+    // Create job record in database, keeping the inputs for revalidation
+    let inputs = VerificationInputs {
+        expected_brand: metadata_brand.clone(),
+        expected_class: metadata_class.clone(),
+        expected_abv: metadata_abv,
+        ttb_id: ttb_id.clone(),
+        profile: profile_name,
+        tenant,
+        profile_overrides,
+    };
+    let job = queries::create_job(&state.db, &image_key, None, &inputs)
         .await
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
 
@@ -135,6 +189,8 @@ pub async fn submit_verification(
         expected_brand: metadata_brand,
         expected_class: metadata_class,
         expected_abv: metadata_abv,
+        ttb_id,
+        profile: Some(validation_profile),
     };
 
     state
@@ -156,7 +212,7 @@ pub async fn submit_verification(
 
     Ok(Json(VerifyResponse {
         job_id: job.id,
-        status: "pending".to_string(),
+        status: job.status,
         message: "Label submitted for verification".to_string(),
     }))
 }
@@ -172,23 +228,117 @@ pub async fn get_job_status(
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?
         .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
 
-    let status_str = match job.status {
-        JobStatus::Pending => "pending",
-        JobStatus::Processing => "processing",
-        JobStatus::Completed => "completed",
-        JobStatus::Failed => "failed",
-    };
-
     tracing::info!(
         job_id = %job_id,
-        status = status_str,
+        status = %job.status,
         "Job status retrieved"
     );
 
     Ok(Json(JobStatusResponse {
         job_id: job.id,
-        status: status_str.to_string(),
+        status: job.status,
         result: job.result,
         error: job.error,
+        extraction: job.extraction,
+    }))
+}
+
+/// POST /api/v1/verify/:job_id/revalidate — Re-run validation on a job's stored
+/// extraction, optionally with new expected values or profile.
+///
+/// The result is added to the job's history as a new revision and becomes its
+/// current result; earlier revisions are kept.
+pub async fn revalidate_job(
+    State(state): State<AppState>,
+    Path(job_id): Path<Uuid>,
+    request: Option<Json<RevalidateRequest>>,
+) -> Result<Json<RevalidateResponse>, (StatusCode, String)> {
+    let request = request.map(|Json(r)| r).unwrap_or_default();
+    request
+        .validate()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    if let Some(Some(ttb_id)) = &request.ttb_id {
+        if !ttb_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err((StatusCode::BAD_REQUEST, "ttb_id must be numeric".to_string()));
+        }
+    }
+
+    let job = queries::get_job(&state.db, job_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?
+        .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
+
+    if matches!(job.status, JobStatus::Pending | JobStatus::Processing) {
+        return Err((StatusCode::CONFLICT, "Job is still being processed".to_string()));
+    }
+
+    let extraction = job_extraction::load(&state.db, &state.encryption, job_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Failed to load extraction: {}", e)))?
+        .ok_or((
+            StatusCode::CONFLICT,
+            "Job has no stored extraction to revalidate".to_string(),
+        ))?;
+
+    let inputs = request.apply(job.inputs);
+    let validation_profile = profile::registry()
+        .resolve(inputs.profile.as_deref(), inputs.tenant.as_deref(), inputs.profile_overrides.as_deref())
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+
+    let result = validation::verify_label_with_database(
+        &state.db,
+        &extraction.fields,
+        inputs.expected_brand.as_deref(),
+        inputs.expected_class.as_deref(),
+        inputs.expected_abv,
+        inputs.ttb_id.as_deref(),
+        &validation_profile,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Validation failed: {}", e)))?;
+
+    let revision = queries::record_verification(
+        &state.db,
+        job_id,
+        VerificationTrigger::Revalidate,
+        &inputs,
+        &result,
+    )
+    .await
+    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+
+    metrics::counter!("verification_revalidations_total").increment(1);
+
+    tracing::info!(
+        job_id = %job_id,
+        revision = revision,
+        passed = result.passed,
+        confidence = result.confidence_score,
+        profile = %result.profile_name,
+        "Job revalidated"
+    );
+
+    Ok(Json(RevalidateResponse {
+        job_id,
+        revision,
+        inputs,
+        result,
     }))
 }
+
+/// GET /api/v1/verify/:job_id/history — Every validation of a job, oldest first.
+pub async fn get_job_history(
+    State(state): State<AppState>,
+    Path(job_id): Path<Uuid>,
+) -> Result<Json<JobHistoryResponse>, (StatusCode, String)> {
+    queries::get_job(&state.db, job_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?
+        .ok_or((StatusCode::NOT_FOUND, "Job not found".to_string()))?;
+
+    let revisions = queries::get_verification_history(&state.db, job_id)
+        .await
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?;
+
+    Ok(Json(JobHistoryResponse { job_id, revisions }))
+}
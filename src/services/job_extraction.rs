@@ -0,0 +1,153 @@
+//! Encrypted storage of each job's OCR extraction.
+//!
+//! The worker saves the extracted fields, the model's raw output (every
+//! member's, for an OCR ensemble) and the OCR timing on `verification_jobs`,
+//! encrypted with the same key as label images. Stored fields can be
+//! validated again (a retried job, or after a rules change) without another
+//! Workers AI call. When the output has no readable fields, the provenance
+//! and raw output are stored with the parse error instead.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::db::queries;
+use crate::models::job::ExtractionProvenance;
+use crate::models::label::ExtractedLabelFields;
+use crate::services::encryption::{EncryptionError, EncryptionService};
+use crate::services::ocr::{OcrExtraction, UnreadableOutput};
+
+#[derive(Debug, thiserror::Error)]
+pub enum JobExtractionError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+
+    #[error("Encryption error: {0}")]
+    Encryption(#[from] EncryptionError),
+
+    #[error("Stored extraction is not valid JSON: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+
+    #[error("Stored raw output is not valid UTF-8")]
+    InvalidRawOutput,
+}
+
+/// Raw output of an OCR ensemble member after the first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemberOutput {
+    pub model: String,
+    pub prompt_name: String,
+    pub prompt_version: i32,
+    pub raw_output: String,
+}
+
+/// A job's decrypted OCR extraction.
+#[derive(Debug, Clone)]
+pub struct StoredExtraction {
+    pub provenance: ExtractionProvenance,
+    pub fields: ExtractedLabelFields,
+    pub raw_output: String,
+    /// The other ensemble members' output (empty for a single model).
+    pub ensemble_output: Vec<MemberOutput>,
+    pub duration_ms: i32,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Encrypt and store a job's extraction, returning its provenance.
+pub async fn save(
+    pool: &PgPool,
+    encryption: &EncryptionService,
+    job_id: Uuid,
+    extraction: &OcrExtraction,
+    duration: Duration,
+) -> Result<ExtractionProvenance, JobExtractionError> {
+    let provenance = ExtractionProvenance {
+        model: extraction.model.clone(),
+        prompt_name: extraction.prompt.name.to_string(),
+        prompt_version: extraction.prompt.version,
+    };
+    let fields = encryption.encrypt(&serde_json::to_vec(&extraction.fields)?)?;
+    let raw_output = encryption.encrypt(extraction.raw_output.as_bytes())?;
+    let ensemble_output = if extraction.members.is_empty() {
+        None
+    } else {
+        let outputs: Vec<MemberOutput> = extraction
+            .members
+            .iter()
+            .map(|m| MemberOutput {
+                model: m.model.clone(),
+                prompt_name: m.prompt.name.to_string(),
+                prompt_version: m.prompt.version,
+                raw_output: m.raw_output.clone(),
+            })
+            .collect();
+        Some(encryption.encrypt(&serde_json::to_vec(&outputs)?)?)
+    };
+    let duration_ms = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+
+    queries::record_ocr_extraction(
+        pool,
+        job_id,
+        &provenance,
+        &fields,
+        &raw_output,
+        ensemble_output.as_deref(),
+        duration_ms,
+    )
+    .await?;
+
+    Ok(provenance)
+}
+
+/// Encrypt and store the raw output of an extraction that had no readable
+/// label fields, with the parse error, returning its provenance.
+pub async fn save_unreadable(
+    pool: &PgPool,
+    encryption: &EncryptionService,
+    job_id: Uuid,
+    output: &UnreadableOutput,
+    duration: Duration,
+) -> Result<ExtractionProvenance, JobExtractionError> {
+    let provenance = ExtractionProvenance {
+        model: output.model.clone(),
+        prompt_name: output.prompt.name.to_string(),
+        prompt_version: output.prompt.version,
+    };
+    let raw_output = encryption.encrypt(output.raw_output.as_bytes())?;
+    let duration_ms = i32::try_from(duration.as_millis()).unwrap_or(i32::MAX);
+
+    queries::record_ocr_parse_failure(pool, job_id, &provenance, &raw_output, &output.reason, duration_ms)
+        .await?;
+
+    Ok(provenance)
+}
+
+/// Load and decrypt a job's stored extraction (None if OCR has not completed).
+pub async fn load(
+    pool: &PgPool,
+    encryption: &EncryptionService,
+    job_id: Uuid,
+) -> Result<Option<StoredExtraction>, JobExtractionError> {
+    let Some(stored) = queries::get_ocr_extraction(pool, job_id).await? else {
+        return Ok(None);
+    };
+
+    let fields = serde_json::from_slice(&encryption.decrypt(&stored.extracted_fields)?)?;
+    let raw_output = String::from_utf8(encryption.decrypt(&stored.raw_output)?)
+        .map_err(|_| JobExtractionError::InvalidRawOutput)?;
+    let ensemble_output = match stored.ensemble_output {
+        Some(encrypted) => serde_json::from_slice(&encryption.decrypt(&encrypted)?)?,
+        None => Vec::new(),
+    };
+
+    Ok(Some(StoredExtraction {
+        provenance: stored.provenance,
+        fields,
+        raw_output,
+        ensemble_output,
+        duration_ms: stored.duration_ms,
+        completed_at: stored.completed_at,
+    }))
+}
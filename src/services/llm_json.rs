@@ -0,0 +1,366 @@
+//! Tolerant extraction of a JSON object from LLM output.
+//!
+//! Vision models asked to "return ONLY valid JSON" still wrap it in prose or
+//! a ```json fence, add comments, use single quotes or Python literals, leave
+//! trailing commas, or stop mid-object at the token limit. `extract_object`
+//! finds the first balanced `{...}` in the text, rewrites those defects into
+//! strict JSON and reports each `Repair` it made so callers can log them.
+
+use std::fmt;
+
+use serde_json::Value;
+
+/// A defect fixed while extracting the object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Repair {
+    /// Markdown-escaped underscores (`brand\_name`).
+    EscapedUnderscores,
+    /// Prose or a code fence before or after the object.
+    SurroundingText,
+    /// `//`, `/* */` or `#` comments.
+    Comments,
+    /// Strings delimited by single or typographic quotes.
+    NonStandardQuotes,
+    /// Object keys without quotes.
+    UnquotedKeys,
+    /// `True`, `False` or `None`.
+    PythonLiterals,
+    /// A comma before `}` or `]`.
+    TrailingCommas,
+    /// Raw newlines or tabs inside strings.
+    ControlCharacters,
+    /// Backslash escapes JSON doesn't have (`\%`); the backslash is dropped.
+    InvalidEscapes,
+    /// Output ended inside the object; open strings and brackets were closed.
+    Truncated,
+}
+
+impl Repair {
+    /// Stable name for logs and metric labels.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::EscapedUnderscores => "escaped_underscores",
+            Self::SurroundingText => "surrounding_text",
+            Self::Comments => "comments",
+            Self::NonStandardQuotes => "non_standard_quotes",
+            Self::UnquotedKeys => "unquoted_keys",
+            Self::PythonLiterals => "python_literals",
+            Self::TrailingCommas => "trailing_commas",
+            Self::ControlCharacters => "control_characters",
+            Self::InvalidEscapes => "invalid_escapes",
+            Self::Truncated => "truncated",
+        }
+    }
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LlmJsonError {
+    #[error("No JSON object in model output")]
+    NoObject,
+
+    #[error("Invalid JSON object in model output: {0}")]
+    Invalid(#[source] serde_json::Error),
+}
+
+/// A JSON object extracted from model output, with the repairs applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extraction {
+    pub object: serde_json::Map<String, Value>,
+    /// Each kind of repair at most once, in the order first applied.
+    pub repairs: Vec<Repair>,
+}
+
+/// Extract the first JSON object from `text`.
+///
+/// Each `{` is tried in turn until one yields a valid object after repair, so
+/// braces in leading prose don't hide the real object. The error is that of
+/// the first candidate when none parses.
+pub fn extract_object(text: &str) -> Result<Extraction, LlmJsonError> {
+    let mut base_repairs = Vec::new();
+    let unescaped;
+    let text = if text.contains("\\_") {
+        base_repairs.push(Repair::EscapedUnderscores);
+        unescaped = text.replace("\\_", "_");
+        unescaped.as_str()
+    } else {
+        text
+    };
+
+    let mut first_error = None;
+    for (start, _) in text.match_indices('{') {
+        let mut repairs = base_repairs.clone();
+        let (json, end) = rewrite_object(&text[start..], &mut repairs);
+        if !text[..start].trim().is_empty() || !text[start + end..].trim().is_empty() {
+            note(&mut repairs, Repair::SurroundingText);
+        }
+
+        match serde_json::from_str::<Value>(&json) {
+            Ok(Value::Object(object)) => return Ok(Extraction { object, repairs }),
+            Ok(_) => {}
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    Err(first_error.map_or(LlmJsonError::NoObject, LlmJsonError::Invalid))
+}
+
+fn note(repairs: &mut Vec<Repair>, repair: Repair) {
+    if !repairs.contains(&repair) {
+        repairs.push(repair);
+    }
+}
+
+/// Rewrite the object starting at `text[0] == '{'` as strict JSON.
+///
+/// Returns the JSON and the byte length of `text` it consumed.
+fn rewrite_object(text: &str, repairs: &mut Vec<Repair>) -> (String, usize) {
+    let mut out = String::with_capacity(text.len());
+    let mut closers: Vec<char> = Vec::new();
+    let mut chars = text.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' => {
+                closers.push('}');
+                out.push(c);
+            }
+            '[' => {
+                closers.push(']');
+                out.push(c);
+            }
+            '}' | ']' => {
+                strip_trailing_comma(&mut out, repairs);
+                closers.pop();
+                out.push(c);
+                if closers.is_empty() {
+                    return (out, i + c.len_utf8());
+                }
+            }
+            '"' | '\'' | '\u{201C}' | '\u{2018}' => {
+                if c != '"' {
+                    note(repairs, Repair::NonStandardQuotes);
+                }
+                let close = match c {
+                    '\u{201C}' => '\u{201D}',
+                    '\u{2018}' => '\u{2019}',
+                    _ => c,
+                };
+                if !rewrite_string(&mut chars, close, &mut out, repairs) {
+                    break;
+                }
+            }
+            '/' if matches!(chars.peek(), Some((_, '/' | '*'))) => {
+                note(repairs, Repair::Comments);
+                let block = chars.next().is_some_and(|(_, c)| c == '*');
+                skip_comment(&mut chars, block);
+            }
+            '#' => {
+                note(repairs, Repair::Comments);
+                skip_comment(&mut chars, false);
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let mut word = c.to_string();
+                while let Some(&(_, next)) = chars.peek() {
+                    if !(next.is_ascii_alphanumeric() || next == '_') {
+                        break;
+                    }
+                    word.push(next);
+                    chars.next();
+                }
+                match word.as_str() {
+                    "True" | "False" | "None" => {
+                        note(repairs, Repair::PythonLiterals);
+                        out.push_str(match word.as_str() {
+                            "True" => "true",
+                            "False" => "false",
+                            _ => "null",
+                        });
+                    }
+                    "true" | "false" | "null" => out.push_str(&word),
+                    _ if next_non_space(&mut chars) == Some(':') => {
+                        note(repairs, Repair::UnquotedKeys);
+                        out.push('"');
+                        out.push_str(&word);
+                        out.push('"');
+                    }
+                    // Not JSON; left for the parser to reject
+                    _ => out.push_str(&word),
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+
+    // Ran out of input inside the object
+    note(repairs, Repair::Truncated);
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    if out.ends_with(':') {
+        out.push_str(" null");
+    }
+    strip_trailing_comma(&mut out, repairs);
+    while let Some(close) = closers.pop() {
+        out.push(close);
+    }
+    (out, text.len())
+}
+
+/// Copy a string body up to `close` as a double-quoted JSON string.
+///
+/// Returns false if the input ended first (the string is closed anyway).
+fn rewrite_string(
+    chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>,
+    close: char,
+    out: &mut String,
+    repairs: &mut Vec<Repair>,
+) -> bool {
+    out.push('"');
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                // \' is not a JSON escape
+                Some((_, '\'')) => out.push('\''),
+                Some((_, escaped)) if "\"\\/bfnrtu".contains(escaped) => {
+                    out.push('\\');
+                    out.push(escaped);
+                }
+                Some((_, escaped)) => {
+                    note(repairs, Repair::InvalidEscapes);
+                    out.push(escaped);
+                }
+                None => break,
+            },
+            c if c == close => {
+                out.push('"');
+                return true;
+            }
+            '"' => out.push_str("\\\""),
+            '\n' | '\r' | '\t' => {
+                note(repairs, Repair::ControlCharacters);
+                out.push_str(match c {
+                    '\n' => "\\n",
+                    '\r' => "\\r",
+                    _ => "\\t",
+                });
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    false
+}
+
+fn skip_comment(chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>, block: bool) {
+    let mut previous = '\0';
+    for (_, c) in chars.by_ref() {
+        if (block && previous == '*' && c == '/') || (!block && c == '\n') {
+            return;
+        }
+        previous = c;
+    }
+}
+
+/// Peek past whitespace without consuming anything but the whitespace.
+fn next_non_space(chars: &mut std::iter::Peekable<std::str::CharIndices<'_>>) -> Option<char> {
+    while let Some(&(_, c)) = chars.peek() {
+        if !c.is_whitespace() {
+            return Some(c);
+        }
+        chars.next();
+    }
+    None
+}
+
+fn strip_trailing_comma(out: &mut String, repairs: &mut Vec<Repair>) {
+    let trimmed = out.trim_end();
+    if trimmed.ends_with(',') {
+        note(repairs, Repair::TrailingCommas);
+        out.truncate(trimmed.len() - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(text: &str) -> Extraction {
+        extract_object(text).unwrap()
+    }
+
+    #[test]
+    fn test_strict_json_needs_no_repair() {
+        let extraction = extract(r#"{"brand_name": "Stone Creek", "abv": 13.5}"#);
+        assert_eq!(extraction.object["brand_name"], "Stone Creek");
+        assert!(extraction.repairs.is_empty());
+    }
+
+    #[test]
+    fn test_prose_and_code_fence() {
+        let text = "Sure! Here is the JSON:\n```json\n{\"brand\\_name\": \"Stone Creek\"}\n```\nLet me know {if} you need more.";
+        let extraction = extract(text);
+        assert_eq!(extraction.object["brand_name"], "Stone Creek");
+        assert_eq!(
+            extraction.repairs,
+            vec![Repair::EscapedUnderscores, Repair::SurroundingText]
+        );
+    }
+
+    #[test]
+    fn test_braces_in_leading_prose_are_skipped() {
+        let extraction = extract("The label {front} reads: {\"brand_name\": \"Stone Creek\"}");
+        assert_eq!(extraction.object["brand_name"], "Stone Creek");
+    }
+
+    #[test]
+    fn test_python_style_object() {
+        let text = "{'brand_name': 'Kendall-Jackson\\'s', 'country_of_origin': None, 'organic': True,}";
+        let extraction = extract(text);
+        assert_eq!(extraction.object["brand_name"], "Kendall-Jackson's");
+        assert_eq!(extraction.object["country_of_origin"], Value::Null);
+        assert_eq!(extraction.object["organic"], true);
+        assert_eq!(
+            extraction.repairs,
+            vec![Repair::NonStandardQuotes, Repair::PythonLiterals, Repair::TrailingCommas]
+        );
+    }
+
+    #[test]
+    fn test_comments_unquoted_keys_and_newlines() {
+        let text = "{\n  brand_name: \"Stone Creek\", // from the front label\n  /* guess */ abv: 13.5,\n  government_warning: \"GOVERNMENT WARNING:\n(1) ...\"\n} # done";
+        let extraction = extract(text);
+        assert_eq!(extraction.object["abv"], 13.5);
+        assert_eq!(extraction.object["government_warning"], "GOVERNMENT WARNING:\n(1) ...");
+        assert!(extraction.repairs.contains(&Repair::Comments));
+        assert!(extraction.repairs.contains(&Repair::UnquotedKeys));
+        assert!(extraction.repairs.contains(&Repair::ControlCharacters));
+        assert!(extraction.repairs.contains(&Repair::SurroundingText));
+
+        let extraction = extract(r#"{"abv": "13.5\% ALC/VOL"}"#);
+        assert_eq!(extraction.object["abv"], "13.5% ALC/VOL");
+        assert_eq!(extraction.repairs, vec![Repair::InvalidEscapes]);
+    }
+
+    #[test]
+    fn test_truncated_output_is_closed() {
+        let extraction = extract(r#"{"brand_name": "Stone Creek", "disclosure_statements": ["Contains Sulf"#);
+        assert_eq!(extraction.object["disclosure_statements"][0], "Contains Sulf");
+        assert_eq!(extraction.repairs, vec![Repair::Truncated]);
+
+        let extraction = extract(r#"{"brand_name": "Stone Creek", "abv":"#);
+        assert_eq!(extraction.object["abv"], Value::Null);
+    }
+
+    #[test]
+    fn test_no_object() {
+        assert!(matches!(extract_object("I cannot read this label."), Err(LlmJsonError::NoObject)));
+        assert!(matches!(extract_object("{brand name here}"), Err(LlmJsonError::Invalid(_))));
+    }
+}
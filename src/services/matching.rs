@@ -0,0 +1,229 @@
+//! Brand and producer name matching.
+//!
+//! Plain lowercase Jaro-Winkler scores "The Glenlivet" vs "Glenlivet" or
+//! "Château" vs "Chateau" poorly. This module normalizes names first (Unicode
+//! folding, punctuation/apostrophe stripping, stop-word and corporate-suffix
+//! removal) and then blends three signals:
+//!
+//! - token-set ratio (word order and extra words don't matter)
+//! - Jaro-Winkler on the normalized strings (typos, prefixes)
+//! - Soundex agreement per token (OCR/phonetic misspellings)
+
+use strsim::{jaro_winkler, normalized_levenshtein};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::models::label::MatchComponents;
+
+/// Weight of the token-set ratio in the combined score.
+const TOKEN_SET_WEIGHT: f64 = 0.45;
+/// Weight of Jaro-Winkler in the combined score.
+const JARO_WINKLER_WEIGHT: f64 = 0.40;
+/// Weight of phonetic agreement in the combined score.
+const PHONETIC_WEIGHT: f64 = 0.15;
+
+/// Words that carry no brand identity.
+const STOP_WORDS: &[&str] = &["the", "a", "an", "and", "of", "de", "la", "le", "el", "di", "du"];
+
+/// Corporate and producer-type suffixes removed before comparison.
+const CORPORATE_SUFFIXES: &[&str] = &[
+    "winery", "wineries", "vineyard", "vineyards", "cellars", "cellar", "estate", "estates",
+    "distillery", "distilleries", "distilling", "distillers", "brewery", "brewing", "brewers",
+    "co", "company", "companies", "corp", "corporation", "inc", "incorporated", "llc", "ltd",
+    "limited", "plc", "sa", "spa", "gmbh",
+];
+
+/// Normalize a brand or producer name for comparison.
+///
+/// Folds accents ("Château" → "chateau"), drops apostrophes ("Daniel's" →
+/// "daniels"), replaces other punctuation with spaces, and removes stop words
+/// and corporate suffixes. If removal would leave nothing (e.g. a brand that is
+/// literally "The Winery"), the folded tokens are kept instead.
+pub fn normalize_name(name: &str) -> String {
+    let folded: String = name
+        .nfkd()
+        .filter(|c| !is_combining_mark(*c))
+        .filter(|c| !matches!(c, '\'' | '\u{2019}' | '`'))
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { ' ' })
+        .collect();
+
+    let tokens: Vec<&str> = folded.split_whitespace().collect();
+    let significant: Vec<&str> = tokens
+        .iter()
+        .copied()
+        .filter(|t| !STOP_WORDS.contains(t) && !CORPORATE_SUFFIXES.contains(t))
+        .collect();
+
+    if significant.is_empty() {
+        tokens.join(" ")
+    } else {
+        significant.join(" ")
+    }
+}
+
+/// Score two names, returning the combined score and its components.
+pub fn score_names(extracted: &str, reference: &str) -> MatchComponents {
+    let a = normalize_name(extracted);
+    let b = normalize_name(reference);
+
+    let (token_set, jw, phonetic) = if a.is_empty() || b.is_empty() {
+        (0.0, 0.0, 0.0)
+    } else if a == b {
+        (1.0, 1.0, 1.0)
+    } else {
+        (token_set_ratio(&a, &b), jaro_winkler(&a, &b), phonetic_similarity(&a, &b))
+    };
+
+    MatchComponents {
+        score: TOKEN_SET_WEIGHT * token_set + JARO_WINKLER_WEIGHT * jw + PHONETIC_WEIGHT * phonetic,
+        token_set,
+        jaro_winkler: jw,
+        phonetic,
+        normalized_extracted: a,
+        normalized_reference: b,
+    }
+}
+
+/// Token-set ratio: compares the shared tokens against each side's full token
+/// set, so "glenlivet 12" vs "glenlivet" scores on what they have in common.
+fn token_set_ratio(a: &str, b: &str) -> f64 {
+    let mut ta: Vec<&str> = a.split_whitespace().collect();
+    let mut tb: Vec<&str> = b.split_whitespace().collect();
+    ta.sort_unstable();
+    ta.dedup();
+    tb.sort_unstable();
+    tb.dedup();
+
+    let common: Vec<&str> = ta.iter().copied().filter(|t| tb.contains(t)).collect();
+    let only_a: Vec<&str> = ta.iter().copied().filter(|t| !common.contains(t)).collect();
+    let only_b: Vec<&str> = tb.iter().copied().filter(|t| !common.contains(t)).collect();
+
+    let base = common.join(" ");
+    let with_a = [base.as_str(), &only_a.join(" ")].join(" ").trim().to_string();
+    let with_b = [base.as_str(), &only_b.join(" ")].join(" ").trim().to_string();
+
+    let mut best = normalized_levenshtein(&with_a, &with_b);
+    if !base.is_empty() {
+        best = best
+            .max(normalized_levenshtein(&base, &with_a))
+            .max(normalized_levenshtein(&base, &with_b));
+    }
+    best
+}
+
+/// Fraction of tokens (over the longer name) whose Soundex codes agree.
+fn phonetic_similarity(a: &str, b: &str) -> f64 {
+    let ca: Vec<String> = a.split_whitespace().map(soundex).collect();
+    let cb: Vec<String> = b.split_whitespace().map(soundex).collect();
+    let longest = ca.len().max(cb.len());
+    if longest == 0 {
+        return 0.0;
+    }
+
+    let mut remaining = cb.clone();
+    let mut agreed = 0;
+    for code in &ca {
+        if let Some(pos) = remaining.iter().position(|c| c == code) {
+            remaining.swap_remove(pos);
+            agreed += 1;
+        }
+    }
+    agreed as f64 / longest as f64
+}
+
+/// American Soundex code for a single lowercase ASCII token (e.g. "creek" → "C620").
+///
+/// Digits are returned unchanged so vintage years and age statements only agree
+/// with themselves.
+pub fn soundex(token: &str) -> String {
+    fn digit(c: char) -> Option<char> {
+        match c {
+            'b' | 'f' | 'p' | 'v' => Some('1'),
+            'c' | 'g' | 'j' | 'k' | 'q' | 's' | 'x' | 'z' => Some('2'),
+            'd' | 't' => Some('3'),
+            'l' => Some('4'),
+            'm' | 'n' => Some('5'),
+            'r' => Some('6'),
+            _ => None,
+        }
+    }
+
+    let mut chars = token.chars().filter(|c| c.is_ascii_alphabetic());
+    let Some(first) = chars.next() else {
+        return token.to_string();
+    };
+
+    let mut code = String::with_capacity(4);
+    code.push(first.to_ascii_uppercase());
+    let mut last = digit(first);
+
+    for c in chars {
+        let d = digit(c);
+        if let Some(d) = d {
+            if Some(d) != last {
+                code.push(d);
+                if code.len() == 4 {
+                    break;
+                }
+            }
+        }
+        // 'h' and 'w' don't separate equal codes; vowels do
+        if c != 'h' && c != 'w' {
+            last = d;
+        }
+    }
+
+    while code.len() < 4 {
+        code.push('0');
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_name() {
+        assert_eq!(normalize_name("The Glenlivet"), "glenlivet");
+        assert_eq!(normalize_name("Jack Daniel's"), "jack daniels");
+        assert_eq!(normalize_name("JACK DANIELS"), "jack daniels");
+        assert_eq!(normalize_name("Château Margaux"), "chateau margaux");
+        assert_eq!(normalize_name("Buffalo Trace Distilling Co."), "buffalo trace");
+        assert_eq!(normalize_name("The Winery"), "the winery");
+    }
+
+    #[test]
+    fn test_equivalent_names_score_perfectly() {
+        assert_eq!(score_names("The Glenlivet", "Glenlivet").score, 1.0);
+        assert_eq!(score_names("Jack Daniel's", "JACK DANIELS").score, 1.0);
+        assert_eq!(score_names("Château Montelena", "Chateau Montelena Winery").score, 1.0);
+    }
+
+    #[test]
+    fn test_ocr_typo_scores_high() {
+        let m = score_names("Stone Creak Vineyards", "Stone Creek Vineyards");
+        assert!(m.score > 0.9, "score was {}", m.score);
+        assert_eq!(m.phonetic, 1.0);
+    }
+
+    #[test]
+    fn test_different_brands_score_low() {
+        let m = score_names("Buffalo Trace", "Maker's Mark");
+        assert!(m.score < 0.6, "score was {}", m.score);
+    }
+
+    #[test]
+    fn test_soundex() {
+        assert_eq!(soundex("robert"), "R163");
+        assert_eq!(soundex("rupert"), "R163");
+        assert_eq!(soundex("ashcraft"), "A261");
+        assert_eq!(soundex("tymczak"), "T522");
+        assert_eq!(soundex("2019"), "2019");
+    }
+
+    #[test]
+    fn test_empty_names() {
+        assert_eq!(score_names("", "Glenlivet").score, 0.0);
+    }
+}
@@ -1,7 +1,20 @@
 pub mod encryption;
+pub mod job_extraction;
+pub mod llm_json;
+pub mod matching;
 pub mod ocr;
+pub mod ocr_ensemble;
+pub mod origin;
+pub mod profile;
+pub mod prompts;
 pub mod queue;
+pub mod reference_import;
+pub mod rules;
+pub mod scoring;
 pub mod storage;
+pub mod throttle;
 pub mod ttb_cola;
+pub mod ttb_cola_csv;
+pub mod ttb_cola_refresh;
 pub mod ttb_standards;
 pub mod validation;
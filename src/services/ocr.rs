@@ -1,16 +1,40 @@
+use futures::future::join_all;
+use image::codecs::jpeg::JpegEncoder;
 use image::imageops::FilterType;
-use image::ImageFormat;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::io::Cursor;
+use serde_json::Value;
 
 use crate::models::label::ExtractedLabelFields;
+use crate::services::llm_json::{self, Extraction, LlmJsonError, Repair};
+use crate::services::ocr_ensemble::{self, EnsembleMember};
+use crate::services::prompts::{self, Prompt};
 
 /// Client for Cloudflare Workers AI LLaVA model.
 pub struct WorkersAiClient {
     http: Client,
     account_id: String,
     api_token: String,
+    model: String,
+    /// Models that each read the label when two or more are configured.
+    ensemble: Vec<EnsembleMember>,
+}
+
+/// Label fields extracted from an image, with what produced them.
+#[derive(Debug, Clone)]
+pub struct OcrExtraction {
+    pub fields: ExtractedLabelFields,
+    /// Workers AI model ID.
+    pub model: String,
+    /// Prompt version sent with the image.
+    pub prompt: Prompt,
+    /// The model's description, before JSON extraction and repair.
+    pub raw_output: String,
+    /// Repairs needed to read `raw_output` as JSON.
+    pub repairs: Vec<Repair>,
+    /// The other ensemble members' extractions; `fields` is the vote of all
+    /// members (see `services::ocr_ensemble`). Empty for a single model.
+    pub members: Vec<OcrExtraction>,
 }
 
 #[derive(Serialize)]
@@ -30,17 +54,8 @@ struct LlavaResponse {
 #[derive(Deserialize)]
 struct LlavaResult {
     description: Option<String>,
-}
-
-/// Lenient intermediate struct for LLaVA output where ABV may be a string.
-#[derive(Deserialize)]
-struct RawLlavaFields {
-    brand_name: String,
-    class_type: String,
-    abv: String,
-    net_contents: String,
-    country_of_origin: Option<String>,
-    government_warning: Option<String>,
+    /// Text output of instruction-tuned vision models (e.g. Llama 3.2 Vision).
+    response: Option<String>,
 }
 
 impl WorkersAiClient {
@@ -49,9 +64,36 @@ impl WorkersAiClient {
             http: Client::new(),
             account_id: account_id.to_string(),
             api_token: api_token.to_string(),
+            model: prompts::DEFAULT_MODEL.to_string(),
+            ensemble: Vec::new(),
         })
     }
 
+    /// Use a different Workers AI model (e.g. `@cf/meta/llama-3.2-11b-vision-instruct`).
+    pub fn with_model(mut self, model: &str) -> Self {
+        self.model = model.trim().to_string();
+        self
+    }
+
+    /// Workers AI model ID used for extraction (the first ensemble member's, if any).
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Read each label with every member and vote on the fields
+    /// (`ocr_ensemble::members_from_spec`). A single member only sets the
+    /// model and prompt version.
+    pub fn with_ensemble(mut self, mut members: Vec<EnsembleMember>) -> Self {
+        if let Some(first) = members.first() {
+            self.model = first.model.clone();
+        }
+        if members.len() < 2 {
+            members.clear();
+        }
+        self.ensemble = members;
+        self
+    }
+
     /// Resize image if it exceeds Workers AI limits (~1MB as JSON array).
     /// Target: max 1024px on longest edge, JPEG quality 85.
     fn resize_if_needed(&self, image_bytes: &[u8]) -> Result<Vec<u8>, OcrError> {
@@ -89,9 +131,10 @@ impl WorkersAiClient {
 
         // Re-encode as JPEG
         let mut buf = Vec::new();
-        let mut cursor = Cursor::new(&mut buf);
+        let encoder = JpegEncoder::new_with_quality(&mut buf, JPEG_QUALITY);
         resized
-            .write_to(&mut cursor, ImageFormat::Jpeg)
+            .to_rgb8()
+            .write_with_encoder(encoder)
             .map_err(|e| OcrError::ImageProcessing(format!("Failed to encode JPEG: {}", e)))?;
 
         tracing::info!(
@@ -104,32 +147,86 @@ impl WorkersAiClient {
         Ok(buf)
     }
 
-    /// Send a label image to Workers AI LLaVA and extract structured fields.
-    pub async fn extract_label_fields(
+    /// Send a label image to Workers AI and extract structured fields, using
+    /// the active `label_fields` prompt, or to every ensemble member and vote.
+    ///
+    /// An ensemble tolerates failed members as long as one succeeds.
+    pub async fn extract_label_fields(&self, image_bytes: &[u8]) -> Result<OcrExtraction, OcrError> {
+        // Resize image if needed to avoid Workers AI payload size limits
+        let processed_bytes = self.resize_if_needed(image_bytes)?;
+
+        if self.ensemble.is_empty() {
+            let prompt = prompts::registry().active(prompts::LABEL_FIELDS);
+            return self.extract_with(processed_bytes, &self.model, prompt).await;
+        }
+
+        let results = join_all(
+            self.ensemble
+                .iter()
+                .map(|member| self.extract_with(processed_bytes.clone(), &member.model, member.prompt)),
+        )
+        .await;
+
+        let mut members = Vec::new();
+        let mut first_error = None;
+        for (member, result) in self.ensemble.iter().zip(results) {
+            match result {
+                Ok(extraction) => members.push(extraction),
+                Err(e) => {
+                    metrics::counter!("ocr_ensemble_member_failures_total", "model" => member.model.clone())
+                        .increment(1);
+                    tracing::warn!(member = %member, error = %e, "OCR ensemble member failed");
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if members.is_empty() {
+            return Err(first_error.expect("ensemble has members"));
+        }
+
+        // With a single surviving member there is nothing to vote on
+        let mut primary = members.remove(0);
+        if !members.is_empty() {
+            let readings: Vec<(String, ExtractedLabelFields)> = std::iter::once(&primary)
+                .chain(&members)
+                .map(|m| (format!("{} ({})", m.model, m.prompt), m.fields.clone()))
+                .collect();
+            primary.fields = ocr_ensemble::vote(&readings);
+
+            for disputed in primary.fields.consensus.iter().filter(|c| c.is_disputed()) {
+                metrics::counter!("ocr_ensemble_disagreements_total", "field" => disputed.field_name.clone())
+                    .increment(1);
+                tracing::warn!(
+                    field = %disputed.field_name,
+                    value = %disputed.value,
+                    confidence = disputed.confidence,
+                    readings = ?disputed.readings.iter().map(|r| (&r.source, &r.value)).collect::<Vec<_>>(),
+                    "OCR ensemble members disagree"
+                );
+            }
+        }
+        primary.members = members;
+
+        Ok(primary)
+    }
+
+    /// Extract label fields with one model and prompt version.
+    async fn extract_with(
         &self,
-        image_bytes: &[u8],
-    ) -> Result<ExtractedLabelFields, OcrError> {
+        processed_bytes: Vec<u8>,
+        model: &str,
+        prompt: Prompt,
+    ) -> Result<OcrExtraction, OcrError> {
         let url = format!(
-            "https://api.cloudflare.com/client/v4/accounts/{}/ai/run/@cf/llava-hf/llava-1.5-7b-hf",
-            self.account_id
-        );
-
-        let prompt = concat!(
-            "Analyze this beverage label image and extract the following fields as JSON: ",
-            "brand_name, class_type (e.g. Wine, Distilled Spirits, Malt Beverage), ",
-            "abv (alcohol by volume as a number), net_contents, ",
-            "country_of_origin, government_warning. ",
-            "Return ONLY valid JSON with these exact field names."
+            "https://api.cloudflare.com/client/v4/accounts/{}/ai/run/{}",
+            self.account_id, model
         );
 
-        // Resize image if needed to avoid Workers AI payload size limits
-        let processed_bytes = self.resize_if_needed(image_bytes)?;
-
         // Workers AI LLaVA expects image as raw byte array [u8], not base64
         let image_array: Vec<u8> = processed_bytes;
         let request_body = LlavaRequest {
             image: image_array,
-            prompt: prompt.to_string(),
+            prompt: prompt.text.to_string(),
             max_tokens: 512,
         };
 
@@ -165,39 +262,123 @@ impl WorkersAiClient {
 
         let description = llava_resp
             .result
-            .and_then(|r| r.description)
+            .and_then(|r| r.description.or(r.response))
             .ok_or_else(|| OcrError::Api(format!("No description in response: {}", body)))?;
 
-        tracing::info!(description_len = description.len(), description = %description, "LLaVA description extracted");
-
-        // Clean LLM output: strip Markdown-escaped underscores, trim whitespace
-        let cleaned = description
-            .replace("\\_", "_")
-            .trim()
-            .to_string();
-
-        // Try parsing into a lenient intermediate format first
-        let raw: RawLlavaFields = serde_json::from_str(&cleaned)
-            .map_err(|e| OcrError::Api(format!("JSON parse error: {} — cleaned text: {}", e, cleaned)))?;
-
-        // Convert ABV from string like "13.5%" to f64
-        let abv = raw.abv
-            .trim_end_matches('%')
-            .trim()
-            .parse::<f64>()
-            .unwrap_or(0.0);
-
-        Ok(ExtractedLabelFields {
-            brand_name: raw.brand_name,
-            class_type: raw.class_type,
-            abv,
-            net_contents: raw.net_contents,
-            country_of_origin: raw.country_of_origin,
-            government_warning: raw.government_warning,
+        tracing::info!(
+            description_len = description.len(),
+            description = %description,
+            model = %model,
+            prompt = %prompt,
+            "LLaVA description extracted"
+        );
+
+        let (fields, repairs) = match parse_label_fields(&description) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                return Err(OcrError::UnreadableOutput(Box::new(UnreadableOutput {
+                    model: model.to_string(),
+                    prompt,
+                    raw_output: description,
+                    reason: e.to_string(),
+                })))
+            }
+        };
+        for repair in &repairs {
+            metrics::counter!("ocr_json_repairs_total", "repair" => repair.as_str()).increment(1);
+        }
+        if !repairs.is_empty() {
+            tracing::warn!(
+                repairs = ?repairs.iter().map(|r| r.as_str()).collect::<Vec<_>>(),
+                description = %description,
+                model = %model,
+                prompt = %prompt,
+                "Repaired malformed JSON in LLaVA output"
+            );
+        }
+
+        Ok(OcrExtraction {
+            fields,
+            model: model.to_string(),
+            prompt,
+            raw_output: description,
+            repairs,
+            members: Vec::new(),
         })
     }
 }
 
+/// Register descriptions for the OCR metrics.
+pub fn describe_metrics() {
+    metrics::describe_counter!(
+        "ocr_json_repairs_total",
+        "LLaVA outputs that needed a JSON repair, by repair (see llm_json::Repair)"
+    );
+    metrics::describe_counter!(
+        "ocr_ensemble_disagreements_total",
+        "Fields OCR ensemble members read differently, by field"
+    );
+    metrics::describe_counter!(
+        "ocr_ensemble_member_failures_total",
+        "OCR ensemble member extractions that failed, by model"
+    );
+}
+
+/// Parse label fields from a LLaVA description.
+///
+/// The JSON object is located and repaired by `llm_json::extract_object`.
+/// Fields are read leniently: null or missing text fields become empty (the
+/// verification checks flag them), ABV may be a number or text such as
+/// "13.5% ALC/VOL", and disclosures may be a single string.
+fn parse_label_fields(description: &str) -> Result<(ExtractedLabelFields, Vec<Repair>), LlmJsonError> {
+    let Extraction { object, repairs } = llm_json::extract_object(description)?;
+    let field = |name: &str| object.get(name).unwrap_or(&Value::Null);
+
+    let fields = ExtractedLabelFields {
+        brand_name: text(field("brand_name")).unwrap_or_default(),
+        class_type: text(field("class_type")).unwrap_or_default(),
+        abv: abv(field("abv")).unwrap_or(0.0),
+        net_contents: text(field("net_contents")).unwrap_or_default(),
+        country_of_origin: text(field("country_of_origin")),
+        government_warning: text(field("government_warning")),
+        name_address_statement: text(field("name_address_statement")),
+        disclosure_statements: match field("disclosure_statements") {
+            Value::Array(items) => items.iter().filter_map(text).collect(),
+            other => text(other).into_iter().collect(),
+        },
+        consensus: Vec::new(),
+    };
+
+    Ok((fields, repairs))
+}
+
+/// Trimmed text of a string or number; `None` for null, empty or other values.
+fn text(value: &Value) -> Option<String> {
+    let text = match value {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    Some(text).filter(|t| !t.is_empty())
+}
+
+/// ABV from a number or the first number in a string ("13.5%", "Alc. 13,5% by vol.").
+fn abv(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => {
+            let start = s.find(|c: char| c.is_ascii_digit())?;
+            let number: String = s[start..]
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+                .map(|c| if c == ',' { '.' } else { c })
+                .collect();
+            number.trim_end_matches('.').parse().ok()
+        }
+        _ => None,
+    }
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum OcrError {
     #[error("HTTP request failed: {0}")]
@@ -211,4 +392,60 @@ pub enum OcrError {
 
     #[error("Failed to parse LLaVA response as structured fields: {0}")]
     Parse(#[from] serde_json::Error),
+
+    #[error("No label fields in {} ({}) output: {}", .0.model, .0.prompt, .0.reason)]
+    UnreadableOutput(Box<UnreadableOutput>),
+}
+
+/// A model answer that no label fields could be read from.
+#[derive(Debug, Clone)]
+pub struct UnreadableOutput {
+    /// Workers AI model ID.
+    pub model: String,
+    pub prompt: Prompt,
+    /// The model's description, as returned.
+    pub raw_output: String,
+    /// Why no JSON object could be read from it.
+    pub reason: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fenced_output_with_numeric_abv() {
+        let description = "Here are the fields:\n```json\n{\"brand\\_name\": \"Stone Creek\", \"class\\_type\": \"Merlot\", \"abv\": 13.5, \"net\\_contents\": \"750 mL\", \"country\\_of\\_origin\": null}\n```";
+        let (fields, repairs) = parse_label_fields(description).unwrap();
+        assert_eq!(fields.brand_name, "Stone Creek");
+        assert_eq!(fields.abv, 13.5);
+        assert_eq!(fields.country_of_origin, None);
+        assert!(fields.disclosure_statements.is_empty());
+        assert_eq!(repairs, vec![Repair::EscapedUnderscores, Repair::SurroundingText]);
+    }
+
+    #[test]
+    fn test_parse_lenient_field_values() {
+        let description = r#"{"brand_name": null, "class_type": "Wine", "abv": "Alc. 12,5% by vol.", "net_contents": 750, "disclosure_statements": "Contains Sulfites"}"#;
+        let (fields, repairs) = parse_label_fields(description).unwrap();
+        assert_eq!(fields.brand_name, "");
+        assert_eq!(fields.abv, 12.5);
+        assert_eq!(fields.net_contents, "750");
+        assert_eq!(fields.disclosure_statements, vec!["Contains Sulfites"]);
+        assert!(repairs.is_empty());
+    }
+
+    #[test]
+    fn test_abv_values() {
+        assert_eq!(abv(&serde_json::json!("13.5%")), Some(13.5));
+        assert_eq!(abv(&serde_json::json!(40)), Some(40.0));
+        assert_eq!(abv(&serde_json::json!("40.")), Some(40.0));
+        assert_eq!(abv(&serde_json::json!("unknown")), None);
+        assert_eq!(abv(&Value::Null), None);
+    }
+
+    #[test]
+    fn test_no_json_is_an_error() {
+        assert!(parse_label_fields("The image is too blurry to read.").is_err());
+    }
 }
@@ -0,0 +1,363 @@
+//! Multi-model OCR ensemble with field-level voting.
+//!
+//! `OCR_ENSEMBLE` lists the models (and optionally the `label_fields` prompt
+//! version) that read each label, e.g.
+//! `@cf/llava-hf/llava-1.5-7b-hf, @cf/meta/llama-3.2-11b-vision-instruct:1`.
+//! Every member extracts the fields independently and each field keeps the
+//! value most members agree on; ties go to a value over a blank reading, then
+//! to the member listed first. The agreement is recorded per field
+//! (`FieldConsensus`) so validation can flag disputed fields for review
+//! instead of trusting one model's answer.
+
+use std::fmt;
+use strsim::normalized_levenshtein;
+
+use crate::models::label::{ExtractedLabelFields, FieldConsensus, OcrReading};
+use crate::services::prompts::{self, Prompt};
+
+/// Minimum similarity for two readings of a long statement to agree
+/// (models differ in punctuation and line breaks on the government warning).
+const STATEMENT_AGREEMENT: f64 = 0.9;
+
+/// Readings of an ABV closer than this agree.
+const ABV_AGREEMENT: f64 = 0.05;
+
+/// A model and prompt version that reads each label.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnsembleMember {
+    /// Workers AI model ID.
+    pub model: String,
+    pub prompt: Prompt,
+}
+
+impl fmt::Display for EnsembleMember {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.model, self.prompt)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum EnsembleError {
+    #[error("Invalid OCR ensemble entry '{0}' (expected model or model:prompt_version)")]
+    InvalidEntry(String),
+
+    #[error("Unknown prompt version {prompt}@v{version} in OCR ensemble", prompt = prompts::LABEL_FIELDS)]
+    UnknownPromptVersion { version: i32 },
+}
+
+/// Parse `OCR_ENSEMBLE` (comma-separated `model[:prompt_version]` entries).
+///
+/// Entries without a version use the active `label_fields` prompt. An unset or
+/// empty spec gives a single member: `default_model` with the active prompt.
+pub fn members_from_spec(default_model: &str, spec: Option<&str>) -> Result<Vec<EnsembleMember>, EnsembleError> {
+    let active = prompts::registry().active(prompts::LABEL_FIELDS);
+    let mut members = Vec::new();
+
+    for entry in spec.unwrap_or("").split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (model, prompt) = match entry.split_once(':') {
+            Some((model, version)) => {
+                let version: i32 = version
+                    .trim()
+                    .trim_start_matches('v')
+                    .parse()
+                    .map_err(|_| EnsembleError::InvalidEntry(entry.to_string()))?;
+                let prompt = prompts::get(prompts::LABEL_FIELDS, version)
+                    .ok_or(EnsembleError::UnknownPromptVersion { version })?;
+                (model.trim(), prompt)
+            }
+            None => (entry, active),
+        };
+        if model.is_empty() {
+            return Err(EnsembleError::InvalidEntry(entry.to_string()));
+        }
+        members.push(EnsembleMember {
+            model: model.to_string(),
+            prompt,
+        });
+    }
+
+    if members.is_empty() {
+        members.push(EnsembleMember {
+            model: default_model.trim().to_string(),
+            prompt: active,
+        });
+    }
+
+    Ok(members)
+}
+
+/// Reconcile the fields read by each member (in ensemble order), recording the
+/// agreement on every field in `consensus`.
+///
+/// A field keeps the value read by the most members. A blank reading only
+/// loses ties, so one member's value (possibly hallucinated) doesn't replace
+/// what most members read as absent; the field is then disputed.
+///
+/// # Panics
+/// If `readings` is empty.
+pub fn vote(readings: &[(String, ExtractedLabelFields)]) -> ExtractedLabelFields {
+    assert!(!readings.is_empty(), "vote needs at least one reading");
+    let mut consensus = Vec::new();
+
+    let mut text = |name: &str, get: fn(&ExtractedLabelFields) -> &str, same: fn(&str, &str) -> bool| {
+        let values: Vec<&str> = readings.iter().map(|(_, f)| get(f)).collect();
+        let winner = decide(
+            name,
+            readings,
+            &values,
+            |v| v.to_string(),
+            |v| v.trim().is_empty(),
+            |a, b| same(a, b),
+            &mut consensus,
+        );
+        values[winner].to_string()
+    };
+    let brand_name = text("brand_name", |f| &f.brand_name, same_text);
+    let class_type = text("class_type", |f| &f.class_type, same_text);
+    let net_contents = text("net_contents", |f| &f.net_contents, same_text);
+    let country_of_origin = text(
+        "country_of_origin",
+        |f| f.country_of_origin.as_deref().unwrap_or(""),
+        same_text,
+    );
+    let government_warning = text(
+        "government_warning",
+        |f| f.government_warning.as_deref().unwrap_or(""),
+        same_statement,
+    );
+    let name_address_statement = text(
+        "name_address_statement",
+        |f| f.name_address_statement.as_deref().unwrap_or(""),
+        same_statement,
+    );
+
+    let abvs: Vec<f64> = readings.iter().map(|(_, f)| f.abv).collect();
+    let abv = abvs[decide(
+        "abv",
+        readings,
+        &abvs,
+        |v| if *v > 0.0 { format!("{:.1}", v) } else { String::new() },
+        |v| *v <= 0.0,
+        |a, b| (a - b).abs() < ABV_AGREEMENT,
+        &mut consensus,
+    )];
+
+    let disclosures: Vec<&Vec<String>> = readings.iter().map(|(_, f)| &f.disclosure_statements).collect();
+    let disclosure_statements = disclosures[decide(
+        "disclosure_statements",
+        readings,
+        &disclosures,
+        |v| v.join("; "),
+        |v| v.is_empty(),
+        |a, b| disclosure_set(a) == disclosure_set(b),
+        &mut consensus,
+    )]
+    .clone();
+
+    let non_empty = |s: String| (!s.trim().is_empty()).then_some(s);
+    ExtractedLabelFields {
+        brand_name,
+        class_type,
+        abv,
+        net_contents,
+        country_of_origin: non_empty(country_of_origin),
+        government_warning: non_empty(government_warning),
+        name_address_statement: non_empty(name_address_statement),
+        disclosure_statements,
+        consensus,
+    }
+}
+
+/// Group equal readings and return the index of the winning reading, pushing
+/// the field's consensus.
+fn decide<T>(
+    field_name: &str,
+    readings: &[(String, ExtractedLabelFields)],
+    values: &[T],
+    display: impl Fn(&T) -> String,
+    is_blank: impl Fn(&T) -> bool,
+    same: impl Fn(&T, &T) -> bool,
+    consensus: &mut Vec<FieldConsensus>,
+) -> usize {
+    // Each group is the indices of readings equal to its first (representative) reading
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (i, value) in values.iter().enumerate() {
+        match groups.iter_mut().find(|g| same(&values[g[0]], value)) {
+            Some(group) => group.push(i),
+            None => groups.push(vec![i]),
+        }
+    }
+
+    // Largest group, a value before a blank on ties; groups are in member
+    // order, so remaining ties keep the earlier member
+    let rank = |g: &Vec<usize>| (g.len(), !is_blank(&values[g[0]]));
+    let winner = groups
+        .iter()
+        .fold(&groups[0], |best, g| if rank(g) > rank(best) { g } else { best });
+
+    consensus.push(FieldConsensus {
+        field_name: field_name.to_string(),
+        value: display(&values[winner[0]]),
+        confidence: winner.len() as f64 / values.len() as f64,
+        readings: readings
+            .iter()
+            .zip(values)
+            .map(|((source, _), value)| OcrReading {
+                source: source.clone(),
+                value: display(value),
+            })
+            .collect(),
+    });
+
+    winner[0]
+}
+
+/// Lowercase letters and digits only, so spacing and punctuation don't count as disagreement.
+fn normalize(s: &str) -> String {
+    s.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+fn same_text(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+fn same_statement(a: &str, b: &str) -> bool {
+    normalized_levenshtein(&normalize(a), &normalize(b)) >= STATEMENT_AGREEMENT
+}
+
+fn disclosure_set(statements: &[String]) -> Vec<String> {
+    let mut set: Vec<String> = statements.iter().map(|s| normalize(s)).collect();
+    set.sort();
+    set.dedup();
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(brand: &str, abv: f64) -> ExtractedLabelFields {
+        ExtractedLabelFields {
+            brand_name: brand.to_string(),
+            class_type: "Table Red Wine".to_string(),
+            abv,
+            net_contents: "750 mL".to_string(),
+            country_of_origin: None,
+            government_warning: Some("GOVERNMENT WARNING: (1) According to the Surgeon General...".to_string()),
+            name_address_statement: None,
+            disclosure_statements: vec!["Contains Sulfites".to_string()],
+            consensus: Vec::new(),
+        }
+    }
+
+    fn reading(source: &str, fields: ExtractedLabelFields) -> (String, ExtractedLabelFields) {
+        (source.to_string(), fields)
+    }
+
+    fn field<'a>(voted: &'a ExtractedLabelFields, name: &str) -> &'a FieldConsensus {
+        voted.consensus.iter().find(|c| c.field_name == name).unwrap()
+    }
+
+    #[test]
+    fn test_unanimous_readings() {
+        let mut spaced = fields("STONE CREEK", 13.5);
+        spaced.net_contents = "750ml".to_string();
+        spaced.government_warning = Some("GOVERNMENT WARNING (1) According to the Surgeon General".to_string());
+        let voted = vote(&[reading("a", fields("Stone Creek", 13.5)), reading("b", spaced)]);
+
+        assert_eq!(voted.brand_name, "Stone Creek");
+        assert!(voted.consensus.iter().all(|c| !c.is_disputed()), "{:?}", voted.consensus);
+        assert_eq!(voted.consensus.len(), 8);
+    }
+
+    #[test]
+    fn test_majority_wins() {
+        let voted = vote(&[
+            reading("a", fields("Stone Creek", 18.5)),
+            reading("b", fields("Stone Creek", 13.5)),
+            reading("c", fields("Stone Creak", 13.5)),
+        ]);
+
+        assert_eq!(voted.abv, 13.5);
+        assert_eq!(voted.brand_name, "Stone Creek");
+        let abv = field(&voted, "abv");
+        assert!(abv.is_disputed());
+        assert!((abv.confidence - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(abv.readings[0].value, "18.5");
+        assert_eq!(abv.readings[0].source, "a");
+    }
+
+    #[test]
+    fn test_tie_keeps_first_member() {
+        let voted = vote(&[reading("a", fields("Stone Creek", 13.5)), reading("b", fields("Stone Creak", 15.5))]);
+
+        assert_eq!(voted.brand_name, "Stone Creek");
+        assert_eq!(voted.abv, 13.5);
+        assert_eq!(field(&voted, "brand_name").confidence, 0.5);
+    }
+
+    #[test]
+    fn test_blank_reading_loses_ties() {
+        let mut missed = fields("", 0.0);
+        missed.disclosure_statements.clear();
+        let voted = vote(&[reading("a", missed), reading("b", fields("Stone Creek", 13.5))]);
+
+        assert_eq!(voted.brand_name, "Stone Creek");
+        assert_eq!(voted.abv, 13.5);
+        assert_eq!(voted.disclosure_statements, vec!["Contains Sulfites"]);
+        assert!(field(&voted, "abv").is_disputed());
+        // Nobody read a country: agreement on "none"
+        assert!(!field(&voted, "country_of_origin").is_disputed());
+        assert_eq!(voted.country_of_origin, None);
+    }
+
+    #[test]
+    fn test_blank_majority_kept() {
+        let mut blank = fields("Stone Creek", 13.5);
+        blank.government_warning = None;
+        let voted = vote(&[
+            reading("a", fields("Stone Creek", 13.5)),
+            reading("b", blank.clone()),
+            reading("c", blank),
+        ]);
+
+        assert_eq!(voted.government_warning, None);
+        let warning = field(&voted, "government_warning");
+        assert!(warning.is_disputed());
+        assert!((warning.confidence - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(warning.value, "");
+    }
+
+    #[test]
+    fn test_members_from_spec() {
+        let members = members_from_spec("@cf/default", None).unwrap();
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].model, "@cf/default");
+
+        let members = members_from_spec(
+            "@cf/default",
+            Some("@cf/llava-hf/llava-1.5-7b-hf, @cf/meta/llama-3.2-11b-vision-instruct:v1"),
+        )
+        .unwrap();
+        assert_eq!(members.len(), 2);
+        assert_eq!(members[1].model, "@cf/meta/llama-3.2-11b-vision-instruct");
+        assert_eq!(members[1].prompt.version, 1);
+        assert_eq!(members[1].to_string(), "@cf/meta/llama-3.2-11b-vision-instruct (label_fields@v1)");
+    }
+
+    #[test]
+    fn test_invalid_spec() {
+        assert!(matches!(
+            members_from_spec("@cf/default", Some("@cf/a:99")),
+            Err(EnsembleError::UnknownPromptVersion { version: 99 })
+        ));
+        assert!(matches!(
+            members_from_spec("@cf/default", Some("@cf/a:x")),
+            Err(EnsembleError::InvalidEntry(_))
+        ));
+        assert!(matches!(
+            members_from_spec("@cf/default", Some(":1")),
+            Err(EnsembleError::InvalidEntry(_))
+        ));
+    }
+}
@@ -0,0 +1,255 @@
+//! Country-of-origin normalization and geographic designation rules.
+//!
+//! Country names are normalized to ISO 3166-1 alpha-2 codes so that label text
+//! ("Product of France"), TTB COLA origin descriptions ("FRANCE", "CALIFORNIA")
+//! and reference data can be compared directly.
+//!
+//! Geographic designations follow 27 CFR 5.22 and the trade agreements recognized
+//! by the TTB (Scotch, Cognac, Tequila, Bourbon, Tennessee Whiskey).
+
+/// ISO 3166-1 alpha-2 code for the United States.
+pub const US: &str = "US";
+
+/// Country names and common label variants mapped to ISO 3166-1 alpha-2 codes.
+pub const COUNTRY_ALIASES: &[(&str, &str)] = &[
+    ("united states", "US"),
+    ("united states of america", "US"),
+    ("usa", "US"),
+    ("u.s.a", "US"),
+    ("u.s", "US"),
+    ("us", "US"),
+    ("america", "US"),
+    ("united kingdom", "GB"),
+    ("uk", "GB"),
+    ("great britain", "GB"),
+    ("scotland", "GB"),
+    ("england", "GB"),
+    ("wales", "GB"),
+    ("northern ireland", "GB"),
+    ("ireland", "IE"),
+    ("republic of ireland", "IE"),
+    ("france", "FR"),
+    ("italy", "IT"),
+    ("spain", "ES"),
+    ("portugal", "PT"),
+    ("germany", "DE"),
+    ("austria", "AT"),
+    ("switzerland", "CH"),
+    ("belgium", "BE"),
+    ("netherlands", "NL"),
+    ("holland", "NL"),
+    ("denmark", "DK"),
+    ("sweden", "SE"),
+    ("norway", "NO"),
+    ("finland", "FI"),
+    ("iceland", "IS"),
+    ("poland", "PL"),
+    ("czech republic", "CZ"),
+    ("czechia", "CZ"),
+    ("hungary", "HU"),
+    ("greece", "GR"),
+    ("croatia", "HR"),
+    ("slovenia", "SI"),
+    ("romania", "RO"),
+    ("bulgaria", "BG"),
+    ("russia", "RU"),
+    ("russian federation", "RU"),
+    ("ukraine", "UA"),
+    ("israel", "IL"),
+    ("lebanon", "LB"),
+    ("turkey", "TR"),
+    ("mexico", "MX"),
+    ("canada", "CA"),
+    ("jamaica", "JM"),
+    ("barbados", "BB"),
+    ("trinidad and tobago", "TT"),
+    ("puerto rico", "PR"),
+    ("dominican republic", "DO"),
+    ("cuba", "CU"),
+    ("haiti", "HT"),
+    ("guatemala", "GT"),
+    ("nicaragua", "NI"),
+    ("venezuela", "VE"),
+    ("guyana", "GY"),
+    ("brazil", "BR"),
+    ("argentina", "AR"),
+    ("chile", "CL"),
+    ("peru", "PE"),
+    ("uruguay", "UY"),
+    ("south africa", "ZA"),
+    ("australia", "AU"),
+    ("new zealand", "NZ"),
+    ("japan", "JP"),
+    ("china", "CN"),
+    ("south korea", "KR"),
+    ("korea", "KR"),
+    ("republic of korea", "KR"),
+    ("taiwan", "TW"),
+    ("india", "IN"),
+    ("thailand", "TH"),
+    ("philippines", "PH"),
+    ("vietnam", "VN"),
+];
+
+/// U.S. states and territories as they appear in TTB COLA origin descriptions.
+///
+/// "Georgia" is deliberately absent here and from `COUNTRY_ALIASES` since it is
+/// ambiguous between the state and the country.
+const US_STATES: &[&str] = &[
+    "alabama", "alaska", "arizona", "arkansas", "california", "colorado", "connecticut",
+    "delaware", "district of columbia", "florida", "hawaii", "idaho", "illinois", "indiana",
+    "iowa", "kansas", "kentucky", "louisiana", "maine", "maryland", "massachusetts", "michigan",
+    "minnesota", "mississippi", "missouri", "montana", "nebraska", "nevada", "new hampshire",
+    "new jersey", "new mexico", "new york", "north carolina", "north dakota", "ohio", "oklahoma",
+    "oregon", "pennsylvania", "rhode island", "south carolina", "south dakota", "tennessee",
+    "texas", "utah", "vermont", "virginia", "washington", "west virginia", "wisconsin",
+    "wyoming",
+];
+
+/// Label prefixes commonly placed before the country name.
+const ORIGIN_PREFIXES: &[&str] = &["product of", "produce of", "made in", "imported from"];
+
+/// Geographic designations of origin and the country they must come from.
+///
+/// Tuple: (class/type keyword, required ISO code, region description, CFR reference).
+/// Longer keywords come first so "Tennessee Whiskey" is matched before "Whiskey" variants.
+pub const GEOGRAPHIC_DESIGNATIONS: &[(&str, &str, &str, &str)] = &[
+    ("tennessee whiskey", "US", "the United States (Tennessee)", "27 CFR 5.22(b)"),
+    ("scotch", "GB", "Scotland", "27 CFR 5.22(b)(8)"),
+    ("cognac", "FR", "France (Cognac region)", "27 CFR 5.22(d)"),
+    ("armagnac", "FR", "France (Armagnac region)", "27 CFR 5.22(d)"),
+    ("tequila", "MX", "Mexico", "27 CFR 5.22(g)"),
+    ("mezcal", "MX", "Mexico", "27 CFR 5.22(g)"),
+    ("bourbon", "US", "the United States", "27 CFR 5.22(b)(1)"),
+    ("irish whiskey", "IE", "Ireland", "27 CFR 5.22(b)(9)"),
+    ("canadian whisky", "CA", "Canada", "27 CFR 5.22(b)(10)"),
+];
+
+/// A geographic designation found in a class/type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeographicDesignation {
+    /// The designation keyword matched (e.g. "scotch").
+    pub designation: &'static str,
+    /// ISO code of the country the product must originate from.
+    pub required_country: &'static str,
+    /// Human-readable description of the required origin.
+    pub region: &'static str,
+    /// CFR citation for the designation.
+    pub cfr_reference: &'static str,
+}
+
+/// Normalize a country name, label origin statement, or TTB origin description
+/// to an ISO 3166-1 alpha-2 code.
+///
+/// U.S. state names (as used in TTB COLA origin descriptions) map to "US".
+/// Inputs that are already two-letter ISO codes are accepted as-is.
+pub fn normalize_country(input: &str) -> Option<&'static str> {
+    let mut lower = input.trim().to_lowercase();
+
+    for prefix in ORIGIN_PREFIXES {
+        if let Some(rest) = lower.strip_prefix(prefix) {
+            lower = rest.trim().to_string();
+            break;
+        }
+    }
+    let lower = lower.trim_end_matches('.').trim();
+
+    if lower.is_empty() {
+        return None;
+    }
+
+    if let Some((_, code)) = COUNTRY_ALIASES.iter().find(|(alias, _)| *alias == lower) {
+        return Some(code);
+    }
+
+    if US_STATES.contains(&lower) {
+        return Some(US);
+    }
+
+    // Accept bare ISO codes ("FR", "mx")
+    if lower.len() == 2 {
+        let upper = lower.to_uppercase();
+        return COUNTRY_ALIASES
+            .iter()
+            .map(|(_, code)| *code)
+            .find(|code| *code == upper);
+    }
+
+    None
+}
+
+/// Find a geographic designation of origin in a class/type designation.
+///
+/// Keywords match whole words only, so "Butterscotch Schnapps" is not Scotch.
+pub fn find_geographic_designation(class_type: &str) -> Option<GeographicDesignation> {
+    let lower = class_type.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    GEOGRAPHIC_DESIGNATIONS
+        .iter()
+        .find(|(keyword, _, _, _)| {
+            let keyword: Vec<&str> = keyword.split(' ').collect();
+            words.windows(keyword.len()).any(|window| window == keyword.as_slice())
+        })
+        .map(|(designation, required_country, region, cfr_reference)| GeographicDesignation {
+            designation,
+            required_country,
+            region,
+            cfr_reference,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_country_names() {
+        assert_eq!(normalize_country("France"), Some("FR"));
+        assert_eq!(normalize_country("Product of Mexico"), Some("MX"));
+        assert_eq!(normalize_country("U.S.A."), Some("US"));
+        assert_eq!(normalize_country("Scotland"), Some("GB"));
+    }
+
+    #[test]
+    fn test_normalize_ttb_origin_desc() {
+        assert_eq!(normalize_country("CALIFORNIA"), Some("US"));
+        assert_eq!(normalize_country("KENTUCKY"), Some("US"));
+        assert_eq!(normalize_country("ITALY"), Some("IT"));
+    }
+
+    #[test]
+    fn test_normalize_iso_codes_and_unknown() {
+        assert_eq!(normalize_country("fr"), Some("FR"));
+        assert_eq!(normalize_country("Atlantis"), None);
+        assert_eq!(normalize_country("   "), None);
+    }
+
+    #[test]
+    fn test_geographic_designations() {
+        let scotch = find_geographic_designation("Blended Scotch Whisky").unwrap();
+        assert_eq!(scotch.required_country, "GB");
+
+        let tn = find_geographic_designation("Tennessee Whiskey").unwrap();
+        assert_eq!(tn.designation, "tennessee whiskey");
+
+        assert_eq!(
+            find_geographic_designation("Kentucky Straight Bourbon Whiskey").map(|d| d.required_country),
+            Some("US")
+        );
+        assert!(find_geographic_designation("Vodka").is_none());
+    }
+
+    #[test]
+    fn test_geographic_designation_whole_words() {
+        assert!(find_geographic_designation("Butterscotch Schnapps").is_none());
+        assert!(find_geographic_designation("Butterscotch-Flavored Liqueur").is_none());
+        assert_eq!(
+            find_geographic_designation("Single Malt Scotch, Aged 12 Years").map(|d| d.designation),
+            Some("scotch")
+        );
+        assert_eq!(normalize_country("American"), None);
+    }
+}
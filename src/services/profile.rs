@@ -0,0 +1,401 @@
+//! Validation profiles: named sets of matching thresholds and ABV limits.
+//!
+//! A profile is chosen per request (explicit `profile` name), per tenant
+//! (`VALIDATION_TENANT_PROFILES`), or falls back to the default profile.
+//! Individual values can additionally be overridden per request: a request
+//! may tighten any value, but only loosen one as far as the limit configured
+//! for it in `VALIDATION_OVERRIDE_LIMITS` (same `key=value, ...` format).
+//!
+//! Profiles are configured with `VALIDATION_PROFILES`, a semicolon-separated
+//! list of `name: key=value, ...` entries. Every profile starts from the
+//! built-in `standard` values, e.g.
+//! `import: brand_threshold=0.78, ttb_brand_cutoff=0.72; strict: brand_threshold=0.92`.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use crate::services::ttb_standards::CLASS_MATCH_THRESHOLD;
+
+/// Name of the built-in profile.
+pub const STANDARD_PROFILE: &str = "standard";
+
+/// Largest `abv_tolerance` a profile may use: the widest labeling tolerance
+/// in 27 CFR 4.36 (wine at 14% ABV or less, ±1.5 percentage points).
+pub const MAX_ABV_TOLERANCE: f64 = 1.5;
+
+/// Largest deviation a profile may allow from a database or COLA reference
+/// ABV (class-inferred ABVs are typical values, not approved ones).
+pub const MAX_ABV_DEVIATION: f64 = 5.0;
+
+/// Kind of a profile setting, which decides its range and which way is looser.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Setting {
+    /// Minimum similarity in [0, 1]; a lower value is looser.
+    Threshold,
+    /// Allowed ABV deviation from 0 up to the cap; a higher value is looser.
+    AbvLimit(f64),
+}
+
+impl Setting {
+    fn in_range(self, value: f64) -> bool {
+        match self {
+            Setting::Threshold => (0.0..=1.0).contains(&value),
+            Setting::AbvLimit(max) => (0.0..=max).contains(&value),
+        }
+    }
+
+    /// Whether `value` is looser than `reference`.
+    fn is_looser(self, value: f64, reference: f64) -> bool {
+        match self {
+            Setting::Threshold => value < reference,
+            Setting::AbvLimit(_) => value > reference,
+        }
+    }
+}
+
+/// Setting kind for a profile key.
+fn setting(key: &str) -> Result<Setting, ProfileError> {
+    match key {
+        "brand_threshold" | "class_threshold" | "bottler_threshold" | "class_standard_threshold"
+        | "ttb_brand_cutoff" => Ok(Setting::Threshold),
+        "abv_tolerance" => Ok(Setting::AbvLimit(MAX_ABV_TOLERANCE)),
+        "abv_database_deviation" | "abv_fuzzy_deviation" | "abv_ttb_cola_deviation" => {
+            Ok(Setting::AbvLimit(MAX_ABV_DEVIATION))
+        }
+        other => Err(ProfileError::UnknownKey(other.to_string())),
+    }
+}
+
+/// Parse comma-separated `key=value` pairs, checking each value's range.
+fn parse_settings(spec: &str) -> Result<Vec<(&str, Setting, f64)>, ProfileError> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+        .map(|pair| {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| ProfileError::InvalidEntry(pair.to_string()))?;
+            let key = key.trim();
+            let setting = setting(key)?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .map_err(|_| ProfileError::InvalidEntry(pair.to_string()))?;
+            if !setting.in_range(value) {
+                return Err(ProfileError::OutOfRange(pair.to_string()));
+            }
+            Ok((key, setting, value))
+        })
+        .collect()
+}
+
+/// Global profile registry (set once at startup, defaults otherwise).
+static PROFILE_REGISTRY: OnceLock<ProfileRegistry> = OnceLock::new();
+
+/// Install the registry used by `registry()`. Later calls are ignored.
+pub fn init(registry: ProfileRegistry) {
+    let _ = PROFILE_REGISTRY.set(registry);
+}
+
+/// Get the configured registry, or one containing only `standard`.
+pub fn registry() -> &'static ProfileRegistry {
+    PROFILE_REGISTRY.get_or_init(ProfileRegistry::default)
+}
+
+/// Thresholds and limits applied by `services::validation`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationProfile {
+    pub name: String,
+    /// Minimum brand similarity against the expected brand.
+    pub brand_threshold: f64,
+    /// Minimum class/type similarity against the expected class/type.
+    pub class_threshold: f64,
+    /// Minimum bottler/producer name similarity against the database record.
+    pub bottler_threshold: f64,
+    /// Minimum similarity to a TTB standard of identity.
+    pub class_standard_threshold: f64,
+    /// Minimum brand similarity for a TTB COLA record to count as a match.
+    pub ttb_brand_cutoff: f64,
+    /// Allowed deviation from the expected ABV (percentage points).
+    pub abv_tolerance: f64,
+    /// Allowed deviation from an exact database match's ABV.
+    pub abv_database_deviation: f64,
+    /// Allowed deviation from a fuzzy database match's ABV.
+    pub abv_fuzzy_deviation: f64,
+    /// Allowed deviation from the ABV inferred from a TTB COLA class.
+    pub abv_ttb_cola_deviation: f64,
+}
+
+impl Default for ValidationProfile {
+    fn default() -> Self {
+        Self {
+            name: STANDARD_PROFILE.to_string(),
+            brand_threshold: 0.85,
+            class_threshold: 0.85,
+            bottler_threshold: 0.85,
+            class_standard_threshold: CLASS_MATCH_THRESHOLD,
+            ttb_brand_cutoff: 0.80,
+            abv_tolerance: 0.3,
+            abv_database_deviation: 1.0,
+            abv_fuzzy_deviation: 2.0,
+            abv_ttb_cola_deviation: 3.0,
+        }
+    }
+}
+
+impl ValidationProfile {
+    /// Apply comma-separated `key=value` overrides (keys are the field names).
+    ///
+    /// Thresholds must be in [0, 1], `abv_tolerance` at most `MAX_ABV_TOLERANCE`
+    /// and the database/COLA deviations at most `MAX_ABV_DEVIATION`.
+    pub fn apply_overrides(&mut self, spec: &str) -> Result<(), ProfileError> {
+        for (key, _, value) in parse_settings(spec)? {
+            *self.slot(key) = value;
+        }
+        Ok(())
+    }
+
+    /// The field for a key already checked by `setting`.
+    fn slot(&mut self, key: &str) -> &mut f64 {
+        match key {
+            "brand_threshold" => &mut self.brand_threshold,
+            "class_threshold" => &mut self.class_threshold,
+            "bottler_threshold" => &mut self.bottler_threshold,
+            "class_standard_threshold" => &mut self.class_standard_threshold,
+            "ttb_brand_cutoff" => &mut self.ttb_brand_cutoff,
+            "abv_tolerance" => &mut self.abv_tolerance,
+            "abv_database_deviation" => &mut self.abv_database_deviation,
+            "abv_fuzzy_deviation" => &mut self.abv_fuzzy_deviation,
+            "abv_ttb_cola_deviation" => &mut self.abv_ttb_cola_deviation,
+            other => unreachable!("unchecked profile key {other}"),
+        }
+    }
+}
+
+/// Named profiles plus the tenant → profile mapping.
+#[derive(Debug, Clone)]
+pub struct ProfileRegistry {
+    profiles: HashMap<String, ValidationProfile>,
+    tenants: HashMap<String, String>,
+    default_profile: String,
+    /// Loosest value a request override may set, per key (tighten-only if absent).
+    override_limits: HashMap<String, f64>,
+}
+
+impl Default for ProfileRegistry {
+    fn default() -> Self {
+        Self {
+            profiles: HashMap::from([(STANDARD_PROFILE.to_string(), ValidationProfile::default())]),
+            tenants: HashMap::new(),
+            default_profile: STANDARD_PROFILE.to_string(),
+            override_limits: HashMap::new(),
+        }
+    }
+}
+
+impl ProfileRegistry {
+    /// Build a registry from the `VALIDATION_PROFILES`, `VALIDATION_TENANT_PROFILES`
+    /// (`tenant=profile, ...`), `VALIDATION_DEFAULT_PROFILE` and
+    /// `VALIDATION_OVERRIDE_LIMITS` settings.
+    pub fn from_specs(
+        profiles: Option<&str>,
+        tenants: Option<&str>,
+        default_profile: Option<&str>,
+        override_limits: Option<&str>,
+    ) -> Result<Self, ProfileError> {
+        let mut registry = Self::default();
+
+        for entry in profiles.unwrap_or_default().split(';').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, overrides) = entry.split_once(':').unwrap_or((entry, ""));
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(ProfileError::InvalidEntry(entry.to_string()));
+            }
+            let mut profile = ValidationProfile {
+                name: name.to_string(),
+                ..ValidationProfile::default()
+            };
+            profile.apply_overrides(overrides)?;
+            registry.profiles.insert(name.to_string(), profile);
+        }
+
+        for pair in tenants.unwrap_or_default().split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (tenant, name) = pair
+                .split_once('=')
+                .ok_or_else(|| ProfileError::InvalidEntry(pair.to_string()))?;
+            let name = name.trim();
+            if !registry.profiles.contains_key(name) {
+                return Err(ProfileError::UnknownProfile(name.to_string()));
+            }
+            registry.tenants.insert(tenant.trim().to_string(), name.to_string());
+        }
+
+        if let Some(name) = default_profile.map(str::trim).filter(|n| !n.is_empty()) {
+            if !registry.profiles.contains_key(name) {
+                return Err(ProfileError::UnknownProfile(name.to_string()));
+            }
+            registry.default_profile = name.to_string();
+        }
+
+        for (key, _, limit) in parse_settings(override_limits.unwrap_or_default())? {
+            registry.override_limits.insert(key.to_string(), limit);
+        }
+
+        Ok(registry)
+    }
+
+    /// Look up a profile by name.
+    pub fn get(&self, name: &str) -> Option<&ValidationProfile> {
+        self.profiles.get(name)
+    }
+
+    /// The profile used when neither the request nor its tenant selects one.
+    pub fn default_profile(&self) -> &ValidationProfile {
+        &self.profiles[&self.default_profile]
+    }
+
+    /// Resolve the effective profile for a request.
+    ///
+    /// Precedence: explicit profile name, then the tenant's profile, then the
+    /// default. Request overrides are applied last and mark the name with
+    /// `+request` so results show the thresholds were adjusted. An override may
+    /// tighten a value freely but loosen it only up to its configured limit.
+    pub fn resolve(
+        &self,
+        profile: Option<&str>,
+        tenant: Option<&str>,
+        overrides: Option<&str>,
+    ) -> Result<ValidationProfile, ProfileError> {
+        let mut resolved = match (profile, tenant.and_then(|t| self.tenants.get(t))) {
+            (Some(name), _) => self
+                .get(name)
+                .ok_or_else(|| ProfileError::UnknownProfile(name.to_string()))?,
+            (None, Some(name)) => &self.profiles[name],
+            (None, None) => self.default_profile(),
+        }
+        .clone();
+
+        if let Some(spec) = overrides.filter(|s| !s.trim().is_empty()) {
+            for (key, setting, value) in parse_settings(spec)? {
+                let slot = resolved.slot(key);
+                let limit = self.override_limits.get(key).copied().unwrap_or(*slot);
+                if setting.is_looser(value, *slot) && setting.is_looser(value, limit) {
+                    return Err(ProfileError::OverrideTooLoose {
+                        key: key.to_string(),
+                        value,
+                        limit: if setting.is_looser(limit, *slot) { limit } else { *slot },
+                    });
+                }
+                *slot = value;
+            }
+            resolved.name = format!("{}+request", resolved.name);
+        }
+
+        Ok(resolved)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileError {
+    #[error("Invalid validation profile entry: {0}")]
+    InvalidEntry(String),
+
+    #[error("Unknown validation profile setting: {0}")]
+    UnknownKey(String),
+
+    #[error("Unknown validation profile: {0}")]
+    UnknownProfile(String),
+
+    #[error("Validation profile value out of range: {0}")]
+    OutOfRange(String),
+
+    #[error("Request override {key}={value} is looser than allowed ({limit})")]
+    OverrideTooLoose { key: String, value: f64, limit: f64 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn registry() -> ProfileRegistry {
+        ProfileRegistry::from_specs(
+            Some("import: brand_threshold=0.78, ttb_brand_cutoff=0.72; strict: brand_threshold=0.92"),
+            Some("import-team=import"),
+            None,
+            Some("abv_tolerance=1.0, brand_threshold=0.7"),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_profiles_start_from_standard() {
+        let registry = registry();
+        let import = registry.get("import").unwrap();
+        assert_eq!(import.brand_threshold, 0.78);
+        assert_eq!(import.ttb_brand_cutoff, 0.72);
+        assert_eq!(import.abv_tolerance, 0.3);
+        assert_eq!(registry.default_profile().name, STANDARD_PROFILE);
+    }
+
+    #[test]
+    fn test_resolve_precedence() {
+        let registry = registry();
+        assert_eq!(registry.resolve(None, None, None).unwrap().name, "standard");
+        assert_eq!(registry.resolve(None, Some("import-team"), None).unwrap().name, "import");
+        assert_eq!(registry.resolve(None, Some("other-team"), None).unwrap().name, "standard");
+        assert_eq!(
+            registry.resolve(Some("strict"), Some("import-team"), None).unwrap().name,
+            "strict"
+        );
+    }
+
+    #[test]
+    fn test_request_overrides() {
+        let resolved = registry()
+            .resolve(None, Some("import-team"), Some("abv_tolerance=0.5"))
+            .unwrap();
+        assert_eq!(resolved.name, "import+request");
+        assert_eq!(resolved.abv_tolerance, 0.5);
+        assert_eq!(resolved.brand_threshold, 0.78);
+    }
+
+    #[test]
+    fn test_invalid_specs_rejected() {
+        assert!(ProfileRegistry::from_specs(Some("x: brand=0.8"), None, None, None).is_err());
+        assert!(ProfileRegistry::from_specs(None, Some("team=missing"), None, None).is_err());
+        assert!(ProfileRegistry::from_specs(None, None, Some("missing"), None).is_err());
+        assert!(ProfileRegistry::from_specs(Some("x: brand_threshold=1.2"), None, None, None).is_err());
+        assert!(ProfileRegistry::from_specs(Some("x: abv_tolerance=2"), None, None, None).is_err());
+        assert!(ProfileRegistry::from_specs(None, None, None, Some("abv_fuzzy_deviation=50")).is_err());
+        assert!(registry().resolve(Some("missing"), None, None).is_err());
+        assert!(registry().resolve(None, None, Some("abv_tolerance=-1")).is_err());
+        assert!(registry().resolve(None, None, Some("brand_threshold=NaN")).is_err());
+        assert!(registry().resolve(None, None, Some("class_threshold=1.5")).is_err());
+    }
+
+    #[test]
+    fn test_request_overrides_loosen_only_within_limits() {
+        let registry = registry();
+        // Tightening is always allowed
+        let resolved = registry
+            .resolve(None, None, Some("class_threshold=0.95, abv_fuzzy_deviation=0.5"))
+            .unwrap();
+        assert_eq!(resolved.class_threshold, 0.95);
+        assert_eq!(resolved.abv_fuzzy_deviation, 0.5);
+
+        // Loosening up to the configured limit is allowed, past it is not
+        assert!(registry.resolve(None, None, Some("brand_threshold=0.7")).is_ok());
+        assert!(matches!(
+            registry.resolve(None, None, Some("brand_threshold=0.69")),
+            Err(ProfileError::OverrideTooLoose { .. })
+        ));
+        assert!(registry.resolve(None, None, Some("abv_tolerance=1.0")).is_ok());
+        assert!(registry.resolve(None, None, Some("abv_tolerance=1.1")).is_err());
+
+        // Keys without a limit can only be tightened
+        assert!(registry.resolve(None, None, Some("class_threshold=0.84")).is_err());
+        assert!(registry.resolve(None, None, Some("abv_fuzzy_deviation=2.5")).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Versioned prompts for model extraction.
+//!
+//! Prompts are built in and identified by name and version. A version is
+//! never edited once released: a changed prompt gets a new version, so the
+//! `ocr_prompt_name`/`ocr_prompt_version` recorded on a job always identify
+//! the exact text that produced its extraction. The newest version of each
+//! prompt is active unless pinned with `OCR_PROMPT_VERSIONS`, a
+//! comma-separated list of `name=version` entries (e.g. `label_fields=1`).
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::OnceLock;
+
+/// Prompt that extracts `ExtractedLabelFields` from a label image.
+pub const LABEL_FIELDS: &str = "label_fields";
+
+/// Workers AI model used for extraction unless `OCR_MODEL` is set.
+pub const DEFAULT_MODEL: &str = "@cf/llava-hf/llava-1.5-7b-hf";
+
+const LABEL_FIELDS_V1: &str = concat!(
+    "Analyze this beverage label image and extract the following fields as JSON: ",
+    "brand_name, class_type (e.g. Wine, Distilled Spirits, Malt Beverage), ",
+    "abv (alcohol by volume as a number), net_contents, ",
+    "country_of_origin, government_warning, ",
+    "name_address_statement (the full \"Bottled by\", \"Imported by\" or ",
+    "\"Distilled by\" statement including name and address), ",
+    "disclosure_statements (array of every allergen or additive statement, ",
+    "e.g. \"Contains Sulfites\", \"FD&C Yellow No. 5\", \"Contains Cochineal Extract\", ",
+    "\"PHENYLKETONURICS: CONTAINS PHENYLALANINE\"; empty array if none). ",
+    "Return ONLY valid JSON with these exact field names."
+);
+
+/// Every released prompt version.
+const BUILTIN_PROMPTS: &[Prompt] = &[Prompt {
+    name: LABEL_FIELDS,
+    version: 1,
+    text: LABEL_FIELDS_V1,
+}];
+
+/// Global prompt registry (set once at startup, defaults otherwise).
+static PROMPT_REGISTRY: OnceLock<PromptRegistry> = OnceLock::new();
+
+/// Install the registry used by `registry()`. Later calls are ignored.
+pub fn init(registry: PromptRegistry) {
+    let _ = PROMPT_REGISTRY.set(registry);
+}
+
+/// Get the configured registry, or one with the newest version of every prompt active.
+pub fn registry() -> &'static PromptRegistry {
+    PROMPT_REGISTRY.get_or_init(PromptRegistry::default)
+}
+
+/// A released prompt version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Prompt {
+    pub name: &'static str,
+    pub version: i32,
+    pub text: &'static str,
+}
+
+impl fmt::Display for Prompt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@v{}", self.name, self.version)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PromptError {
+    #[error("Invalid prompt version entry '{0}' (expected name=version)")]
+    InvalidEntry(String),
+
+    #[error("Unknown prompt version {name}@v{version}")]
+    UnknownVersion { name: String, version: i32 },
+}
+
+/// Released prompts and the version of each that is active.
+#[derive(Debug, Clone)]
+pub struct PromptRegistry {
+    active: HashMap<&'static str, Prompt>,
+}
+
+impl Default for PromptRegistry {
+    fn default() -> Self {
+        let mut active: HashMap<&'static str, Prompt> = HashMap::new();
+        for prompt in BUILTIN_PROMPTS {
+            let newest = active.get(prompt.name).is_none_or(|current| prompt.version > current.version);
+            if newest {
+                active.insert(prompt.name, *prompt);
+            }
+        }
+        Self { active }
+    }
+}
+
+impl PromptRegistry {
+    /// Build the registry, pinning the versions listed in `spec`
+    /// (`OCR_PROMPT_VERSIONS`, e.g. `label_fields=1`).
+    pub fn from_spec(spec: Option<&str>) -> Result<Self, PromptError> {
+        let mut registry = Self::default();
+
+        for entry in spec.unwrap_or("").split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (name, version) = entry
+                .split_once('=')
+                .and_then(|(name, version)| Some((name.trim(), version.trim().trim_start_matches('v').parse().ok()?)))
+                .ok_or_else(|| PromptError::InvalidEntry(entry.to_string()))?;
+            let prompt = get(name, version).ok_or_else(|| PromptError::UnknownVersion {
+                name: name.to_string(),
+                version,
+            })?;
+            registry.active.insert(prompt.name, prompt);
+        }
+
+        Ok(registry)
+    }
+
+    /// The active version of a prompt.
+    ///
+    /// # Panics
+    /// If `name` is not a built-in prompt (a programming error).
+    pub fn active(&self, name: &str) -> Prompt {
+        *self
+            .active
+            .get(name)
+            .unwrap_or_else(|| panic!("No built-in prompt named '{}'", name))
+    }
+}
+
+/// Look up a released prompt version, active or not.
+pub fn get(name: &str, version: i32) -> Option<Prompt> {
+    BUILTIN_PROMPTS
+        .iter()
+        .find(|p| p.name == name && p.version == version)
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newest_version_active_by_default() {
+        let registry = PromptRegistry::default();
+        let newest = BUILTIN_PROMPTS
+            .iter()
+            .filter(|p| p.name == LABEL_FIELDS)
+            .map(|p| p.version)
+            .max()
+            .unwrap();
+        assert_eq!(registry.active(LABEL_FIELDS).version, newest);
+    }
+
+    #[test]
+    fn test_versions_are_unique() {
+        for (i, a) in BUILTIN_PROMPTS.iter().enumerate() {
+            assert!(
+                BUILTIN_PROMPTS[i + 1..].iter().all(|b| (a.name, a.version) != (b.name, b.version)),
+                "{} is defined twice",
+                a
+            );
+        }
+    }
+
+    #[test]
+    fn test_pin_version() {
+        let registry = PromptRegistry::from_spec(Some(" label_fields = v1 ")).unwrap();
+        assert_eq!(registry.active(LABEL_FIELDS).to_string(), "label_fields@v1");
+        assert!(registry.active(LABEL_FIELDS).text.contains("brand_name"));
+    }
+
+    #[test]
+    fn test_invalid_spec() {
+        assert!(matches!(
+            PromptRegistry::from_spec(Some("label_fields=99")),
+            Err(PromptError::UnknownVersion { version: 99, .. })
+        ));
+        assert!(matches!(
+            PromptRegistry::from_spec(Some("label_fields")),
+            Err(PromptError::InvalidEntry(_))
+        ));
+    }
+}
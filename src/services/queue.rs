@@ -2,6 +2,8 @@ use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::services::profile::ValidationProfile;
+
 const QUEUE_KEY: &str = "label_verify:jobs";
 const PROCESSING_KEY: &str = "label_verify:processing";
 
@@ -13,6 +15,12 @@ pub struct QueuedJob {
     pub expected_brand: Option<String>,
     pub expected_class: Option<String>,
     pub expected_abv: Option<f64>,
+    /// COLA the label should be verified against, if the submitter named one.
+    #[serde(default)]
+    pub ttb_id: Option<String>,
+    /// Profile resolved at submission; `None` uses the worker's default profile.
+    #[serde(default)]
+    pub profile: Option<ValidationProfile>,
 }
 
 /// Redis-backed async job queue with retry support.
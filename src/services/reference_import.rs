@@ -0,0 +1,746 @@
+//! Import and export of `known_beverages` reference data (`label-verify-admin`).
+//!
+//! Rows are read from CSV or JSON (the export format, also what
+//! `seed_total_wine_data.py --csv-only` wrote) or from serialized
+//! `TtbColaRecord`s (`cola-json`). An import is planned first: rows are
+//! validated, deduplicated on `idx_known_beverages_unique` (COLAs on their TTB
+//! ID) and diffed against the database, so a dry run can show exactly what
+//! would change. Applying a plan upserts every row in one transaction.
+//!
+//! Beverages cached from a COLA are keyed on it, not on brand, product and
+//! ABV, so they only travel as `cola-json`: CSV/JSON exports leave them out
+//! and CSV/JSON imports refuse `source = 'ttb_cola'` rows.
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use serde::Serialize;
+use sqlx::PgPool;
+use strum::{Display, EnumString};
+
+use crate::db::beverage_queries;
+use crate::models::beverage::{AbvSource, KnownBeverage, NewKnownBeverage};
+use crate::services::ttb_cola::TtbColaRecord;
+
+/// Beverage categories accepted on import (as stored in `beverage_category`).
+const CATEGORIES: &[&str] = &["wine", "distilled_spirits", "malt_beverage"];
+
+/// Error type for reference data import/export.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError {
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Input file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ImportFormat {
+    /// `known_beverages` columns, one row per line, with a header.
+    Csv,
+    /// Array of `known_beverages` rows.
+    Json,
+    /// Array of `TtbColaRecord`s, cached as `source = 'ttb_cola'` rows.
+    ColaJson,
+}
+
+impl ImportFormat {
+    /// Guess the format from a file extension (`.csv` or `.json`).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+}
+
+/// Output file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    /// The COLAs behind the cached `source = 'ttb_cola'` rows.
+    ColaJson,
+}
+
+/// A row that could not be imported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidRow {
+    /// 1-based record number in the input (CSV: excluding the header).
+    pub record: usize,
+    pub reason: String,
+}
+
+/// Rows read from an import file.
+#[derive(Debug, Default)]
+pub struct ParsedRows {
+    pub rows: Vec<NewKnownBeverage>,
+    /// COLAs read from `cola-json`, each with a reference ABV.
+    pub colas: Vec<TtbColaRecord>,
+    pub invalid: Vec<InvalidRow>,
+}
+
+/// A field an import would change on an existing row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// What applying an import would do to one row.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlannedAction {
+    Insert,
+    Update(Vec<FieldChange>),
+    /// The row exists and the import changes nothing but `last_verified_at`.
+    Unchanged,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlannedRow {
+    pub beverage: NewKnownBeverage,
+    /// The COLA the row is cached from; applied through the COLA cache.
+    pub ttb_cola: Option<TtbColaRecord>,
+    pub action: PlannedAction,
+}
+
+/// A validated, deduplicated import diffed against the database.
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    pub rows: Vec<PlannedRow>,
+    /// Input rows dropped because a later row had the same key (or TTB ID).
+    pub duplicates: usize,
+    pub invalid: Vec<InvalidRow>,
+}
+
+/// Row counts for an import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub inserted: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+}
+
+impl ImportPlan {
+    pub fn summary(&self) -> ImportSummary {
+        let mut summary = ImportSummary {
+            duplicates: self.duplicates,
+            invalid: self.invalid.len(),
+            ..ImportSummary::default()
+        };
+        for row in &self.rows {
+            match row.action {
+                PlannedAction::Insert => summary.inserted += 1,
+                PlannedAction::Update(_) => summary.updated += 1,
+                PlannedAction::Unchanged => summary.unchanged += 1,
+            }
+        }
+        summary
+    }
+}
+
+/// Read and validate rows in `format`.
+///
+/// Malformed or invalid records are collected in `invalid`; only an unreadable
+/// file (bad CSV header, JSON that isn't an array) is an error.
+pub fn parse(format: ImportFormat, reader: impl Read) -> Result<ParsedRows, ImportError> {
+    let mut parsed = ParsedRows::default();
+    let mut push = |record: usize, row: Result<NewKnownBeverage, String>| {
+        match row.and_then(reject_cola_cached).and_then(normalize) {
+            Ok(row) => parsed.rows.push(row),
+            Err(reason) => parsed.invalid.push(InvalidRow { record, reason }),
+        }
+    };
+
+    match format {
+        ImportFormat::Csv => {
+            let mut csv_reader = csv::Reader::from_reader(reader);
+            for (i, row) in csv_reader.deserialize::<NewKnownBeverage>().enumerate() {
+                push(i + 1, row.map_err(|e| e.to_string()));
+            }
+        }
+        ImportFormat::Json => {
+            let values: Vec<serde_json::Value> = serde_json::from_reader(reader)?;
+            for (i, value) in values.into_iter().enumerate() {
+                push(i + 1, serde_json::from_value(value).map_err(|e| e.to_string()));
+            }
+        }
+        ImportFormat::ColaJson => {
+            let values: Vec<serde_json::Value> = serde_json::from_reader(reader)?;
+            for (i, value) in values.into_iter().enumerate() {
+                let checked = serde_json::from_value::<TtbColaRecord>(value)
+                    .map_err(|e| e.to_string())
+                    .and_then(|record| {
+                        let row = record.to_known_beverage().ok_or_else(|| {
+                            format!("{} ({}): no approved or inferred ABV", record.brand_name, record.ttb_id)
+                        })?;
+                        normalize(row)?;
+                        Ok(record)
+                    });
+                match checked {
+                    Ok(record) => parsed.colas.push(record),
+                    Err(reason) => parsed.invalid.push(InvalidRow { record: i + 1, reason }),
+                }
+            }
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Refuse CSV/JSON rows cached from a COLA: without the COLA they can't be
+/// keyed on it, and would be inserted as unlinked copies.
+fn reject_cola_cached(row: NewKnownBeverage) -> Result<NewKnownBeverage, String> {
+    if row.source.trim() == "ttb_cola" {
+        return Err(format!(
+            "{}: ttb_cola rows are cached from COLAs; import them as cola-json",
+            row.brand_name.trim()
+        ));
+    }
+    Ok(row)
+}
+
+/// Trim fields, turn blank optional fields into `None`, and check the row
+/// fits the `known_beverages` constraints.
+fn normalize(mut row: NewKnownBeverage) -> Result<NewKnownBeverage, String> {
+    fn blank_to_none(field: &mut Option<String>) {
+        *field = field
+            .take()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+    }
+
+    row.brand_name = row.brand_name.trim().to_string();
+    row.class_type = row.class_type.trim().to_string();
+    row.beverage_category = row.beverage_category.trim().to_lowercase();
+    row.source = row.source.trim().to_string();
+    blank_to_none(&mut row.product_name);
+    blank_to_none(&mut row.country_of_origin);
+    blank_to_none(&mut row.producer);
+    blank_to_none(&mut row.source_url);
+    blank_to_none(&mut row.notes);
+
+    if row.brand_name.is_empty() {
+        return Err("brand_name is empty".to_string());
+    }
+    if row.class_type.is_empty() {
+        return Err(format!("{}: class_type is empty", row.brand_name));
+    }
+    if !CATEGORIES.contains(&row.beverage_category.as_str()) {
+        return Err(format!(
+            "{}: unknown beverage_category '{}' (expected one of {})",
+            row.brand_name,
+            row.beverage_category,
+            CATEGORIES.join(", ")
+        ));
+    }
+    if !(0.0..100.0).contains(&row.abv) {
+        return Err(format!("{}: abv {} out of range", row.brand_name, row.abv));
+    }
+    if row.source.is_empty() {
+        row.source = "manual".to_string();
+    }
+
+    Ok(row)
+}
+
+/// Drop rows that share a `dedupe_key`; the last occurrence wins but keeps
+/// the position of the first. Returns the rows and the number dropped.
+pub fn dedupe(rows: Vec<NewKnownBeverage>) -> (Vec<NewKnownBeverage>, usize) {
+    let mut unique: Vec<NewKnownBeverage> = Vec::with_capacity(rows.len());
+    let mut positions = HashMap::new();
+    let mut duplicates = 0;
+
+    for row in rows {
+        match positions.get(&row.dedupe_key()) {
+            Some(&i) => {
+                unique[i] = row;
+                duplicates += 1;
+            }
+            None => {
+                positions.insert(row.dedupe_key(), unique.len());
+                unique.push(row);
+            }
+        }
+    }
+
+    (unique, duplicates)
+}
+
+/// Fields `upsert_known_beverage` would change on `existing`.
+///
+/// Mirrors the upsert: blank optional fields keep the stored value and
+/// `is_verified` is never cleared. Brand and product name are the key and
+/// are not updated.
+pub fn diff(existing: &KnownBeverage, incoming: &NewKnownBeverage) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    let mut compare = |field: &'static str, old: Option<String>, new: Option<String>| {
+        if let Some(new) = new {
+            if old.as_ref() != Some(&new) {
+                changes.push(FieldChange {
+                    field,
+                    old: old.unwrap_or_default(),
+                    new,
+                });
+            }
+        }
+    };
+
+    compare("class_type", Some(existing.class_type.clone()), Some(incoming.class_type.clone()));
+    compare(
+        "beverage_category",
+        Some(existing.beverage_category.clone()),
+        Some(incoming.beverage_category.clone()),
+    );
+    compare(
+        "standard_size_ml",
+        existing.standard_size_ml.map(|ml| ml.to_string()),
+        incoming.standard_size_ml.map(|ml| ml.to_string()),
+    );
+    compare("country_of_origin", existing.country_of_origin.clone(), incoming.country_of_origin.clone());
+    compare("producer", existing.producer.clone(), incoming.producer.clone());
+    compare(
+        "is_verified",
+        Some(existing.is_verified.to_string()),
+        (incoming.is_verified && !existing.is_verified).then(|| "true".to_string()),
+    );
+    compare("source", Some(existing.source.clone()), Some(incoming.source.clone()));
+    compare("source_url", existing.source_url.clone(), incoming.source_url.clone());
+    compare("notes", existing.notes.clone(), incoming.notes.clone());
+    compare(
+        "abv_source",
+        Some(existing.abv_source.to_string()),
+        Some(incoming.abv_source.to_string()),
+    );
+
+    changes
+}
+
+/// Fields `cache_ttb_cola_record` would change on the row cached from a COLA.
+///
+/// Like `diff`, plus the brand, product name and ABV, which are not the key
+/// here. A measured ABV is kept when the COLA comes without its detail page.
+pub fn diff_cached(existing: &KnownBeverage, incoming: &NewKnownBeverage) -> Vec<FieldChange> {
+    let mut incoming = incoming.clone();
+    if existing.abv_source == AbvSource::Measured && incoming.abv_source == AbvSource::Inferred {
+        incoming.abv = existing.abv;
+        incoming.abv_source = AbvSource::Measured;
+    }
+
+    let mut changes = Vec::new();
+    if existing.brand_name != incoming.brand_name {
+        changes.push(FieldChange {
+            field: "brand_name",
+            old: existing.brand_name.clone(),
+            new: incoming.brand_name.clone(),
+        });
+    }
+    if existing.product_name != incoming.product_name {
+        changes.push(FieldChange {
+            field: "product_name",
+            old: existing.product_name.clone().unwrap_or_default(),
+            new: incoming.product_name.clone().unwrap_or_default(),
+        });
+    }
+    // Stored as NUMERIC(4,2)
+    if (existing.abv - incoming.abv).abs() >= 0.005 {
+        changes.push(FieldChange {
+            field: "abv",
+            old: existing.abv.to_string(),
+            new: incoming.abv.to_string(),
+        });
+    }
+    changes.extend(diff(existing, &incoming));
+    changes
+}
+
+/// Deduplicate parsed rows and diff each against the row it would replace.
+pub async fn plan_import(pool: &PgPool, parsed: ParsedRows) -> Result<ImportPlan, ImportError> {
+    let (rows, duplicates) = dedupe(parsed.rows);
+    let (colas, cola_duplicates) = dedupe_colas(parsed.colas);
+    let mut plan = ImportPlan {
+        rows: Vec::with_capacity(rows.len() + colas.len()),
+        duplicates: duplicates + cola_duplicates,
+        invalid: parsed.invalid,
+    };
+
+    for beverage in rows {
+        let existing = beverage_queries::find_known_beverage_by_key(
+            pool,
+            &beverage.brand_name,
+            beverage.product_name.as_deref(),
+            beverage.abv,
+        )
+        .await?;
+
+        let action = match existing {
+            None => PlannedAction::Insert,
+            Some(existing) => {
+                let changes = diff(&existing, &beverage);
+                if changes.is_empty() {
+                    PlannedAction::Unchanged
+                } else {
+                    PlannedAction::Update(changes)
+                }
+            }
+        };
+        plan.rows.push(PlannedRow {
+            beverage,
+            ttb_cola: None,
+            action,
+        });
+    }
+
+    for record in colas {
+        // `parse` only keeps COLAs with a reference ABV
+        let Some(beverage) = record.to_known_beverage() else {
+            continue;
+        };
+        let action = match beverage_queries::find_ttb_cola_beverage(pool, &record.ttb_id).await? {
+            None => PlannedAction::Insert,
+            Some(existing) => {
+                let changes = diff_cached(&existing, &beverage);
+                if changes.is_empty() {
+                    PlannedAction::Unchanged
+                } else {
+                    PlannedAction::Update(changes)
+                }
+            }
+        };
+        plan.rows.push(PlannedRow {
+            beverage,
+            ttb_cola: Some(record),
+            action,
+        });
+    }
+
+    Ok(plan)
+}
+
+/// `dedupe` for COLAs, keyed on the TTB ID.
+fn dedupe_colas(records: Vec<TtbColaRecord>) -> (Vec<TtbColaRecord>, usize) {
+    let mut unique: Vec<TtbColaRecord> = Vec::with_capacity(records.len());
+    let mut positions = HashMap::new();
+    let mut duplicates = 0;
+
+    for record in records {
+        match positions.get(&record.ttb_id) {
+            Some(&i) => {
+                unique[i] = record;
+                duplicates += 1;
+            }
+            None => {
+                positions.insert(record.ttb_id.clone(), unique.len());
+                unique.push(record);
+            }
+        }
+    }
+
+    (unique, duplicates)
+}
+
+/// Upsert every planned row in one transaction.
+///
+/// Unchanged rows are upserted too, which marks them verified now. COLA rows
+/// are cached the way a TTB COLA lookup caches them.
+pub async fn apply_import(pool: &PgPool, plan: &ImportPlan) -> Result<ImportSummary, ImportError> {
+    let mut tx = pool.begin().await?;
+    for row in &plan.rows {
+        match &row.ttb_cola {
+            Some(record) => {
+                beverage_queries::cache_ttb_cola_record(&mut tx, record).await?;
+            }
+            None => {
+                beverage_queries::upsert_known_beverage(&mut *tx, &row.beverage).await?;
+            }
+        }
+    }
+    tx.commit().await?;
+
+    Ok(plan.summary())
+}
+
+/// Write non-retired known beverages (optionally from one source) in `format`.
+///
+/// CSV and JSON leave out rows cached from a COLA; `cola-json` writes those
+/// COLAs instead (`source` does not apply). The output can be imported again
+/// with the matching `ImportFormat`. Returns the number of rows written.
+pub async fn export(
+    pool: &PgPool,
+    format: ExportFormat,
+    source: Option<&str>,
+    writer: impl Write,
+) -> Result<usize, ImportError> {
+    if format == ExportFormat::ColaJson {
+        let records = beverage_queries::list_cached_ttb_cola_records(pool).await?;
+        write_rows(format, &records, writer)?;
+        return Ok(records.len());
+    }
+
+    let rows: Vec<NewKnownBeverage> = beverage_queries::list_known_beverages(pool, source)
+        .await?
+        .iter()
+        .map(NewKnownBeverage::from)
+        .collect();
+
+    write_rows(format, &rows, writer)?;
+    Ok(rows.len())
+}
+
+fn write_rows<T: Serialize>(format: ExportFormat, rows: &[T], mut writer: impl Write) -> Result<(), ImportError> {
+    match format {
+        ExportFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            for row in rows {
+                csv_writer.serialize(row)?;
+            }
+            csv_writer.flush()?;
+        }
+        ExportFormat::Json | ExportFormat::ColaJson => {
+            serde_json::to_writer_pretty(&mut writer, rows)?;
+            writeln!(writer)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::beverage::AbvSource;
+
+    fn beverage(brand: &str, product: Option<&str>, abv: f64) -> NewKnownBeverage {
+        NewKnownBeverage {
+            brand_name: brand.to_string(),
+            product_name: product.map(str::to_string),
+            class_type: "Cabernet Sauvignon".to_string(),
+            beverage_category: "wine".to_string(),
+            abv,
+            standard_size_ml: Some(750),
+            country_of_origin: None,
+            producer: None,
+            is_verified: false,
+            source: "manual".to_string(),
+            source_url: None,
+            notes: None,
+            abv_source: AbvSource::Measured,
+        }
+    }
+
+    #[test]
+    fn test_parse_csv_with_legacy_columns() {
+        // Columns written by seed_total_wine_data.py (no is_verified/notes)
+        let csv = "\
+brand_name,product_name,class_type,beverage_category,abv,standard_size_ml,country_of_origin,producer,source,source_url
+Caymus,Special Selection,Cabernet Sauvignon,wine,14.6,750,USA,,total_wine,https://example.com/caymus
+Buffalo Trace,,Bourbon Whiskey,distilled_spirits,45,,,,,
+,,Lager,malt_beverage,5,,,,,
+Mystery,,Cider,cider,6.9,,,,,
+";
+        let parsed = parse(ImportFormat::Csv, csv.as_bytes()).unwrap();
+
+        assert_eq!(parsed.rows.len(), 2);
+        let caymus = &parsed.rows[0];
+        assert_eq!(caymus.product_name.as_deref(), Some("Special Selection"));
+        assert_eq!(caymus.standard_size_ml, Some(750));
+        assert_eq!(caymus.producer, None);
+        assert_eq!(caymus.source, "total_wine");
+        assert!(!caymus.is_verified);
+        assert_eq!(parsed.rows[1].product_name, None);
+        assert_eq!(parsed.rows[1].source, "manual");
+
+        assert_eq!(parsed.invalid.len(), 2);
+        assert_eq!(parsed.invalid[0].record, 3);
+        assert!(parsed.invalid[1].reason.contains("unknown beverage_category"));
+    }
+
+    #[test]
+    fn test_parse_json_reports_bad_records() {
+        let json = r#"[
+            {"brand_name": "Caymus", "class_type": "Cabernet Sauvignon", "beverage_category": "wine", "abv": 14.6},
+            {"brand_name": "Caymus", "class_type": "Cabernet Sauvignon", "beverage_category": "wine"},
+            {"brand_name": "Everclear", "class_type": "Grain Spirits", "beverage_category": "distilled_spirits", "abv": 190},
+            {"brand_name": "STONE CREEK", "class_type": "TABLE RED WINE", "beverage_category": "wine", "abv": 12.5, "source": "ttb_cola"}
+        ]"#;
+        let parsed = parse(ImportFormat::Json, json.as_bytes()).unwrap();
+
+        assert_eq!(parsed.rows.len(), 1);
+        assert_eq!(parsed.rows[0].source, "manual");
+        assert_eq!(parsed.invalid.len(), 3);
+        assert!(parsed.invalid[0].reason.contains("abv"));
+        assert!(parsed.invalid[1].reason.contains("out of range"));
+        assert!(parsed.invalid[2].reason.contains("cola-json"));
+
+        assert!(parse(ImportFormat::Json, "{}".as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_parse_cola_json() {
+        let json = r#"[{
+            "ttb_id": "23001001000123",
+            "permit_no": "CA-I-1234",
+            "serial_number": "230001",
+            "completed_date": "2023-01-15",
+            "fanciful_name": null,
+            "brand_name": "STONE CREEK",
+            "origin_code": "06",
+            "origin_desc": "CALIFORNIA",
+            "class_type_code": "80",
+            "class_type_desc": "TABLE RED WINE",
+            "source_url": "https://ttbonline.gov/colasonline/viewColaDetails.do?action=publicDisplaySearchBasic&ttbid=23001001000123",
+            "inferred_abv": 12.5,
+            "beverage_category": "wine"
+        }, {
+            "ttb_id": "23001001000124",
+            "permit_no": "CA-I-1234",
+            "serial_number": "230002",
+            "completed_date": "2023-01-15",
+            "fanciful_name": null,
+            "brand_name": "STONE CREEK",
+            "origin_code": "06",
+            "origin_desc": "CALIFORNIA",
+            "class_type_code": "89",
+            "class_type_desc": "OTHER WINE",
+            "source_url": "https://ttbonline.gov/colasonline/viewColaDetails.do?action=publicDisplaySearchBasic&ttbid=23001001000124",
+            "inferred_abv": null,
+            "beverage_category": "wine"
+        }]"#;
+        let parsed = parse(ImportFormat::ColaJson, json.as_bytes()).unwrap();
+
+        // Without a reference ABV there is nothing to cache
+        assert!(parsed.rows.is_empty());
+        assert_eq!(parsed.colas.len(), 1);
+        assert_eq!(parsed.invalid.len(), 1);
+        assert_eq!(parsed.invalid[0].record, 2);
+        assert!(parsed.invalid[0].reason.contains("23001001000124"));
+        let row = parsed.colas[0].to_known_beverage().unwrap();
+        assert_eq!(row.source, "ttb_cola");
+        assert_eq!(row.abv, 12.5);
+        assert_eq!(
+            row.notes.as_deref(),
+            Some("TTB COLA ID: 23001001000123, Permit: CA-I-1234, Origin: CALIFORNIA (06)")
+        );
+    }
+
+    #[test]
+    fn test_dedupe_last_row_wins() {
+        let mut updated = beverage("caymus ", Some("SPECIAL SELECTION"), 14.6);
+        updated.producer = Some("Caymus Vineyards".to_string());
+        let rows = vec![
+            beverage("Caymus", Some("Special Selection"), 14.6),
+            beverage("Caymus", None, 14.6),
+            updated,
+            beverage("Caymus", Some("Special Selection"), 14.5),
+        ];
+
+        let (unique, duplicates) = dedupe(rows);
+        assert_eq!(duplicates, 1);
+        assert_eq!(unique.len(), 3);
+        assert_eq!(unique[0].producer.as_deref(), Some("Caymus Vineyards"));
+        assert_eq!(unique[1].product_name, None);
+    }
+
+    #[test]
+    fn test_diff_mirrors_upsert() {
+        let stored = beverage("Caymus", None, 14.6);
+        let existing = KnownBeverage {
+            brand_name: stored.brand_name.clone(),
+            class_type: stored.class_type.clone(),
+            beverage_category: stored.beverage_category.clone(),
+            abv: stored.abv,
+            standard_size_ml: Some(750),
+            country_of_origin: Some("USA".to_string()),
+            is_verified: true,
+            source: "manual".to_string(),
+            ..KnownBeverage::test_fixture()
+        };
+
+        // Blank optional fields and an unverified import change nothing
+        assert!(diff(&existing, &stored).is_empty());
+
+        let mut incoming = stored.clone();
+        incoming.class_type = "Red Wine".to_string();
+        incoming.producer = Some("Caymus Vineyards".to_string());
+        let changes = diff(&existing, &incoming);
+        assert_eq!(
+            changes,
+            vec![
+                FieldChange {
+                    field: "class_type",
+                    old: "Cabernet Sauvignon".to_string(),
+                    new: "Red Wine".to_string(),
+                },
+                FieldChange {
+                    field: "producer",
+                    old: String::new(),
+                    new: "Caymus Vineyards".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_cached_keeps_measured_abv() {
+        let existing = KnownBeverage {
+            abv: 13.9,
+            ..KnownBeverage::test_fixture()
+        };
+        let mut incoming = NewKnownBeverage {
+            class_type: "TABLE RED WINE".to_string(),
+            standard_size_ml: None,
+            source: "ttb_cola".to_string(),
+            abv_source: AbvSource::Inferred,
+            ..beverage("STONE CREEK", None, 12.5)
+        };
+
+        // The COLA seen again without its detail page changes nothing
+        assert!(diff_cached(&existing, &incoming).is_empty());
+
+        incoming.abv = 14.1;
+        incoming.abv_source = AbvSource::Measured;
+        incoming.product_name = Some("RESERVE".to_string());
+        let fields: Vec<_> = diff_cached(&existing, &incoming).iter().map(|c| c.field).collect();
+        assert_eq!(fields, vec!["product_name", "abv"]);
+    }
+
+    #[test]
+    fn test_export_round_trips() {
+        let mut row = beverage("Caymus", Some("Special Selection"), 14.6);
+        row.notes = Some("Napa, \"estate\"".to_string());
+        let rows = vec![row, beverage("Buffalo Trace", None, 45.0)];
+
+        for (export_format, import_format) in [
+            (ExportFormat::Csv, ImportFormat::Csv),
+            (ExportFormat::Json, ImportFormat::Json),
+        ] {
+            let mut out = Vec::new();
+            write_rows(export_format, &rows, &mut out).unwrap();
+            let parsed = parse(import_format, out.as_slice()).unwrap();
+            assert!(parsed.invalid.is_empty());
+            assert_eq!(parsed.rows, rows);
+        }
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ImportFormat::from_path(Path::new("data/wine.CSV")), Some(ImportFormat::Csv));
+        assert_eq!(ImportFormat::from_path(Path::new("export.json")), Some(ImportFormat::Json));
+        assert_eq!(ImportFormat::from_path(Path::new("export.txt")), None);
+        assert_eq!("cola-json".parse::<ImportFormat>().unwrap(), ImportFormat::ColaJson);
+    }
+}
@@ -0,0 +1,316 @@
+//! Catalogue of verification rules.
+//!
+//! Every `FieldVerification` produced by the validation service is tagged with a
+//! stable rule code, a severity, and (where one applies) the CFR section that
+//! requires it. Codes are part of the API contract: reviewers and downstream
+//! tooling key on them, so they must not be renamed once shipped.
+
+use crate::models::label::{FieldVerification, Severity};
+
+/// A single verification rule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule {
+    /// Stable identifier (e.g. "abv.expected").
+    pub code: &'static str,
+    /// How a failed check affects the overall result.
+    pub severity: Severity,
+    /// CFR section requiring the check, if it is regulatory.
+    pub cfr_citation: Option<&'static str>,
+}
+
+impl Rule {
+    /// A field result pre-populated with this rule's code, severity and citation.
+    ///
+    /// Intended for struct-update syntax:
+    /// `FieldVerification { field_name, expected, extracted, matches, similarity_score, ..rule.base() }`
+    pub fn base(&self) -> FieldVerification {
+        FieldVerification {
+            field_name: String::new(),
+            rule_code: self.code.to_string(),
+            severity: self.severity,
+            cfr_citation: self.cfr_citation.map(str::to_string),
+            expected: None,
+            extracted: String::new(),
+            matches: false,
+            similarity_score: 0.0,
+            match_components: None,
+        }
+    }
+}
+
+// ── Label vs. submitted application values ──────────────────────────────
+
+pub const BRAND_EXPECTED: Rule = Rule {
+    code: "brand.expected",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.33, 5.64, 7.64"),
+};
+
+pub const CLASS_EXPECTED: Rule = Rule {
+    code: "class.expected",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.34, 5.35, 7.24"),
+};
+
+pub const ABV_EXPECTED: Rule = Rule {
+    code: "abv.expected",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.36, 5.65, 7.65"),
+};
+
+// ── Standards of identity and mandatory statements ──────────────────────
+
+pub const CLASS_TTB_STANDARD: Rule = Rule {
+    code: "class.ttb_standard",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.21, 5.22, 7.24"),
+};
+
+pub const CLASS_SPELLING: Rule = Rule {
+    code: "class.spelling",
+    severity: Severity::Warning,
+    cfr_citation: Some("27 CFR 4.21, 5.22, 7.24"),
+};
+
+pub const CLASS_COMPOSITION_STATEMENT: Rule = Rule {
+    code: "class.composition_statement",
+    severity: Severity::Warning,
+    cfr_citation: Some("27 CFR 5.35"),
+};
+
+pub const NET_CONTENTS_FORMAT: Rule = Rule {
+    code: "net_contents.format",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.37, 5.70, 7.70"),
+};
+
+pub const NAME_ADDRESS_STATEMENT: Rule = Rule {
+    code: "name_address.statement",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.35, 5.66, 7.66"),
+};
+
+pub const ORIGIN_IMPORTER_COUNTRY: Rule = Rule {
+    code: "origin.importer_country",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 5.69, 7.69; 19 CFR 134"),
+};
+
+pub const ORIGIN_RECOGNIZED: Rule = Rule {
+    code: "origin.recognized",
+    severity: Severity::Warning,
+    cfr_citation: None,
+};
+
+pub const ORIGIN_GEOGRAPHIC_DESIGNATION: Rule = Rule {
+    code: "origin.geographic_designation",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 5.22"),
+};
+
+pub const DISCLOSURE_WORDING: Rule = Rule {
+    code: "disclosure.wording",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.32"),
+};
+
+pub const DISCLOSURE_SULFITES: Rule = Rule {
+    code: "disclosure.sulfites",
+    severity: Severity::Warning,
+    cfr_citation: Some("27 CFR 4.32(e)"),
+};
+
+pub const FIELD_PRESENT: Rule = Rule {
+    code: "field.present",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 4.32, 5.63, 7.63"),
+};
+
+pub const SAME_FIELD_OF_VISION: Rule = Rule {
+    code: "field.same_field_of_vision",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 5.63"),
+};
+
+// ── Reference database cross-checks ─────────────────────────────────────
+
+pub const ABV_DATABASE: Rule = Rule {
+    code: "abv.database",
+    severity: Severity::Error,
+    cfr_citation: None,
+};
+
+pub const BRAND_DATABASE_FUZZY: Rule = Rule {
+    code: "brand.database_fuzzy",
+    severity: Severity::Info,
+    cfr_citation: None,
+};
+
+pub const ABV_DATABASE_FUZZY: Rule = Rule {
+    code: "abv.database_fuzzy",
+    severity: Severity::Error,
+    cfr_citation: None,
+};
+
+pub const BOTTLER_DATABASE: Rule = Rule {
+    code: "name_address.database",
+    severity: Severity::Warning,
+    cfr_citation: None,
+};
+
+pub const TTB_COLA_REFERENCE: Rule = Rule {
+    code: "ttb_cola.reference",
+    severity: Severity::Info,
+    cfr_citation: None,
+};
+
+pub const TTB_COLA_SUBMITTED: Rule = Rule {
+    code: "ttb_cola.submitted",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 13.21"),
+};
+
+/// The matched COLA was surrendered or revoked.
+pub const TTB_COLA_STATUS: Rule = Rule {
+    code: "ttb_cola.status",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR part 13"),
+};
+
+/// The matched COLA has expired.
+pub const TTB_COLA_STATUS_EXPIRED: Rule = Rule {
+    code: "ttb_cola.status_expired",
+    severity: Severity::Warning,
+    cfr_citation: None,
+};
+
+pub const BOTTLER_TTB_COLA: Rule = Rule {
+    code: "name_address.ttb_cola",
+    severity: Severity::Warning,
+    cfr_citation: None,
+};
+
+pub const ABV_TTB_COLA: Rule = Rule {
+    code: "abv.ttb_cola",
+    severity: Severity::Error,
+    cfr_citation: None,
+};
+
+pub const ORIGIN_TTB_COLA: Rule = Rule {
+    code: "origin.ttb_cola",
+    severity: Severity::Error,
+    cfr_citation: Some("27 CFR 5.69, 7.69"),
+};
+
+pub const ABV_CATEGORY_RANGE: Rule = Rule {
+    code: "abv.category_range",
+    severity: Severity::Error,
+    cfr_citation: None, // Taken from beverage_category_rules.cfr_reference
+};
+
+pub const ABV_CATEGORY_TYPICAL: Rule = Rule {
+    code: "abv.category_typical",
+    severity: Severity::Info,
+    cfr_citation: None,
+};
+
+pub const LOGICAL_CONSISTENCY: Rule = Rule {
+    code: "consistency.abv_category",
+    severity: Severity::Error,
+    cfr_citation: None,
+};
+
+// ── Extraction quality ──────────────────────────────────────────────────
+
+/// OCR ensemble members read a field differently; a reviewer should check the image.
+pub const OCR_DISAGREEMENT: Rule = Rule {
+    code: "ocr.disagreement",
+    severity: Severity::Warning,
+    cfr_citation: None,
+};
+
+/// Every rule in the catalogue.
+pub const ALL: &[Rule] = &[
+    BRAND_EXPECTED,
+    CLASS_EXPECTED,
+    ABV_EXPECTED,
+    CLASS_TTB_STANDARD,
+    CLASS_SPELLING,
+    CLASS_COMPOSITION_STATEMENT,
+    NET_CONTENTS_FORMAT,
+    NAME_ADDRESS_STATEMENT,
+    ORIGIN_IMPORTER_COUNTRY,
+    ORIGIN_RECOGNIZED,
+    ORIGIN_GEOGRAPHIC_DESIGNATION,
+    DISCLOSURE_WORDING,
+    DISCLOSURE_SULFITES,
+    FIELD_PRESENT,
+    SAME_FIELD_OF_VISION,
+    ABV_DATABASE,
+    BRAND_DATABASE_FUZZY,
+    ABV_DATABASE_FUZZY,
+    BOTTLER_DATABASE,
+    TTB_COLA_REFERENCE,
+    TTB_COLA_SUBMITTED,
+    TTB_COLA_STATUS,
+    TTB_COLA_STATUS_EXPIRED,
+    BOTTLER_TTB_COLA,
+    ABV_TTB_COLA,
+    ORIGIN_TTB_COLA,
+    ABV_CATEGORY_RANGE,
+    ABV_CATEGORY_TYPICAL,
+    LOGICAL_CONSISTENCY,
+    OCR_DISAGREEMENT,
+];
+
+/// Look up a rule by its code.
+pub fn find(code: &str) -> Option<&'static Rule> {
+    ALL.iter().find(|rule| rule.code == code)
+}
+
+/// Whether a set of field results passes: true unless an error-severity check failed.
+pub fn passes(field_results: &[FieldVerification]) -> bool {
+    !field_results
+        .iter()
+        .any(|f| !f.matches && f.severity == Severity::Error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_passes_ignores_warnings_and_info() {
+        let info = FieldVerification {
+            field_name: "abv_category_typical_range".to_string(),
+            ..ABV_CATEGORY_TYPICAL.base()
+        };
+        let warning = FieldVerification {
+            field_name: "class_type_spelling".to_string(),
+            ..CLASS_SPELLING.base()
+        };
+        assert!(passes(&[info.clone(), warning]));
+
+        let error = FieldVerification {
+            field_name: "abv".to_string(),
+            ..ABV_EXPECTED.base()
+        };
+        assert!(!passes(&[info, error]));
+    }
+
+    #[test]
+    fn test_catalogue_codes_unique() {
+        let codes: std::collections::HashSet<_> = ALL.iter().map(|rule| rule.code).collect();
+        assert_eq!(codes.len(), ALL.len());
+        assert_eq!(find("abv.expected"), Some(&ABV_EXPECTED));
+        assert_eq!(find("abv.unknown"), None);
+    }
+
+    #[test]
+    fn test_base_carries_rule_metadata() {
+        let f = NAME_ADDRESS_STATEMENT.base();
+        assert_eq!(f.rule_code, "name_address.statement");
+        assert_eq!(f.severity, Severity::Error);
+        assert_eq!(f.cfr_citation.as_deref(), Some("27 CFR 4.35, 5.66, 7.66"));
+    }
+}
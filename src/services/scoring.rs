@@ -0,0 +1,223 @@
+//! Weighted confidence scoring for verification results.
+//!
+//! Each field result contributes `weight × similarity_score` to the overall
+//! confidence, normalized by the total weight. Weights are looked up by rule
+//! code, falling back to a per-severity default, so informational checks can be
+//! added without diluting the score.
+//!
+//! Weights are configured with `CONFIDENCE_WEIGHTS`, a comma-separated list of
+//! `rule_code=weight` pairs, e.g. `brand.expected=3,abv.category_typical=0`.
+//! Codes must name a rule in `rules::ALL`, so a typo fails at startup.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use crate::models::label::{FieldVerification, ScoreContribution, Severity};
+use crate::services::rules;
+
+/// Global scoring model (set once at startup, defaults otherwise).
+static SCORING_MODEL: OnceLock<ScoringModel> = OnceLock::new();
+
+/// Install the scoring model used by `model()`. Later calls are ignored.
+pub fn init(model: ScoringModel) {
+    let _ = SCORING_MODEL.set(model);
+}
+
+/// Get the configured scoring model, or the default if none was installed.
+pub fn model() -> &'static ScoringModel {
+    SCORING_MODEL.get_or_init(ScoringModel::default)
+}
+
+/// Default per-rule weights. Rules not listed use the severity default.
+const DEFAULT_RULE_WEIGHTS: &[(&str, f64)] = &[
+    ("brand.expected", 3.0),
+    ("abv.expected", 3.0),
+    ("class.expected", 2.0),
+    ("class.ttb_standard", 2.0),
+    ("field.present", 3.0),
+    ("field.same_field_of_vision", 2.0),
+    ("name_address.statement", 2.0),
+    ("abv.database", 2.0),
+    ("abv.category_range", 2.0),
+];
+
+/// Weighting model mapping rule codes to weights.
+#[derive(Debug, Clone)]
+pub struct ScoringModel {
+    weights: HashMap<String, f64>,
+    error_weight: f64,
+    warning_weight: f64,
+    info_weight: f64,
+}
+
+impl Default for ScoringModel {
+    fn default() -> Self {
+        Self {
+            weights: DEFAULT_RULE_WEIGHTS
+                .iter()
+                .map(|(code, weight)| (code.to_string(), *weight))
+                .collect(),
+            error_weight: 1.0,
+            warning_weight: 0.5,
+            info_weight: 0.0,
+        }
+    }
+}
+
+impl ScoringModel {
+    /// Build a model from the defaults plus `rule_code=weight` overrides.
+    ///
+    /// The pseudo-codes `severity.error`, `severity.warning` and `severity.info`
+    /// override the fallback weight for rules without an explicit entry.
+    pub fn from_spec(spec: &str) -> Result<Self, ScoringError> {
+        let mut model = Self::default();
+
+        for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (code, weight) = pair
+                .split_once('=')
+                .ok_or_else(|| ScoringError::InvalidEntry(pair.to_string()))?;
+            let code = code.trim();
+            let weight: f64 = weight
+                .trim()
+                .parse()
+                .map_err(|_| ScoringError::InvalidEntry(pair.to_string()))?;
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(ScoringError::InvalidEntry(pair.to_string()));
+            }
+
+            match code {
+                "severity.error" => model.error_weight = weight,
+                "severity.warning" => model.warning_weight = weight,
+                "severity.info" => model.info_weight = weight,
+                _ if rules::find(code).is_some() => {
+                    model.weights.insert(code.to_string(), weight);
+                }
+                _ => return Err(ScoringError::UnknownRule(code.to_string())),
+            }
+        }
+
+        Ok(model)
+    }
+
+    /// Weight applied to a field result.
+    pub fn weight_for(&self, field: &FieldVerification) -> f64 {
+        self.weights
+            .get(&field.rule_code)
+            .copied()
+            .unwrap_or(match field.severity {
+                Severity::Error => self.error_weight,
+                Severity::Warning => self.warning_weight,
+                Severity::Info => self.info_weight,
+            })
+    }
+
+    /// Compute the weighted confidence score and its per-rule breakdown.
+    ///
+    /// Contributions sum to the returned score. Returns 0.0 when every result
+    /// has zero weight (or there are no results).
+    pub fn score(&self, field_results: &[FieldVerification]) -> (f64, Vec<ScoreContribution>) {
+        let weights: Vec<f64> = field_results.iter().map(|f| self.weight_for(f)).collect();
+        let total: f64 = weights.iter().sum();
+
+        if total <= 0.0 {
+            return (0.0, Vec::new());
+        }
+
+        let breakdown: Vec<ScoreContribution> = field_results
+            .iter()
+            .zip(&weights)
+            .filter(|(_, weight)| **weight > 0.0)
+            .map(|(field, weight)| {
+                let share = weight / total;
+                ScoreContribution {
+                    rule_code: field.rule_code.clone(),
+                    field_name: field.field_name.clone(),
+                    weight: *weight,
+                    share,
+                    similarity_score: field.similarity_score,
+                    contribution: share * field.similarity_score,
+                }
+            })
+            .collect();
+
+        let score = breakdown.iter().map(|c| c.contribution).sum();
+        (score, breakdown)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScoringError {
+    #[error("Invalid confidence weight entry '{0}' (expected rule_code=weight with weight >= 0)")]
+    InvalidEntry(String),
+
+    #[error("Unknown rule code '{0}' in confidence weights")]
+    UnknownRule(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(rule: &rules::Rule, similarity_score: f64) -> FieldVerification {
+        FieldVerification {
+            field_name: rule.code.to_string(),
+            similarity_score,
+            ..rule.base()
+        }
+    }
+
+    #[test]
+    fn test_info_rules_do_not_dilute_score() {
+        let model = ScoringModel::default();
+        let (without, _) = model.score(&[field(&rules::BRAND_EXPECTED, 1.0)]);
+        let (with, breakdown) = model.score(&[
+            field(&rules::BRAND_EXPECTED, 1.0),
+            field(&rules::ABV_CATEGORY_TYPICAL, 0.7),
+        ]);
+        assert_eq!(without, with);
+        assert_eq!(breakdown.len(), 1);
+    }
+
+    #[test]
+    fn test_contributions_sum_to_score() {
+        let model = ScoringModel::default();
+        let (score, breakdown) = model.score(&[
+            field(&rules::BRAND_EXPECTED, 0.9),
+            field(&rules::NET_CONTENTS_FORMAT, 0.0),
+            field(&rules::CLASS_SPELLING, 0.5),
+        ]);
+        let sum: f64 = breakdown.iter().map(|c| c.contribution).sum();
+        assert!((score - sum).abs() < 1e-12);
+        let shares: f64 = breakdown.iter().map(|c| c.share).sum();
+        assert!((shares - 1.0).abs() < 1e-12);
+        // brand (3.0) + net contents (1.0) + spelling warning (0.5)
+        assert!((score - (3.0 * 0.9 + 0.5 * 0.5) / 4.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_from_spec_overrides() {
+        let model = ScoringModel::from_spec("brand.expected=10, severity.info=1").unwrap();
+        assert_eq!(model.weight_for(&field(&rules::BRAND_EXPECTED, 1.0)), 10.0);
+        assert_eq!(model.weight_for(&field(&rules::ABV_CATEGORY_TYPICAL, 1.0)), 1.0);
+        assert!(ScoringModel::from_spec("brand.expected").is_err());
+        assert!(ScoringModel::from_spec("brand.expected=-1").is_err());
+        assert!(matches!(
+            ScoringModel::from_spec("brand.expectd=2"),
+            Err(ScoringError::UnknownRule(code)) if code == "brand.expectd"
+        ));
+    }
+
+    #[test]
+    fn test_default_weights_name_catalogued_rules() {
+        for (code, _) in DEFAULT_RULE_WEIGHTS {
+            assert!(rules::find(code).is_some(), "{} is not a rule", code);
+        }
+    }
+
+    #[test]
+    fn test_empty_results_score_zero() {
+        let (score, breakdown) = ScoringModel::default().score(&[]);
+        assert_eq!(score, 0.0);
+        assert!(breakdown.is_empty());
+    }
+}
@@ -36,7 +36,7 @@ impl R2Client {
             .await
             .map_err(StorageError::S3)?;
         let status = response.status_code();
-        if status < 200 || status >= 300 {
+        if !(200..300).contains(&status) {
             return Err(StorageError::Config(format!(
                 "R2 upload returned HTTP {}: {}",
                 status,
@@ -51,7 +51,7 @@ impl R2Client {
     pub async fn download(&self, key: &str) -> Result<Vec<u8>, StorageError> {
         let response = self.bucket.get_object(key).await.map_err(StorageError::S3)?;
         let status = response.status_code();
-        if status < 200 || status >= 300 {
+        if !(200..300).contains(&status) {
             return Err(StorageError::Config(format!(
                 "R2 download returned HTTP {}: {}",
                 status,
@@ -0,0 +1,317 @@
+//! Rate limiting, retry backoff and circuit breaking for outbound HTTP clients.
+//!
+//! Used by the TTB COLA client so that a batch of jobs can't flood TTB and
+//! so that jobs stop waiting on request timeouts while TTB is down.
+//!
+//! Settings are configured with `TTB_COLA_THROTTLE`, a comma-separated list of
+//! `key=value` pairs, e.g. `max_concurrent=2, min_interval_ms=250, open_secs=120`.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Upper bound for a single retry delay.
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Error type for throttle settings.
+#[derive(Debug, thiserror::Error)]
+pub enum ThrottleError {
+    #[error("Invalid throttle entry '{0}' (expected key=value with a non-negative integer)")]
+    InvalidEntry(String),
+
+    #[error("Unknown throttle setting '{0}'")]
+    UnknownKey(String),
+}
+
+/// Limits applied to an outbound client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottleSettings {
+    /// Maximum requests in flight at once.
+    pub max_concurrent: usize,
+    /// Minimum spacing between request starts.
+    pub min_interval: Duration,
+    /// Retries after a failed request (0 = no retries).
+    pub max_retries: u32,
+    /// First retry delay; doubles on each further retry.
+    pub backoff_base: Duration,
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial request is allowed.
+    pub open_duration: Duration,
+}
+
+impl Default for ThrottleSettings {
+    fn default() -> Self {
+        Self {
+            max_concurrent: 4,
+            min_interval: Duration::from_millis(200),
+            max_retries: 2,
+            backoff_base: Duration::from_millis(500),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ThrottleSettings {
+    /// Parse comma-separated `key=value` overrides on top of the defaults.
+    ///
+    /// Keys: `max_concurrent`, `min_interval_ms`, `max_retries`, `backoff_ms`,
+    /// `failure_threshold`, `open_secs`.
+    pub fn from_spec(spec: &str) -> Result<Self, ThrottleError> {
+        let mut settings = Self::default();
+
+        for pair in spec.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| ThrottleError::InvalidEntry(pair.to_string()))?;
+            let value: u64 = value
+                .trim()
+                .parse()
+                .map_err(|_| ThrottleError::InvalidEntry(pair.to_string()))?;
+
+            match key.trim() {
+                "max_concurrent" if value > 0 => settings.max_concurrent = value as usize,
+                "max_concurrent" => return Err(ThrottleError::InvalidEntry(pair.to_string())),
+                "min_interval_ms" => settings.min_interval = Duration::from_millis(value),
+                "max_retries" => settings.max_retries = value as u32,
+                "backoff_ms" => settings.backoff_base = Duration::from_millis(value),
+                "failure_threshold" => settings.failure_threshold = (value as u32).max(1),
+                "open_secs" => settings.open_duration = Duration::from_secs(value),
+                other => return Err(ThrottleError::UnknownKey(other.to_string())),
+            }
+        }
+
+        Ok(settings)
+    }
+}
+
+/// Shared limiter: at most `max_concurrent` requests, started `min_interval` apart.
+pub struct RateLimiter {
+    permits: Semaphore,
+    min_interval: Duration,
+    next_start: tokio::sync::Mutex<Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(max_concurrent: usize, min_interval: Duration) -> Self {
+        Self {
+            permits: Semaphore::new(max_concurrent.max(1)),
+            min_interval,
+            next_start: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait for a request slot. The request may run while the permit is held.
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        let permit = self.permits.acquire().await.expect("rate limiter semaphore is never closed");
+
+        let wait = {
+            let mut next_start = self.next_start.lock().await;
+            let now = Instant::now();
+            let start = (*next_start).max(now);
+            *next_start = start + self.min_interval;
+            start - now
+        };
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+
+        permit
+    }
+}
+
+/// Circuit breaker state, as exported in metrics (closed = 0, open = 1, half-open = 2).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally.
+    Closed,
+    /// Requests are rejected until the open period ends.
+    Open,
+    /// The open period ended; one trial request decides whether to close.
+    HalfOpen,
+}
+
+impl CircuitState {
+    pub fn as_gauge(self) -> f64 {
+        match self {
+            CircuitState::Closed => 0.0,
+            CircuitState::Open => 1.0,
+            CircuitState::HalfOpen => 2.0,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the current half-open trial request was let through.
+    trial_started: Option<Instant>,
+}
+
+/// Opens after `failure_threshold` consecutive failures and rejects requests
+/// for `open_duration`, then lets a single trial request through.
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_duration: Duration,
+    state: Mutex<BreakerState>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_duration: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_duration,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Whether a request may proceed; `Err` carries the time until the next trial.
+    ///
+    /// In the half-open state the first caller becomes the trial request and
+    /// later callers are rejected until it reports back (or, if it never does,
+    /// until another `open_duration` has passed).
+    pub fn check(&self) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let reopen_at = opened_at + self.open_duration;
+        if now < reopen_at {
+            return Err(reopen_at - now);
+        }
+
+        match state.trial_started {
+            Some(started) if now < started + self.open_duration => {
+                Err(started + self.open_duration - now)
+            }
+            _ => {
+                state.trial_started = Some(now);
+                Ok(())
+            }
+        }
+    }
+
+    /// Close the circuit and reset the failure count, ending any half-open trial.
+    pub fn record_success(&self) {
+        *self.state.lock().unwrap() = BreakerState::default();
+    }
+
+    /// Count a failure, opening (or re-opening) the circuit at the threshold.
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.consecutive_failures += 1;
+        if state.consecutive_failures >= self.failure_threshold || state.trial_started.is_some() {
+            state.opened_at = Some(Instant::now());
+            state.trial_started = None;
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        let state = self.state.lock().unwrap();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.open_duration => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// Delay before retry number `attempt` (1-based): `base * 2^(attempt - 1)`, capped.
+pub fn backoff_delay(base: Duration, attempt: u32) -> Duration {
+    base.saturating_mul(1 << attempt.saturating_sub(1).min(16))
+        .min(MAX_BACKOFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_from_spec() {
+        let settings = ThrottleSettings::from_spec("max_concurrent=2, min_interval_ms=250, open_secs=120").unwrap();
+        assert_eq!(settings.max_concurrent, 2);
+        assert_eq!(settings.min_interval, Duration::from_millis(250));
+        assert_eq!(settings.open_duration, Duration::from_secs(120));
+        assert_eq!(settings.max_retries, ThrottleSettings::default().max_retries);
+
+        assert_eq!(ThrottleSettings::from_spec("").unwrap(), ThrottleSettings::default());
+        assert!(matches!(
+            ThrottleSettings::from_spec("burst=3"),
+            Err(ThrottleError::UnknownKey(_))
+        ));
+        assert!(matches!(
+            ThrottleSettings::from_spec("max_concurrent=0"),
+            Err(ThrottleError::InvalidEntry(_))
+        ));
+        assert!(matches!(
+            ThrottleSettings::from_spec("max_retries=-1"),
+            Err(ThrottleError::InvalidEntry(_))
+        ));
+    }
+
+    #[test]
+    fn test_breaker_opens_after_threshold() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.check().is_ok());
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert!(breaker.check().is_err());
+    }
+
+    #[test]
+    fn test_breaker_success_resets_failures() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_breaker_half_open_allows_single_trial() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+        breaker.record_failure();
+        assert!(breaker.check().is_err());
+        std::thread::sleep(Duration::from_millis(25));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.check().is_ok());
+        assert!(breaker.check().is_err());
+
+        // A failed trial re-opens the circuit; a successful one closes it
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        std::thread::sleep(Duration::from_millis(25));
+        assert!(breaker.check().is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.check().is_ok());
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles_and_caps() {
+        let base = Duration::from_millis(500);
+        assert_eq!(backoff_delay(base, 1), Duration::from_millis(500));
+        assert_eq!(backoff_delay(base, 2), Duration::from_secs(1));
+        assert_eq!(backoff_delay(base, 3), Duration::from_secs(2));
+        assert_eq!(backoff_delay(base, 30), MAX_BACKOFF);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_request_starts() {
+        let limiter = RateLimiter::new(4, Duration::from_millis(30));
+        let started = Instant::now();
+        for _ in 0..3 {
+            drop(limiter.acquire().await);
+        }
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+}
@@ -8,12 +8,75 @@
 use chrono::NaiveDate;
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use tokio::sync::OnceCell;
+use tracing::{debug, warn};
+
+use crate::models::beverage::{AbvSource, ColaStatus, NewKnownBeverage};
+use crate::services::throttle::{self, CircuitBreaker, RateLimiter, ThrottleSettings};
 
 /// Global TTB COLA client (lazily initialized).
 static TTB_CLIENT: OnceLock<TtbColaClient> = OnceLock::new();
 
+/// Default COLA Online base URL.
+pub const DEFAULT_BASE_URL: &str = "https://ttbonline.gov/colasonline";
+
+/// Search parameters left out of fixture keys: the default completed-date
+/// window moves every day, so keying on it would break replay.
+const UNKEYED_PARAMS: &[&str] = &[
+    "searchCriteria.dateCompletedFrom",
+    "searchCriteria.dateCompletedTo",
+];
+
+/// Message on the page TTB returns for a TTB ID with no COLA.
+const COLA_NOT_FOUND_MESSAGE: &str = "The COLA you requested could not be found";
+
+/// URL of a COLA's public detail page under `base_url`.
+pub fn detail_url(base_url: &str, ttb_id: &str) -> String {
+    format!(
+        "{}/viewColaDetails.do?action=publicDisplaySearchBasic&ttbid={}",
+        base_url.trim_end_matches('/'),
+        ttb_id
+    )
+}
+
+/// Register descriptions for the TTB COLA client metrics.
+pub fn describe_metrics() {
+    metrics::describe_counter!(
+        "ttb_cola_requests_total",
+        "HTTP requests sent to TTB COLA, by outcome (success, failure)"
+    );
+    metrics::describe_histogram!("ttb_cola_request_seconds", "TTB COLA HTTP request latency");
+    metrics::describe_counter!("ttb_cola_retries_total", "TTB COLA requests retried after a failure");
+    metrics::describe_counter!(
+        "ttb_cola_coalesced_total",
+        "Brand searches served by an identical in-flight search"
+    );
+    metrics::describe_counter!(
+        "ttb_cola_short_circuited_total",
+        "TTB COLA requests rejected while the circuit breaker was open"
+    );
+    metrics::describe_counter!(
+        "ttb_cola_negative_cache_hits_total",
+        "Brand lookups answered by a cached TTB COLA miss"
+    );
+    metrics::describe_gauge!(
+        "ttb_cola_circuit_state",
+        "TTB COLA circuit breaker state (0 = closed, 1 = open, 2 = half-open)"
+    );
+}
+
+/// Install the global TTB COLA client used by `get_client()`. Later calls are ignored.
+pub fn init(client: TtbColaClient) {
+    let _ = TTB_CLIENT.set(client);
+}
+
 /// Get or initialize the global TTB COLA client.
+///
+/// Falls back to a live client against `DEFAULT_BASE_URL` if `init` was not called.
 pub fn get_client() -> Result<&'static TtbColaClient, TtbColaError> {
     if let Some(client) = TTB_CLIENT.get() {
         return Ok(client);
@@ -41,33 +104,230 @@ pub struct TtbColaRecord {
     pub source_url: String,
     pub inferred_abv: Option<f64>,
     pub beverage_category: String,
+    /// Data from the COLA detail page, if it was fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<TtbColaDetail>,
+}
+
+impl TtbColaRecord {
+    /// Best available ABV: the approved alcohol content if known, else the inferred value.
+    pub fn reference_abv(&self) -> Option<f64> {
+        self.detail
+            .as_ref()
+            .and_then(|d| d.alcohol_content)
+            .or(self.inferred_abv)
+    }
+
+    /// `Measured` if `reference_abv` is the approved alcohol content, else `Inferred`.
+    pub fn abv_source(&self) -> AbvSource {
+        match self.detail.as_ref().and_then(|d| d.alcohol_content) {
+            Some(_) => AbvSource::Measured,
+            None => AbvSource::Inferred,
+        }
+    }
+
+    /// Approval status from the detail page, if it was fetched and recognized.
+    pub fn approval_status(&self) -> Option<ColaStatus> {
+        self.detail.as_ref().and_then(TtbColaDetail::approval_status)
+    }
+
+    /// Notes stored on the cached `known_beverages` row (COLA ID, permit,
+    /// origin and, if known, approval status).
+    pub fn cache_notes(&self) -> String {
+        let mut notes = format!(
+            "TTB COLA ID: {}, Permit: {}, Origin: {} ({})",
+            self.ttb_id, self.permit_no, self.origin_desc, self.origin_code
+        );
+        if let Some(status) = self.detail.as_ref().and_then(|d| d.status.as_deref()) {
+            notes.push_str(&format!(", Status: {}", status));
+        }
+        notes
+    }
+
+    /// The `known_beverages` row this record is cached as, or None if it has
+    /// no reference ABV (such records are not cached).
+    pub fn to_known_beverage(&self) -> Option<NewKnownBeverage> {
+        Some(NewKnownBeverage {
+            brand_name: self.brand_name.clone(),
+            product_name: self.fanciful_name.clone(),
+            class_type: self.class_type_desc.clone(),
+            beverage_category: self.beverage_category.clone(),
+            abv: self.reference_abv()?,
+            standard_size_ml: None,
+            country_of_origin: None,
+            producer: self.detail.as_ref().and_then(|d| d.applicant_name.clone()),
+            is_verified: false,
+            source: "ttb_cola".to_string(),
+            source_url: Some(self.source_url.clone()),
+            notes: Some(self.cache_notes()),
+            abv_source: self.abv_source(),
+        })
+    }
+}
+
+/// Fields from a COLA detail page (`viewColaDetails.do`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TtbColaDetail {
+    /// Approval status as shown by TTB (e.g. "APPROVED", "SURRENDERED").
+    pub status: Option<String>,
+    pub approval_date: Option<NaiveDate>,
+    pub applicant_name: Option<String>,
+    pub applicant_address: Option<String>,
+    /// Alcohol content stated on the application, when present.
+    pub alcohol_content: Option<f64>,
+    /// Absolute URLs of the approved label images.
+    #[serde(default)]
+    pub label_image_urls: Vec<String>,
+}
+
+impl TtbColaDetail {
+    /// The status text as a `ColaStatus` (`None` if absent or unrecognized).
+    pub fn approval_status(&self) -> Option<ColaStatus> {
+        self.status.as_deref().and_then(ColaStatus::from_ttb_text)
+    }
 }
 
 /// Error type for TTB COLA client operations.
-#[derive(Debug, thiserror::Error)]
+///
+/// Cloneable so that coalesced searches can share one outcome.
+#[derive(Debug, Clone, thiserror::Error)]
 pub enum TtbColaError {
     #[error("HTTP request to TTB failed: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(Arc<reqwest::Error>),
 
     #[error("Failed to parse TTB response HTML: {0}")]
     Parse(String),
 
     #[error("TTB service unavailable: {0}")]
     Unavailable(String),
+
+    #[error("TTB COLA fixture error: {0}")]
+    Fixture(String),
+
+    #[error("Invalid TTB COLA configuration: {0}")]
+    Config(String),
+
+    #[error("TTB COLA requests suspended after repeated failures (next attempt in {0}s)")]
+    CircuitOpen(u64),
 }
 
+impl From<reqwest::Error> for TtbColaError {
+    fn from(e: reqwest::Error) -> Self {
+        TtbColaError::Http(Arc::new(e))
+    }
+}
+
+impl TtbColaError {
+    /// Whether the failure points at TTB itself (network error or error
+    /// status) and so counts toward the circuit breaker and may be retried.
+    fn is_transient(&self) -> bool {
+        matches!(self, TtbColaError::Http(_) | TtbColaError::Unavailable(_))
+    }
+}
+
+/// How the client obtains TTB pages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TtbColaMode {
+    /// Request pages from `base_url` over HTTP.
+    Live,
+    /// Request pages over HTTP and save each response in the directory.
+    Record(PathBuf),
+    /// Serve saved responses from the directory without touching the network.
+    Replay(PathBuf),
+}
+
+/// TTB COLA client settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TtbColaConfig {
+    /// COLA Online base URL (point at a local stub server in development).
+    pub base_url: String,
+    pub mode: TtbColaMode,
+    /// Rate limit, retry and circuit breaker settings for HTTP requests.
+    pub throttle: ThrottleSettings,
+}
+
+impl Default for TtbColaConfig {
+    fn default() -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.to_string(),
+            mode: TtbColaMode::Live,
+            throttle: ThrottleSettings::default(),
+        }
+    }
+}
+
+impl TtbColaConfig {
+    /// Build settings from the optional `TTB_COLA_*` environment values.
+    ///
+    /// `mode` is `live` (default), `record` or `replay`; the latter two need
+    /// `fixtures_dir`. `throttle` is a `services::throttle` spec.
+    pub fn from_settings(
+        base_url: Option<&str>,
+        mode: Option<&str>,
+        fixtures_dir: Option<&str>,
+        throttle: Option<&str>,
+    ) -> Result<Self, TtbColaError> {
+        let fixtures_dir = || {
+            fixtures_dir
+                .map(PathBuf::from)
+                .ok_or_else(|| TtbColaError::Config("record and replay modes need a fixtures directory".to_string()))
+        };
+
+        let mode = match mode.map(|m| m.trim().to_lowercase()).as_deref() {
+            None | Some("") | Some("live") => TtbColaMode::Live,
+            Some("record") => TtbColaMode::Record(fixtures_dir()?),
+            Some("replay") => TtbColaMode::Replay(fixtures_dir()?),
+            Some(other) => {
+                return Err(TtbColaError::Config(format!(
+                    "unknown mode '{}' (expected live, record or replay)",
+                    other
+                )))
+            }
+        };
+
+        Ok(Self {
+            base_url: base_url
+                .map(|url| url.trim().trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty())
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            mode,
+            throttle: throttle
+                .map(ThrottleSettings::from_spec)
+                .transpose()
+                .map_err(|e| TtbColaError::Config(e.to_string()))?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Shared outcome of an in-flight brand search.
+type SearchCell = Arc<OnceCell<Result<Vec<TtbColaRecord>, TtbColaError>>>;
+
 /// Client for querying the TTB COLA public database.
+///
+/// HTTP requests share one rate limiter and circuit breaker, and identical
+/// concurrent brand searches are coalesced into a single request.
 pub struct TtbColaClient {
     http: reqwest::Client,
     base_url: String,
+    mode: TtbColaMode,
+    throttle: ThrottleSettings,
+    limiter: RateLimiter,
+    breaker: CircuitBreaker,
+    in_flight: Mutex<HashMap<String, SearchCell>>,
 }
 
 impl TtbColaClient {
-    /// Create a new TTB COLA client.
+    /// Create a live TTB COLA client against `DEFAULT_BASE_URL`.
+    pub fn new() -> Result<Self, TtbColaError> {
+        Self::with_config(TtbColaConfig::default())
+    }
+
+    /// Create a TTB COLA client with a custom base URL and mode.
     ///
     /// Uses `danger_accept_invalid_certs(true)` because the TTB website
     /// has recurring SSL certificate issues.
-    pub fn new() -> Result<Self, TtbColaError> {
+    pub fn with_config(config: TtbColaConfig) -> Result<Self, TtbColaError> {
         let http = reqwest::Client::builder()
             .danger_accept_invalid_certs(true)
             .user_agent("Mozilla/5.0 (compatible; LabelVerifyBot/1.0; +https://github.com/aerocristobal/label-verify-hw)")
@@ -76,29 +336,72 @@ impl TtbColaClient {
 
         Ok(Self {
             http,
-            base_url: "https://ttbonline.gov/colasonline".to_string(),
+            base_url: config.base_url,
+            mode: config.mode,
+            limiter: RateLimiter::new(config.throttle.max_concurrent, config.throttle.min_interval),
+            breaker: CircuitBreaker::new(
+                config.throttle.failure_threshold,
+                config.throttle.open_duration,
+            ),
+            throttle: config.throttle,
+            in_flight: Mutex::new(HashMap::new()),
         })
     }
 
     /// Search TTB COLA public database by brand name.
     ///
     /// Uses the TTB search form with `productOrFancifulName` parameter
-    /// and a 5-year lookback window for maximum recall.
+    /// and a 5-year lookback window for maximum recall. Concurrent calls for
+    /// the same brand (case-insensitive), category and limit share one request.
     pub async fn search_by_brand(
         &self,
         brand_name: &str,
         category: Option<&str>,
         limit: usize,
     ) -> Result<Vec<TtbColaRecord>, TtbColaError> {
-        let now = chrono::Utc::now();
-        let from_date = now - chrono::Duration::days(5 * 365);
+        let key = format!("{}|{}|{}", brand_search_key(brand_name), category.unwrap_or_default(), limit);
+
+        let cell = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            match in_flight.get(&key) {
+                Some(cell) => {
+                    metrics::counter!("ttb_cola_coalesced_total").increment(1);
+                    debug!(brand = %brand_name, "Joining in-flight TTB COLA brand search");
+                    cell.clone()
+                }
+                None => {
+                    let cell = SearchCell::default();
+                    in_flight.insert(key.clone(), cell.clone());
+                    cell
+                }
+            }
+        };
+
+        let result = cell
+            .get_or_init(|| self.search_by_brand_uncoalesced(brand_name, category, limit))
+            .await
+            .clone();
+
+        // Whoever finishes first retires the entry, so later calls search afresh
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(&key).is_some_and(|c| Arc::ptr_eq(c, &cell)) {
+            in_flight.remove(&key);
+        }
+
+        result
+    }
+
+    async fn search_by_brand_uncoalesced(
+        &self,
+        brand_name: &str,
+        category: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<TtbColaRecord>, TtbColaError> {
+        let (from_date, to_date) = default_date_range();
 
-        let mut params = vec![
-            ("searchCriteria.dateCompletedFrom", from_date.format("%m/%d/%Y").to_string()),
-            ("searchCriteria.dateCompletedTo", now.format("%m/%d/%Y").to_string()),
-            ("searchCriteria.productOrFancifulName", brand_name.to_string()),
-            ("searchCriteria.productNameSearchType", "E".to_string()),
-        ];
+        let mut params = date_params(from_date, to_date);
+        params.push(("searchCriteria.productOrFancifulName", brand_name.to_string()));
+        params.push(("searchCriteria.productNameSearchType", "E".to_string()));
 
         // Add category-specific class type code ranges
         if let Some(cat) = category {
@@ -119,24 +422,259 @@ impl TtbColaClient {
             }
         }
 
+        self.run_search(&params, limit).await
+    }
+
+    /// Search by permit number (e.g. "BWN-CA-12345") for COLAs completed in a date range.
+    pub async fn search_by_permit(
+        &self,
+        permit_no: &str,
+        from_date: NaiveDate,
+        to_date: NaiveDate,
+        limit: usize,
+    ) -> Result<Vec<TtbColaRecord>, TtbColaError> {
+        let mut params = date_params(from_date, to_date);
+        params.push(("searchCriteria.permitId", permit_no.trim().to_string()));
+
+        self.run_search(&params, limit).await
+    }
+
+    /// Search by applicant serial number (e.g. "250001") over the 5-year window.
+    ///
+    /// Serial numbers are only unique per permit, so this can return several records.
+    pub async fn search_by_serial_number(
+        &self,
+        serial_number: &str,
+        limit: usize,
+    ) -> Result<Vec<TtbColaRecord>, TtbColaError> {
+        let (from_date, to_date) = default_date_range();
+
+        let mut params = date_params(from_date, to_date);
+        params.push(("searchCriteria.serialNumber", serial_number.trim().to_string()));
+
+        self.run_search(&params, limit).await
+    }
+
+    /// Fetch a single COLA by TTB ID, including its detail page data.
+    ///
+    /// Returns `None` if the ID isn't a TTB ID or TTB's page says no COLA has
+    /// it. Any other page that can't be read as a COLA (an error or
+    /// maintenance page, a changed layout) is a `Parse` error, so callers
+    /// don't mistake it for a withdrawn COLA.
+    pub async fn fetch_by_ttb_id(&self, ttb_id: &str) -> Result<Option<TtbColaRecord>, TtbColaError> {
+        let ttb_id = ttb_id.trim();
+        if ttb_id.is_empty() || !ttb_id.chars().all(|c| c.is_ascii_digit()) {
+            return Ok(None);
+        }
+
+        let url = self.detail_url(ttb_id);
+        let html = self.get_page(&url).await?;
+        if html.contains(COLA_NOT_FOUND_MESSAGE) {
+            return Ok(None);
+        }
+        self.parse_detail_record(&html, &url).map(Some)
+    }
+
+    /// POST a search form and parse the results table.
+    async fn run_search(
+        &self,
+        params: &[(&str, String)],
+        limit: usize,
+    ) -> Result<Vec<TtbColaRecord>, TtbColaError> {
         let url = format!("{}/publicSearchColasBasicProcess.do?action=search", self.base_url);
 
-        let response = self
-            .http
-            .post(&url)
-            .form(&params)
-            .send()
-            .await?;
+        let html = self.fetch(&url, Some(params)).await?;
+        self.parse_search_results(&html, limit)
+    }
+
+    /// GET a TTB page.
+    async fn get_page(&self, url: &str) -> Result<String, TtbColaError> {
+        self.fetch(url, None).await
+    }
+
+    /// Fetch a page (POST when `form` is given, else GET) according to the mode.
+    async fn fetch(&self, url: &str, form: Option<&[(&str, String)]>) -> Result<String, TtbColaError> {
+        match &self.mode {
+            TtbColaMode::Live => self.fetch_live(url, form).await,
+            TtbColaMode::Replay(dir) => {
+                let path = dir.join(fixture_file_name(url, form.unwrap_or_default()));
+                tokio::fs::read_to_string(&path).await.map_err(|e| {
+                    TtbColaError::Fixture(format!("no recorded response {}: {}", path.display(), e))
+                })
+            }
+            TtbColaMode::Record(dir) => {
+                let html = self.fetch_live(url, form).await?;
+                let path = dir.join(fixture_file_name(url, form.unwrap_or_default()));
+                let write = async {
+                    tokio::fs::create_dir_all(dir).await?;
+                    tokio::fs::write(&path, &html).await
+                };
+                write.await.map_err(|e| {
+                    TtbColaError::Fixture(format!("failed to record {}: {}", path.display(), e))
+                })?;
+                Ok(html)
+            }
+        }
+    }
+
+    /// Request a page over HTTP through the rate limiter and circuit breaker,
+    /// retrying transient failures with exponential backoff.
+    async fn fetch_live(&self, url: &str, form: Option<&[(&str, String)]>) -> Result<String, TtbColaError> {
+        let mut attempt = 0;
+        loop {
+            if let Err(remaining) = self.breaker.check() {
+                metrics::counter!("ttb_cola_short_circuited_total").increment(1);
+                return Err(TtbColaError::CircuitOpen(remaining.as_secs().max(1)));
+            }
+
+            let permit = self.limiter.acquire().await;
+            let started = Instant::now();
+            let result = self.send(url, form).await;
+            drop(permit);
+            metrics::histogram!("ttb_cola_request_seconds").record(started.elapsed().as_secs_f64());
+
+            self.record_outcome(&result);
+            match result {
+                Ok(html) => return Ok(html),
+                Err(e) if !e.is_transient() => return Err(e),
+                Err(e) => {
+                    if attempt >= self.throttle.max_retries {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                    let delay = throttle::backoff_delay(self.throttle.backoff_base, attempt);
+                    warn!(url = %url, attempt, delay_ms = delay.as_millis() as u64, error = %e, "TTB COLA request failed, retrying");
+                    metrics::counter!("ttb_cola_retries_total").increment(1);
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// Report a completed request to the circuit breaker. Only a transient
+    /// failure counts against TTB; any other outcome means TTB answered, which
+    /// closes the circuit and ends a half-open trial.
+    fn record_outcome(&self, result: &Result<String, TtbColaError>) {
+        let outcome = if result.is_ok() { "success" } else { "failure" };
+        metrics::counter!("ttb_cola_requests_total", "outcome" => outcome).increment(1);
+        match result {
+            Err(e) if e.is_transient() => self.breaker.record_failure(),
+            _ => self.breaker.record_success(),
+        }
+        metrics::gauge!("ttb_cola_circuit_state").set(self.breaker.state().as_gauge());
+    }
+
+    /// Send one HTTP request, mapping non-success statuses to `Unavailable`.
+    async fn send(&self, url: &str, form: Option<&[(&str, String)]>) -> Result<String, TtbColaError> {
+        let request = match form {
+            Some(params) => self.http.post(url).form(params),
+            None => self.http.get(url),
+        };
+        let response = request.send().await?;
 
         if !response.status().is_success() {
             return Err(TtbColaError::Unavailable(format!(
-                "TTB returned HTTP {}",
-                response.status()
+                "TTB returned HTTP {} for {}",
+                response.status(),
+                url
             )));
         }
 
-        let html = response.text().await?;
-        self.parse_search_results(&html, limit)
+        Ok(response.text().await?)
+    }
+
+    /// Public detail page URL for a TTB ID.
+    fn detail_url(&self, ttb_id: &str) -> String {
+        detail_url(&self.base_url, ttb_id)
+    }
+
+    /// Fetch and parse the detail page for a record (its `source_url`).
+    pub async fn fetch_detail(&self, record: &TtbColaRecord) -> Result<TtbColaDetail, TtbColaError> {
+        let html = self.get_page(&record.source_url).await?;
+        self.parse_detail_page(&html)
+    }
+
+    /// Parse a COLA detail page.
+    ///
+    /// The page lays out `Label:` / value pairs in table cells, either in the
+    /// same cell (`<strong>Status:</strong> APPROVED`) or in the next cell.
+    /// Label images are `<img>` tags pointing at `publicViewAttachment.do`.
+    fn parse_detail_page(&self, html: &str) -> Result<TtbColaDetail, TtbColaError> {
+        let document = Html::parse_document(html);
+        let fields = detail_fields(&document);
+
+        if field_value(&fields, |l| l == "TTB ID").is_none() {
+            return Err(TtbColaError::Parse("COLA detail page has no TTB ID field".to_string()));
+        }
+
+        let applicant = field_lines(&fields, |l| l.contains("NAME AND ADDRESS"));
+        let img_sel = Selector::parse("img").expect("valid selector");
+
+        Ok(TtbColaDetail {
+            status: field_value(&fields, |l| l == "STATUS").map(|s| s.to_uppercase()),
+            approval_date: field_value(&fields, |l| {
+                l == "APPROVAL DATE" || l == "DATE ISSUED" || l == "DATE OF APPROVAL"
+            })
+            .and_then(|d| NaiveDate::parse_from_str(&d, "%m/%d/%Y").ok()),
+            applicant_name: applicant.and_then(|lines| lines.first().cloned()),
+            applicant_address: applicant
+                .filter(|lines| lines.len() > 1)
+                .map(|lines| lines[1..].join(", ")),
+            alcohol_content: field_value(&fields, |l| l == "ALCOHOL CONTENT")
+                .and_then(|v| parse_alcohol_content(&v)),
+            label_image_urls: document
+                .select(&img_sel)
+                .filter_map(|img| img.value().attr("src"))
+                .filter(|src| src.contains("Attachment"))
+                .map(|src| self.absolute_url(src))
+                .collect(),
+        })
+    }
+
+    /// Build a full record (search-table fields plus detail) from a detail page.
+    ///
+    /// Fails when the page isn't a COLA or lacks the brand name or class/type.
+    fn parse_detail_record(&self, html: &str, url: &str) -> Result<TtbColaRecord, TtbColaError> {
+        let detail = self.parse_detail_page(html)?;
+        let document = Html::parse_document(html);
+        let fields = detail_fields(&document);
+        let required = |label: &str, pred: fn(&str) -> bool| {
+            field_value(&fields, pred)
+                .ok_or_else(|| TtbColaError::Parse(format!("COLA detail page has no {} field", label)))
+        };
+
+        let ttb_id = required("TTB ID", |l| l == "TTB ID")?;
+        let brand_name = required("brand name", |l| l == "BRAND NAME")?;
+        let class_type_desc =
+            required("class/type", |l| l == "CLASS/TYPE DESCRIPTION" || l == "CLASS/TYPE")?;
+        let class_type_code = field_value(&fields, |l| l == "CLASS/TYPE CODE").unwrap_or_default();
+        let text = |pred: fn(&str) -> bool| field_value(&fields, pred).unwrap_or_default();
+
+        Ok(TtbColaRecord {
+            permit_no: text(|l| l.contains("PERMIT") && !l.contains("NAME AND ADDRESS")),
+            serial_number: text(|l| l == "SERIAL #" || l == "SERIAL NUMBER"),
+            completed_date: detail.approval_date,
+            fanciful_name: field_value(&fields, |l| l == "FANCIFUL NAME"),
+            origin_code: text(|l| l == "ORIGIN CODE"),
+            origin_desc: text(|l| l == "ORIGIN" || l == "ORIGIN DESCRIPTION"),
+            source_url: url.to_string(),
+            inferred_abv: infer_abv_from_class_type(&class_type_desc),
+            beverage_category: get_category_from_class_type(&class_type_desc, &class_type_code),
+            ttb_id,
+            brand_name,
+            class_type_code,
+            class_type_desc,
+            detail: Some(detail),
+        })
+    }
+
+    /// Resolve a link from a TTB page against the COLA base URL.
+    fn absolute_url(&self, href: &str) -> String {
+        if href.starts_with("http") {
+            href.to_string()
+        } else {
+            format!("{}/{}", self.base_url, href.trim_start_matches('/'))
+        }
     }
 
     /// Parse HTML search results table from TTB COLA response.
@@ -210,19 +748,8 @@ impl TtbColaClient {
                 .select(&a_sel)
                 .next()
                 .and_then(|a| a.value().attr("href"))
-                .map(|href| {
-                    if href.starts_with("http") {
-                        href.to_string()
-                    } else {
-                        format!("{}/{}", self.base_url, href)
-                    }
-                })
-                .unwrap_or_else(|| {
-                    format!(
-                        "{}/viewColaDetails.do?action=publicDisplaySearchBasic&ttbid={}",
-                        self.base_url, ttb_id
-                    )
-                });
+                .map(|href| self.absolute_url(href))
+                .unwrap_or_else(|| self.detail_url(&ttb_id));
 
             let fanciful_name = if fanciful_name_raw.is_empty() {
                 None
@@ -247,6 +774,7 @@ impl TtbColaClient {
                 source_url,
                 inferred_abv,
                 beverage_category,
+                detail: None,
             });
 
             if records.len() >= limit {
@@ -258,6 +786,146 @@ impl TtbColaClient {
     }
 }
 
+/// Normalized brand used to key brand searches: trimmed, lowercased, with
+/// whitespace runs collapsed. TTB's brand search is case-insensitive, so
+/// searches with the same key return the same records.
+pub fn brand_search_key(brand_name: &str) -> String {
+    brand_name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// File name under which a response is recorded and replayed.
+///
+/// Keyed by the endpoint plus the URL query and form parameters, sorted,
+/// with values trimmed and lowercased and the completed-date window left
+/// out (see `UNKEYED_PARAMS`). The name starts with a readable slug of the
+/// parameter values, e.g. `publicSearchColasBasicProcess-e-fetzer-<hash>.html`.
+pub fn fixture_file_name(url: &str, form: &[(&str, String)]) -> String {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let endpoint = path.rsplit('/').next().unwrap_or_default().trim_end_matches(".do");
+
+    let mut params: Vec<(String, String)> = query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .chain(form.iter().map(|(k, v)| (k.to_string(), v.clone())))
+        .filter(|(k, _)| !UNKEYED_PARAMS.contains(&k.as_str()))
+        .map(|(k, v)| (k, v.trim().to_lowercase()))
+        .collect();
+    params.sort();
+
+    let slug: String = params
+        .iter()
+        .filter(|(k, _)| k != "action")
+        .map(|(_, v)| v.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .take(40)
+        .collect();
+
+    let canonical = params
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&");
+
+    format!(
+        "{}-{}-{:016x}.html",
+        endpoint,
+        slug.trim_end_matches('-'),
+        fnv1a(&format!("{}?{}", endpoint, canonical))
+    )
+}
+
+/// 64-bit FNV-1a hash (stable across Rust versions, unlike `DefaultHasher`).
+fn fnv1a(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Default search window: the last 5 years, for maximum recall.
+pub fn default_date_range() -> (NaiveDate, NaiveDate) {
+    let today = chrono::Utc::now().date_naive();
+    (today - chrono::Duration::days(5 * 365), today)
+}
+
+/// Completed-date range parameters for the search form.
+fn date_params(from_date: NaiveDate, to_date: NaiveDate) -> Vec<(&'static str, String)> {
+    vec![
+        ("searchCriteria.dateCompletedFrom", from_date.format("%m/%d/%Y").to_string()),
+        ("searchCriteria.dateCompletedTo", to_date.format("%m/%d/%Y").to_string()),
+    ]
+}
+
+/// `Label:` / value-lines pairs from a detail page, labels uppercased.
+///
+/// The value is whatever follows the colon in the same cell, plus the cell's
+/// remaining lines; if that is empty, the next cell's lines.
+fn detail_fields(document: &Html) -> Vec<(String, Vec<String>)> {
+    let td_sel = Selector::parse("td").expect("valid selector");
+
+    let cells: Vec<Vec<String>> = document
+        .select(&td_sel)
+        .map(|cell| {
+            cell.text()
+                .map(|t| t.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|t| !t.is_empty())
+                .collect()
+        })
+        .collect();
+
+    let mut fields = Vec::new();
+    for (i, lines) in cells.iter().enumerate() {
+        let Some((label, rest)) = lines.first().and_then(|first| first.split_once(':')) else {
+            continue;
+        };
+
+        let mut value: Vec<String> = std::iter::once(rest.trim().to_string())
+            .chain(lines[1..].iter().cloned())
+            .filter(|l| !l.is_empty())
+            .collect();
+        if value.is_empty() {
+            value = cells.get(i + 1).cloned().unwrap_or_default();
+        }
+        if !value.is_empty() {
+            fields.push((label.trim().to_uppercase(), value));
+        }
+    }
+    fields
+}
+
+/// Value lines of the first field whose label matches.
+fn field_lines(fields: &[(String, Vec<String>)], matches: impl Fn(&str) -> bool) -> Option<&[String]> {
+    fields
+        .iter()
+        .find(|(label, _)| matches(label))
+        .map(|(_, lines)| lines.as_slice())
+}
+
+/// Single-line value of the first field whose label matches.
+fn field_value(fields: &[(String, Vec<String>)], matches: impl Fn(&str) -> bool) -> Option<String> {
+    field_lines(fields, matches).map(|lines| lines.join(" "))
+}
+
+/// Parse the first number in an alcohol content statement ("13.5% ALC/VOL").
+fn parse_alcohol_content(value: &str) -> Option<f64> {
+    let start = value.find(|c: char| c.is_ascii_digit())?;
+    let number: String = value[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+    number.parse().ok().filter(|abv: &f64| *abv > 0.0 && *abv <= 100.0)
+}
+
 /// Infer ABV from TTB class/type description using regulatory ranges.
 ///
 /// TTB COLA results do NOT include ABV. We infer typical values
@@ -487,5 +1155,361 @@ mod tests {
         assert_eq!(records[0].inferred_abv, Some(12.0));
         assert_eq!(records[0].beverage_category, "wine");
         assert_eq!(records[0].fanciful_name, Some("Reserve".to_string()));
+        assert_eq!(
+            records[0].source_url,
+            "https://ttbonline.gov/colasonline/viewColaDetails.do?ttbid=123"
+        );
+    }
+
+    #[test]
+    fn test_parse_detail_page() {
+        let client = TtbColaClient::new().unwrap();
+        let html = r#"
+        <html><body>
+        <table>
+            <tr><td><strong>TTB ID:</strong> 23001001000123</td></tr>
+            <tr><td><strong>Status:</strong></td><td>Approved</td></tr>
+            <tr><td><strong>Approval Date:</strong> 01/15/2026</td></tr>
+            <tr>
+                <td><strong>Name and Address of Applicant:</strong></td>
+                <td>STONE CREEK CELLARS<br>123 Vineyard Rd<br>Napa, CA 94558</td>
+            </tr>
+            <tr><td><strong>Alcohol Content:</strong> 13.9%</td></tr>
+        </table>
+        <img src="/images/logo.gif">
+        <img src="publicViewAttachment.do?filename=front.jpg&amp;filetype=l">
+        </body></html>
+        "#;
+        let detail = client.parse_detail_page(html).unwrap();
+        assert_eq!(detail.status.as_deref(), Some("APPROVED"));
+        assert_eq!(detail.approval_date, NaiveDate::from_ymd_opt(2026, 1, 15));
+        assert_eq!(detail.applicant_name.as_deref(), Some("STONE CREEK CELLARS"));
+        assert_eq!(
+            detail.applicant_address.as_deref(),
+            Some("123 Vineyard Rd, Napa, CA 94558")
+        );
+        assert_eq!(detail.alcohol_content, Some(13.9));
+        assert_eq!(
+            detail.label_image_urls,
+            vec!["https://ttbonline.gov/colasonline/publicViewAttachment.do?filename=front.jpg&filetype=l"]
+        );
+    }
+
+    #[test]
+    fn test_cola_status_from_ttb_text() {
+        assert_eq!(ColaStatus::from_ttb_text("APPROVED"), Some(ColaStatus::Approved));
+        assert_eq!(ColaStatus::from_ttb_text("Voluntarily Surrendered"), Some(ColaStatus::Surrendered));
+        assert_eq!(ColaStatus::from_ttb_text("REVOKED"), Some(ColaStatus::Revoked));
+        assert_eq!(ColaStatus::from_ttb_text("EXPIRED"), Some(ColaStatus::Expired));
+        assert_eq!(ColaStatus::from_ttb_text("RECEIVED"), None);
+    }
+
+    #[test]
+    fn test_parse_detail_record() {
+        let client = TtbColaClient::new().unwrap();
+        let html = r#"
+        <html><body>
+        <table>
+            <tr><td><strong>TTB ID:</strong> 23001001000123</td></tr>
+            <tr><td><strong>Status:</strong></td><td>Approved</td></tr>
+            <tr><td><strong>Plant Registry/Basic Permit/Brewers No:</strong> CA-I-1234</td></tr>
+            <tr><td><strong>Serial #:</strong> 230001</td></tr>
+            <tr><td><strong>Brand Name:</strong> STONE CREEK</td></tr>
+            <tr><td><strong>Class/Type Code:</strong> 80</td></tr>
+            <tr><td><strong>Class/Type Description:</strong> TABLE RED WINE</td></tr>
+            <tr><td><strong>Origin Code:</strong> 06</td></tr>
+        </table>
+        </body></html>
+        "#;
+        let url = client.detail_url("23001001000123");
+        let record = client.parse_detail_record(html, &url).unwrap();
+        assert_eq!(record.ttb_id, "23001001000123");
+        assert_eq!(record.permit_no, "CA-I-1234");
+        assert_eq!(record.serial_number, "230001");
+        assert_eq!(record.brand_name, "STONE CREEK");
+        assert_eq!(record.class_type_desc, "TABLE RED WINE");
+        assert_eq!(record.beverage_category, "wine");
+        assert_eq!(record.source_url, url);
+        assert_eq!(record.detail.unwrap().status.as_deref(), Some("APPROVED"));
+
+        // A COLA page without a brand name can't be used as a reference
+        assert!(client
+            .parse_detail_record(&html.replace("Brand Name", "Label Name"), &url)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_detail_page_rejects_other_pages() {
+        let client = TtbColaClient::new().unwrap();
+        assert!(client.parse_detail_page("<html><body>Session expired</body></html>").is_err());
+    }
+
+    #[test]
+    fn test_reference_abv_prefers_approved_value() {
+        let mut record = TtbColaRecord {
+            ttb_id: "1".to_string(),
+            permit_no: String::new(),
+            serial_number: String::new(),
+            completed_date: None,
+            fanciful_name: None,
+            brand_name: "FETZER".to_string(),
+            origin_code: String::new(),
+            origin_desc: String::new(),
+            class_type_code: "80".to_string(),
+            class_type_desc: "TABLE RED WINE".to_string(),
+            source_url: String::new(),
+            inferred_abv: Some(12.0),
+            beverage_category: "wine".to_string(),
+            detail: None,
+        };
+        assert_eq!(record.reference_abv(), Some(12.0));
+        assert_eq!(record.abv_source(), AbvSource::Inferred);
+        record.detail = Some(TtbColaDetail {
+            alcohol_content: Some(13.9),
+            ..TtbColaDetail::default()
+        });
+        assert_eq!(record.reference_abv(), Some(13.9));
+        assert_eq!(record.abv_source(), AbvSource::Measured);
+    }
+
+    fn fixtures_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ttb_cola")
+    }
+
+    fn replay_client() -> TtbColaClient {
+        TtbColaClient::with_config(TtbColaConfig {
+            mode: TtbColaMode::Replay(fixtures_dir()),
+            ..TtbColaConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_fixture_file_name_ignores_date_window_and_case() {
+        let url = format!("{}/publicSearchColasBasicProcess.do?action=search", DEFAULT_BASE_URL);
+        let today = date_params(
+            NaiveDate::from_ymd_opt(2026, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2026, 10, 18).unwrap(),
+        );
+        let mut form = today.clone();
+        form.push(("searchCriteria.productOrFancifulName", "Stone Creek".to_string()));
+        form.push(("searchCriteria.productNameSearchType", "E".to_string()));
+
+        let mut later = date_params(
+            NaiveDate::from_ymd_opt(2027, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2027, 10, 18).unwrap(),
+        );
+        later.push(("searchCriteria.productNameSearchType", "E".to_string()));
+        later.push(("searchCriteria.productOrFancifulName", " STONE CREEK ".to_string()));
+
+        let name = fixture_file_name(&url, &form);
+        assert_eq!(name, fixture_file_name(&url, &later));
+        assert!(name.starts_with("publicSearchColasBasicProcess-e-stone-creek-"));
+        assert!(name.ends_with(".html"));
+
+        form[2].1 = "Stone Creak".to_string();
+        assert_ne!(name, fixture_file_name(&url, &form));
+    }
+
+    #[test]
+    fn test_brand_search_key() {
+        assert_eq!(brand_search_key("  Stone   Creek "), "stone creek");
+        assert_eq!(brand_search_key("STONE CREEK"), brand_search_key("stone creek"));
+        assert_ne!(brand_search_key("Stone Creek Cellars"), brand_search_key("Stone Creek"));
+    }
+
+    #[test]
+    fn test_config_from_settings() {
+        assert_eq!(TtbColaConfig::from_settings(None, None, None, None).unwrap(), TtbColaConfig::default());
+
+        let config = TtbColaConfig::from_settings(
+            Some("http://127.0.0.1:8089/colasonline/"),
+            Some("Replay"),
+            Some("tests/fixtures/ttb_cola"),
+            Some("max_concurrent=1"),
+        )
+        .unwrap();
+        assert_eq!(config.base_url, "http://127.0.0.1:8089/colasonline");
+        assert_eq!(config.mode, TtbColaMode::Replay(PathBuf::from("tests/fixtures/ttb_cola")));
+        assert_eq!(config.throttle.max_concurrent, 1);
+
+        assert!(TtbColaConfig::from_settings(None, Some("record"), None, None).is_err());
+        assert!(TtbColaConfig::from_settings(None, Some("offline"), Some("dir"), None).is_err());
+        assert!(TtbColaConfig::from_settings(None, None, None, Some("burst=3")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_replay_search_and_detail() {
+        let client = replay_client();
+
+        let records = client.search_by_brand("Stone Creek", None, 20).await.unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].ttb_id, "23001001000123");
+        assert_eq!(records[2].beverage_category, "wine");
+
+        let detail = client.fetch_detail(&records[0]).await.unwrap();
+        assert_eq!(detail.status.as_deref(), Some("APPROVED"));
+        assert_eq!(detail.alcohol_content, Some(13.9));
+
+        assert!(client.search_by_brand("No Such Brand", None, 20).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_replay_identifier_lookups() {
+        let client = replay_client();
+
+        let record = client.fetch_by_ttb_id("23001001000123").await.unwrap().unwrap();
+        assert_eq!(record.brand_name, "STONE CREEK");
+        assert_eq!(record.permit_no, "CA-I-1234");
+        assert_eq!(record.reference_abv(), Some(13.9));
+        assert!(client.fetch_by_ttb_id("99999999999999").await.unwrap().is_none());
+        assert!(client.fetch_by_ttb_id("not-a-ttb-id").await.unwrap().is_none());
+        // A maintenance page is not a "not found" answer
+        assert!(matches!(
+            client.fetch_by_ttb_id("99999999999998").await,
+            Err(TtbColaError::Parse(_))
+        ));
+
+        let from = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2026, 12, 31).unwrap();
+        assert_eq!(client.search_by_permit("CA-I-1234", from, to, 20).await.unwrap().len(), 2);
+        assert_eq!(client.search_by_serial_number("230001", 20).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_replay_missing_fixture() {
+        let err = replay_client()
+            .search_by_brand("Unrecorded Brand", None, 20)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, TtbColaError::Fixture(_)));
+    }
+
+    /// Serve `app` on a local port and return its COLA base URL.
+    async fn spawn_stub(app: axum::Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}/colasonline", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        base_url
+    }
+
+    fn live_client(base_url: String, throttle: &str) -> TtbColaClient {
+        TtbColaClient::with_config(TtbColaConfig {
+            base_url,
+            throttle: ThrottleSettings::from_spec(throttle).unwrap(),
+            ..TtbColaConfig::default()
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_identical_brand_searches_are_coalesced() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = axum::Router::new().route(
+            "/colasonline/publicSearchColasBasicProcess.do",
+            axum::routing::post(move || {
+                let counter = counter.clone();
+                async move {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+                    axum::response::Html("No results were found for your search criteria")
+                }
+            }),
+        );
+        let client = live_client(spawn_stub(app).await, "min_interval_ms=0");
+
+        let searches = ["Stone Creek", "STONE CREEK", " stone  creek", "Stone Creek"]
+            .map(|brand| client.search_by_brand(brand, None, 20));
+        for result in futures::future::join_all(searches).await {
+            assert!(result.unwrap().is_empty());
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+        // Once finished, the next search goes out again
+        client.search_by_brand("Stone Creek", None, 20).await.unwrap();
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_after_repeated_failures() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let app = axum::Router::new().fallback(move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                axum::http::StatusCode::SERVICE_UNAVAILABLE
+            }
+        });
+        let client = live_client(
+            spawn_stub(app).await,
+            "min_interval_ms=0, max_retries=1, backoff_ms=1, failure_threshold=2, open_secs=60",
+        );
+
+        // One request plus one retry, both failing, opens the circuit
+        let err = client.search_by_brand("Stone Creek", None, 20).await.unwrap_err();
+        assert!(matches!(err, TtbColaError::Unavailable(_)));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // Further requests are rejected without reaching TTB
+        let err = client.fetch_by_ttb_id("23001001000123").await.unwrap_err();
+        assert!(matches!(err, TtbColaError::CircuitOpen(_)));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_non_transient_trial_outcome_closes_circuit() {
+        let mut client = live_client(DEFAULT_BASE_URL.to_string(), "");
+        client.breaker = CircuitBreaker::new(1, std::time::Duration::from_millis(20));
+        client.record_outcome(&Err(TtbColaError::Unavailable("HTTP 503".to_string())));
+        std::thread::sleep(std::time::Duration::from_millis(25));
+
+        // The half-open trial ends in an error that is not TTB's fault
+        assert!(client.breaker.check().is_ok());
+        client.record_outcome(&Err(TtbColaError::Parse("unexpected page".to_string())));
+        assert_eq!(client.breaker.state(), throttle::CircuitState::Closed);
+        assert!(client.breaker.check().is_ok());
+        assert!(client.breaker.check().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_record_then_replay() {
+        let search_page = std::fs::read_to_string(
+            fixtures_dir().join("publicSearchColasBasicProcess-e-stone-creek-e488b49f0b15cbee.html"),
+        )
+        .unwrap();
+        let app = axum::Router::new().route(
+            "/colasonline/publicSearchColasBasicProcess.do",
+            axum::routing::post(move || async move { axum::response::Html(search_page) }),
+        );
+        let base_url = spawn_stub(app).await;
+
+        let dir = std::env::temp_dir().join(format!("ttb-cola-record-{}", uuid::Uuid::new_v4()));
+        let recorder = TtbColaClient::with_config(TtbColaConfig {
+            base_url: base_url.clone(),
+            mode: TtbColaMode::Record(dir.clone()),
+            ..TtbColaConfig::default()
+        })
+        .unwrap();
+        let recorded = recorder.search_by_brand("Stone Creek", None, 20).await.unwrap();
+        assert_eq!(recorded.len(), 3);
+        assert!(recorded[0].source_url.starts_with(&base_url));
+
+        let replayer = TtbColaClient::with_config(TtbColaConfig {
+            base_url,
+            mode: TtbColaMode::Replay(dir.clone()),
+            ..TtbColaConfig::default()
+        })
+        .unwrap();
+        let replayed = replayer.search_by_brand("STONE CREEK", None, 20).await.unwrap();
+        assert_eq!(replayed.len(), 3);
+        assert_eq!(replayed[1].ttb_id, recorded[1].ttb_id);
+
+        std::fs::remove_dir_all(dir).unwrap();
     }
 }
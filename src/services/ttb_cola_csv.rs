@@ -0,0 +1,296 @@
+//! Bulk import of TTB COLA search result CSV exports.
+//!
+//! COLA Online can save a search result set as CSV with the same columns as
+//! the results table (TTB ID, Permit No., Serial Number, Completed Date,
+//! Fanciful Name, Brand Name, Origin, Origin Desc, Class/Type, Class/Type
+//! Desc). `ColaCsvReader` maps each row to a `TtbColaRecord` the way
+//! `parse_search_results` maps a table row, and `import` streams the records
+//! into `known_beverages` through `upsert_batch_from_ttb_cola`, one
+//! transaction per batch, so large exports never need to fit in memory.
+
+use std::io::Read;
+
+use chrono::NaiveDate;
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::db::beverage_queries;
+use crate::services::ttb_cola::{self, TtbColaRecord};
+
+/// Records upserted per transaction.
+pub const DEFAULT_BATCH_SIZE: usize = 1000;
+
+/// Error type for COLA CSV imports.
+#[derive(Debug, thiserror::Error)]
+pub enum ColaCsvError {
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Missing required column '{0}'")]
+    MissingColumn(&'static str),
+
+    /// A row that can't be mapped to a record; `import` skips these.
+    #[error("Line {line}: {reason}")]
+    InvalidRow { line: u64, reason: String },
+
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Column positions, resolved from the header row.
+#[derive(Debug)]
+struct Columns {
+    ttb_id: usize,
+    permit_no: Option<usize>,
+    serial_number: Option<usize>,
+    completed_date: Option<usize>,
+    fanciful_name: Option<usize>,
+    brand_name: usize,
+    origin_code: Option<usize>,
+    origin_desc: Option<usize>,
+    class_type_code: Option<usize>,
+    class_type_desc: usize,
+}
+
+impl Columns {
+    /// Match headers ignoring case, spaces and punctuation ("Class/Type Desc"
+    /// and "class_type_desc" are the same column).
+    fn from_headers(headers: &csv::StringRecord) -> Result<Self, ColaCsvError> {
+        let normalized: Vec<String> = headers
+            .iter()
+            .map(|h| {
+                h.chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .collect::<String>()
+                    .to_ascii_lowercase()
+            })
+            .collect();
+        let find = |names: &[&str]| normalized.iter().position(|h| names.contains(&h.as_str()));
+        let require = |names: &[&str], column: &'static str| {
+            find(names).ok_or(ColaCsvError::MissingColumn(column))
+        };
+
+        Ok(Self {
+            ttb_id: require(&["ttbid"], "TTB ID")?,
+            permit_no: find(&["permitno", "permitnumber"]),
+            serial_number: find(&["serialnumber", "serialno"]),
+            completed_date: find(&["completeddate", "datecompleted"]),
+            fanciful_name: find(&["fancifulname"]),
+            brand_name: require(&["brandname"], "Brand Name")?,
+            origin_code: find(&["origin", "origincode"]),
+            origin_desc: find(&["origindesc", "origindescription"]),
+            class_type_code: find(&["classtype", "classtypecode"]),
+            class_type_desc: require(&["classtypedesc", "classtypedescription"], "Class/Type Desc")?,
+        })
+    }
+}
+
+/// Streaming reader of COLA CSV export rows as `TtbColaRecord`s.
+///
+/// Rows that can't be mapped yield `ColaCsvError::InvalidRow`; reading
+/// continues with the next row.
+pub struct ColaCsvReader<R> {
+    records: csv::StringRecordsIntoIter<R>,
+    columns: Columns,
+    base_url: String,
+}
+
+impl<R: Read> ColaCsvReader<R> {
+    /// Read the header row. `base_url` is the COLA Online base used for each
+    /// record's detail page URL.
+    pub fn new(reader: R, base_url: &str) -> Result<Self, ColaCsvError> {
+        let mut csv_reader = csv::ReaderBuilder::new().flexible(true).from_reader(reader);
+        let columns = Columns::from_headers(csv_reader.headers()?)?;
+
+        Ok(Self {
+            records: csv_reader.into_records(),
+            columns,
+            base_url: base_url.to_string(),
+        })
+    }
+
+    fn to_record(&self, row: &csv::StringRecord) -> Result<TtbColaRecord, String> {
+        let get = |i: usize| row.get(i).unwrap_or("").trim().to_string();
+        let get_opt = |i: Option<usize>| i.map(get).unwrap_or_default();
+
+        // Spreadsheet round trips prefix long numbers with ' to keep them as text
+        let ttb_id = get(self.columns.ttb_id).trim_start_matches('\'').to_string();
+        if ttb_id.is_empty() || !ttb_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(format!("invalid TTB ID '{}'", ttb_id));
+        }
+        let brand_name = get(self.columns.brand_name);
+        if brand_name.is_empty() {
+            return Err(format!("TTB ID {}: brand name is empty", ttb_id));
+        }
+        let class_type_desc = get(self.columns.class_type_desc);
+        if class_type_desc.is_empty() {
+            return Err(format!("TTB ID {}: class/type is empty", ttb_id));
+        }
+
+        let completed_date = get_opt(self.columns.completed_date);
+        let completed_date = NaiveDate::parse_from_str(&completed_date, "%m/%d/%Y")
+            .or_else(|_| NaiveDate::parse_from_str(&completed_date, "%Y-%m-%d"))
+            .ok();
+        let fanciful_name = Some(get_opt(self.columns.fanciful_name)).filter(|name| !name.is_empty());
+        let class_type_code = get_opt(self.columns.class_type_code);
+
+        Ok(TtbColaRecord {
+            source_url: ttb_cola::detail_url(&self.base_url, &ttb_id),
+            permit_no: get_opt(self.columns.permit_no),
+            serial_number: get_opt(self.columns.serial_number),
+            completed_date,
+            fanciful_name,
+            brand_name,
+            origin_code: get_opt(self.columns.origin_code),
+            origin_desc: get_opt(self.columns.origin_desc),
+            inferred_abv: ttb_cola::infer_abv_from_class_type(&class_type_desc),
+            beverage_category: ttb_cola::get_category_from_class_type(&class_type_desc, &class_type_code),
+            class_type_code,
+            class_type_desc,
+            ttb_id,
+            detail: None,
+        })
+    }
+}
+
+impl<R: Read> Iterator for ColaCsvReader<R> {
+    type Item = Result<TtbColaRecord, ColaCsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let row = match self.records.next()? {
+                Ok(row) => row,
+                // A row that isn't valid UTF-8 is skippable; I/O errors are not
+                Err(e) if matches!(e.kind(), csv::ErrorKind::Utf8 { .. }) => {
+                    let line = e.position().map(|p| p.line()).unwrap_or_default();
+                    return Some(Err(ColaCsvError::InvalidRow { line, reason: e.to_string() }));
+                }
+                Err(e) => return Some(Err(e.into())),
+            };
+
+            // Rows of empty cells, e.g. trailing spreadsheet rows
+            if row.iter().all(|field| field.trim().is_empty()) {
+                continue;
+            }
+
+            let line = row.position().map(|p| p.line()).unwrap_or_default();
+            return Some(self.to_record(&row).map_err(|reason| ColaCsvError::InvalidRow { line, reason }));
+        }
+    }
+}
+
+/// Row counts for a COLA CSV import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ColaCsvImportSummary {
+    pub inserted: usize,
+    pub updated: usize,
+    /// Invalid rows plus records that failed to upsert.
+    pub skipped: usize,
+    /// Records stored without a cached beverage (no ABV to infer).
+    pub uncached: usize,
+}
+
+/// Upsert every record from `reader`, `batch_size` records per transaction.
+///
+/// Invalid rows are logged and skipped; CSV I/O and database errors abort the
+/// import (batches already committed stay committed).
+pub async fn import<R: Read>(
+    pool: &PgPool,
+    reader: ColaCsvReader<R>,
+    batch_size: usize,
+) -> Result<ColaCsvImportSummary, ColaCsvError> {
+    let batch_size = batch_size.max(1);
+    let mut summary = ColaCsvImportSummary::default();
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut reader = reader.peekable();
+
+    while let Some(record) = reader.next() {
+        match record {
+            Ok(record) => batch.push(record),
+            Err(ColaCsvError::InvalidRow { line, reason }) => {
+                warn!(line, reason = %reason, "Skipping invalid COLA CSV row");
+                summary.skipped += 1;
+            }
+            Err(e) => return Err(e),
+        }
+
+        if batch.len() >= batch_size || (reader.peek().is_none() && !batch.is_empty()) {
+            let result = beverage_queries::upsert_batch_from_ttb_cola(pool, &batch).await?;
+            summary.inserted += result.inserted;
+            summary.updated += result.updated;
+            summary.skipped += result.skipped;
+            summary.uncached += result.uncached;
+            batch.clear();
+
+            info!(
+                inserted = summary.inserted,
+                updated = summary.updated,
+                skipped = summary.skipped,
+                uncached = summary.uncached,
+                "COLA CSV import progress"
+            );
+        }
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = "\
+TTB ID,Permit No.,Serial Number,Completed Date,Fanciful Name,Brand Name,Origin,Origin Desc,Class/Type,Class/Type Desc
+'23001001000123,CA-I-1234,230001,01/15/2023,,STONE CREEK,06,CALIFORNIA,80,TABLE RED WINE
+23001001000456,CA-I-1234,230002,02/01/2023,\"RESERVE, NAPA\",STONE CREEK,06,CALIFORNIA,80,TABLE RED WINE
+,CA-I-1234,230003,02/01/2023,,STONE CREEK,06,CALIFORNIA,80,TABLE RED WINE
+23001001000789,KY-DSP-15,230004,2023-03-01,,OLD BARREL,21,KENTUCKY,101,STRAIGHT BOURBON WHISKY
+,,,,,,,,,
+";
+
+    #[test]
+    fn test_reads_export_rows() {
+        let results: Vec<_> = ColaCsvReader::new(EXPORT.as_bytes(), ttb_cola::DEFAULT_BASE_URL)
+            .unwrap()
+            .collect();
+        assert_eq!(results.len(), 4);
+
+        let first = results[0].as_ref().unwrap();
+        assert_eq!(first.ttb_id, "23001001000123");
+        assert_eq!(first.completed_date, NaiveDate::from_ymd_opt(2023, 1, 15));
+        assert_eq!(first.fanciful_name, None);
+        assert_eq!(first.beverage_category, "wine");
+        assert_eq!(
+            first.source_url,
+            "https://ttbonline.gov/colasonline/viewColaDetails.do?action=publicDisplaySearchBasic&ttbid=23001001000123"
+        );
+
+        assert_eq!(results[1].as_ref().unwrap().fanciful_name.as_deref(), Some("RESERVE, NAPA"));
+        assert!(matches!(results[2], Err(ColaCsvError::InvalidRow { line: 4, .. })));
+
+        let bourbon = results[3].as_ref().unwrap();
+        assert_eq!(bourbon.completed_date, NaiveDate::from_ymd_opt(2023, 3, 1));
+        assert_eq!(bourbon.beverage_category, "distilled_spirits");
+    }
+
+    #[test]
+    fn test_header_variants() {
+        let csv = "ttb_id,brand_name,class_type_desc\n23001001000123,STONE CREEK,TABLE RED WINE\n";
+        let record = ColaCsvReader::new(csv.as_bytes(), "http://127.0.0.1:8089/colasonline/")
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.permit_no, "");
+        assert!(record.source_url.starts_with("http://127.0.0.1:8089/colasonline/viewColaDetails.do"));
+    }
+
+    #[test]
+    fn test_missing_required_column() {
+        let csv = "TTB ID,Brand Name\n23001001000123,STONE CREEK\n";
+        assert!(matches!(
+            ColaCsvReader::new(csv.as_bytes(), ttb_cola::DEFAULT_BASE_URL),
+            Err(ColaCsvError::MissingColumn("Class/Type Desc"))
+        ));
+    }
+}
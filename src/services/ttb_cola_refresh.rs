@@ -0,0 +1,240 @@
+//! Background refresh of TTB COLA rows in the `known_beverages` cache.
+//!
+//! The worker runs `run_scheduler`, which periodically claims a batch of
+//! stale `source = 'ttb_cola'` rows, re-fetches each COLA by TTB ID through
+//! the shared (rate-limited) TTB COLA client, and records the outcome on the
+//! row: `refreshed` (updated from TTB), `retired` (TTB no longer has the COLA)
+//! or `failed` (retried after `retry_after_hours`).
+
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tracing::{info, warn};
+
+use crate::db::beverage_queries;
+use crate::models::beverage::{KnownBeverage, TtbColaRefreshOutcome};
+use crate::services::ttb_cola::{self, TtbColaClient};
+use crate::services::validation;
+
+/// Refresh scheduler settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RefreshSettings {
+    /// Time between refresh runs.
+    pub interval: Duration,
+    /// Rows claimed per run.
+    pub batch_size: i64,
+    /// Rows last verified more than this many days ago are refreshed.
+    pub stale_after_days: i64,
+    /// Minimum time before a row is attempted again after a failure.
+    pub retry_after_hours: i64,
+}
+
+impl Default for RefreshSettings {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(3600),
+            batch_size: 50,
+            stale_after_days: validation::CACHE_STALENESS_THRESHOLD_DAYS,
+            retry_after_hours: 6,
+        }
+    }
+}
+
+/// Row counts for one refresh run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RefreshSummary {
+    pub refreshed: usize,
+    pub retired: usize,
+    pub failed: usize,
+}
+
+impl RefreshSummary {
+    pub fn total(&self) -> usize {
+        self.refreshed + self.retired + self.failed
+    }
+
+    fn add(&mut self, outcome: TtbColaRefreshOutcome) {
+        match outcome {
+            TtbColaRefreshOutcome::Refreshed => self.refreshed += 1,
+            TtbColaRefreshOutcome::Retired => self.retired += 1,
+            TtbColaRefreshOutcome::Failed => self.failed += 1,
+        }
+    }
+}
+
+/// Register descriptions for the refresh metrics.
+pub fn describe_metrics() {
+    metrics::describe_counter!(
+        "ttb_cola_refresh_rows_total",
+        "Cached TTB COLA rows refreshed by the worker, by outcome (refreshed, retired, failed)"
+    );
+    metrics::describe_histogram!("ttb_cola_refresh_run_seconds", "Duration of a TTB COLA cache refresh run");
+}
+
+/// Run refresh batches forever, `settings.interval` apart.
+///
+/// Errors are logged; the next run retries.
+pub async fn run_scheduler(pool: PgPool, settings: RefreshSettings) {
+    info!(
+        interval_secs = settings.interval.as_secs(),
+        batch_size = settings.batch_size,
+        stale_after_days = settings.stale_after_days,
+        "Starting TTB COLA cache refresh scheduler"
+    );
+
+    let mut ticker = tokio::time::interval(settings.interval);
+    ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        let client = match ttb_cola::get_client() {
+            Ok(client) => client,
+            Err(e) => {
+                warn!(error = %e, "TTB COLA client unavailable, skipping cache refresh");
+                continue;
+            }
+        };
+
+        let started = std::time::Instant::now();
+        match refresh_stale_batch(&pool, client, &settings).await {
+            Ok(summary) if summary.total() > 0 => info!(
+                refreshed = summary.refreshed,
+                retired = summary.retired,
+                failed = summary.failed,
+                "TTB COLA cache refresh run complete"
+            ),
+            Ok(_) => tracing::debug!("No stale TTB COLA rows to refresh"),
+            Err(e) => warn!(error = %e, "TTB COLA cache refresh run failed"),
+        }
+        metrics::histogram!("ttb_cola_refresh_run_seconds").record(started.elapsed().as_secs_f64());
+    }
+}
+
+/// Claim one batch of stale rows and refresh each from TTB.
+pub async fn refresh_stale_batch(
+    pool: &PgPool,
+    client: &TtbColaClient,
+    settings: &RefreshSettings,
+) -> Result<RefreshSummary, sqlx::Error> {
+    let rows = beverage_queries::claim_stale_ttb_cola_beverages(
+        pool,
+        settings.stale_after_days,
+        settings.retry_after_hours,
+        settings.batch_size,
+    )
+    .await?;
+
+    let mut summary = RefreshSummary::default();
+    for row in &rows {
+        let outcome = refresh_row(pool, client, row).await?;
+        metrics::counter!("ttb_cola_refresh_rows_total", "outcome" => outcome.to_string()).increment(1);
+        summary.add(outcome);
+    }
+
+    Ok(summary)
+}
+
+/// Re-fetch one row's COLA and record the outcome.
+async fn refresh_row(
+    pool: &PgPool,
+    client: &TtbColaClient,
+    row: &KnownBeverage,
+) -> Result<TtbColaRefreshOutcome, sqlx::Error> {
+    let Some(ttb_id) = ttb_id_of(row) else {
+        warn!(id = %row.id, brand = %row.brand_name, "Cached TTB COLA row has no TTB ID, cannot refresh");
+        beverage_queries::record_ttb_cola_refresh_outcome(
+            pool,
+            row.id,
+            TtbColaRefreshOutcome::Failed,
+            Some("No TTB ID in source_url or notes"),
+        )
+        .await?;
+        return Ok(TtbColaRefreshOutcome::Failed);
+    };
+
+    let (outcome, error) = match client.fetch_by_ttb_id(&ttb_id).await {
+        Ok(Some(record)) => match beverage_queries::refresh_ttb_cola_beverage(pool, row.id, &record).await {
+            Ok(()) => (TtbColaRefreshOutcome::Refreshed, None),
+            // e.g. another row is already cached from this COLA
+            Err(e) => (TtbColaRefreshOutcome::Failed, Some(format!("Update failed: {}", e))),
+        },
+        // Only TTB's "not found" page; unreadable pages are parse errors and retried
+        Ok(None) => (TtbColaRefreshOutcome::Retired, None),
+        Err(e) => (TtbColaRefreshOutcome::Failed, Some(e.to_string())),
+    };
+
+    if outcome != TtbColaRefreshOutcome::Refreshed {
+        beverage_queries::record_ttb_cola_refresh_outcome(pool, row.id, outcome, error.as_deref()).await?;
+    }
+
+    info!(
+        id = %row.id,
+        brand = %row.brand_name,
+        ttb_id = %ttb_id,
+        outcome = %outcome,
+        error = error.as_deref().unwrap_or(""),
+        "Refreshed cached TTB COLA row"
+    );
+
+    Ok(outcome)
+}
+
+/// TTB ID of a cached COLA row: the `ttbid` parameter of its detail URL, or
+/// the "TTB COLA ID: ..." prefix of its notes.
+fn ttb_id_of(row: &KnownBeverage) -> Option<String> {
+    let from_url = row.source_url.as_deref().and_then(|url| {
+        url.split(['?', '&'])
+            .find_map(|param| param.strip_prefix("ttbid="))
+            .map(str::to_string)
+    });
+
+    let from_notes = || {
+        row.notes.as_deref().and_then(|notes| {
+            notes
+                .strip_prefix("TTB COLA ID: ")
+                .map(|rest| rest.chars().take_while(|c| c.is_ascii_digit()).collect::<String>())
+        })
+    };
+
+    from_url
+        .or_else(from_notes)
+        .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cached_row(source_url: Option<&str>, notes: Option<&str>) -> KnownBeverage {
+        KnownBeverage {
+            source_url: source_url.map(str::to_string),
+            notes: notes.map(str::to_string),
+            ..KnownBeverage::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_ttb_id_from_source_url() {
+        let row = cached_row(
+            Some("https://ttbonline.gov/colasonline/viewColaDetails.do?action=publicDisplaySearchBasic&ttbid=23001001000123"),
+            None,
+        );
+        assert_eq!(ttb_id_of(&row).as_deref(), Some("23001001000123"));
+    }
+
+    #[test]
+    fn test_ttb_id_from_notes() {
+        let row = cached_row(
+            Some("https://example.com/product/123"),
+            Some("TTB COLA ID: 23001001000456, Permit: CA-I-1234, Origin: CALIFORNIA (06)"),
+        );
+        assert_eq!(ttb_id_of(&row).as_deref(), Some("23001001000456"));
+    }
+
+    #[test]
+    fn test_ttb_id_missing() {
+        assert_eq!(ttb_id_of(&cached_row(None, Some("Imported from Total Wine"))), None);
+        assert_eq!(ttb_id_of(&cached_row(Some("viewColaDetails.do?ttbid="), None)), None);
+    }
+}
@@ -6,7 +6,7 @@
 use strsim::jaro_winkler;
 
 /// Minimum similarity score for a class/type to be considered a valid match.
-const CLASS_MATCH_THRESHOLD: f64 = 0.88;
+pub const CLASS_MATCH_THRESHOLD: f64 = 0.88;
 
 // ── Distilled Spirits (27 CFR 5.22) ─────────────────────────────────────
 
@@ -82,6 +82,7 @@ pub const DISTILLED_SPIRITS_TYPES: &[&str] = &[
 
 /// Standard wine types recognized by the TTB.
 pub const WINE_TYPES: &[&str] = &[
+    "Wine",
     "Grape Wine",
     "Table Wine",
     "Red Wine",
@@ -223,6 +224,11 @@ pub struct ClassificationResult {
 
 /// Validate a class/type designation against TTB standards of identity.
 pub fn validate_classification(class_type: &str) -> ClassificationResult {
+    validate_classification_with_threshold(class_type, CLASS_MATCH_THRESHOLD)
+}
+
+/// Validate a class/type designation, accepting standards at or above `threshold`.
+pub fn validate_classification_with_threshold(class_type: &str, threshold: f64) -> ClassificationResult {
     let input = class_type.trim().to_string();
     let lower = input.to_lowercase();
 
@@ -244,7 +250,7 @@ pub fn validate_classification(class_type: &str) -> ClassificationResult {
     // Try matching against all categories
     let (best_match, best_score, category) = find_best_match(&match_term);
 
-    let is_valid = best_score >= CLASS_MATCH_THRESHOLD;
+    let is_valid = best_score >= threshold;
 
     // If no good match found and input looks like a fanciful name
     let requires_composition_statement = !is_valid && !lower.is_empty() && !is_flavored;
@@ -262,13 +268,62 @@ pub fn validate_classification(class_type: &str) -> ClassificationResult {
 }
 
 /// Check if the input matches a known misspelling.
+///
+/// Matches the whole designation first, then individual words so that
+/// "Burbon Whiskey" is corrected to "Bourbon Whiskey". Words that are
+/// themselves standard terms (e.g. "Whisky") are never treated as misspellings.
 fn check_misspelling(input: &str) -> Option<String> {
-    for (misspelling, correction) in COMMON_MISSPELLINGS {
-        if input == *misspelling || jaro_winkler(input, misspelling) > 0.95 {
-            return Some(correction.to_string());
-        }
+    if let Some(correction) = lookup_misspelling(input) {
+        return Some(correction.to_string());
+    }
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+    if words.len() < 2 {
+        return None;
+    }
+
+    let mut corrected = false;
+    let fixed: Vec<String> = words
+        .iter()
+        .map(|word| match lookup_misspelling(word) {
+            Some(correction) if !is_standard_term(word) => {
+                corrected = true;
+                correction.to_string()
+            }
+            _ => title_case(word),
+        })
+        .collect();
+
+    if corrected {
+        Some(fixed.join(" "))
+    } else {
+        None
+    }
+}
+
+/// Look up a single term in the misspelling table.
+fn lookup_misspelling(term: &str) -> Option<&'static str> {
+    COMMON_MISSPELLINGS
+        .iter()
+        .find(|(misspelling, _)| term == *misspelling || jaro_winkler(term, misspelling) > 0.95)
+        .map(|(_, correction)| *correction)
+}
+
+/// Whether a single word is itself a standard TTB term.
+fn is_standard_term(word: &str) -> bool {
+    DISTILLED_SPIRITS_TYPES
+        .iter()
+        .chain(WINE_TYPES)
+        .chain(MALT_BEVERAGE_TYPES)
+        .any(|standard| standard.eq_ignore_ascii_case(word))
+}
+
+fn title_case(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
     }
-    None
 }
 
 /// Detect flavored designation patterns.
@@ -318,6 +373,182 @@ fn find_best_match(input: &str) -> (String, f64, String) {
     (best_match, best_score, best_category)
 }
 
+// ── Name and Address Statements (27 CFR 4.35, 5.66, 7.66) ───────────────
+
+/// Phrases that introduce the bottler, packer, or importer name and address.
+///
+/// Longer phrases come first so "Distilled and bottled by" wins over "Bottled by".
+pub const NAME_ADDRESS_PHRASES: &[&str] = &[
+    "Distilled and bottled by",
+    "Produced and bottled by",
+    "Blended and bottled by",
+    "Vinted and bottled by",
+    "Cellared and bottled by",
+    "Brewed and bottled by",
+    "Brewed and canned by",
+    "Imported by",
+    "Distilled by",
+    "Produced by",
+    "Blended by",
+    "Brewed by",
+    "Bottled by",
+    "Packed by",
+    "Canned by",
+    "Made by",
+];
+
+/// A parsed bottler/packer/importer name and address statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameAddressStatement {
+    /// The introductory phrase as it appears in the canonical list (e.g. "Bottled by").
+    pub phrase: String,
+    /// Whether the statement identifies an importer ("Imported by").
+    pub is_importer: bool,
+    /// Name of the bottler, packer, or importer.
+    pub name: String,
+    /// Address portion following the name (city/state or country), if present.
+    pub address: Option<String>,
+}
+
+/// Parse a name and address statement such as
+/// "Bottled by Stone Creek Vineyards, Napa, CA".
+///
+/// Returns `None` if no recognized phrase is found or no name follows it.
+pub fn parse_name_address_statement(statement: &str) -> Option<NameAddressStatement> {
+    // ASCII lowercasing keeps byte offsets aligned with the original text
+    let lower = statement.to_ascii_lowercase();
+
+    let (start, phrase) = NAME_ADDRESS_PHRASES
+        .iter()
+        .filter_map(|phrase| lower.find(&phrase.to_ascii_lowercase()).map(|idx| (idx, *phrase)))
+        .min_by_key(|(idx, phrase)| (*idx, std::cmp::Reverse(phrase.len())))?;
+
+    let rest = statement[start + phrase.len()..]
+        .trim_start_matches(|c: char| c == ':' || c.is_whitespace());
+
+    let (name, address) = match rest.split_once(',') {
+        Some((name, address)) => (name.trim(), Some(address.trim())),
+        None => (rest.trim(), None),
+    };
+
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(NameAddressStatement {
+        phrase: phrase.to_string(),
+        is_importer: phrase.eq_ignore_ascii_case("Imported by"),
+        name: name.to_string(),
+        address: address.filter(|a| !a.is_empty()).map(str::to_string),
+    })
+}
+
+// ── Allergen and Additive Disclosures (27 CFR 4.32) ─────────────────────
+
+/// An additive or allergen that must be disclosed with specific wording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisclosureRule {
+    /// Short identifier used in field names (e.g. "sulfites").
+    pub code: &'static str,
+    /// Keywords indicating that a statement concerns this additive.
+    pub keywords: &'static [&'static str],
+    /// Accepted wording; a statement containing any of these is well formed.
+    pub accepted_wording: &'static [&'static str],
+    /// Wording shown to reviewers when a statement is missing or malformed.
+    pub required_wording: &'static str,
+    /// Regulation requiring the disclosure.
+    pub cfr_reference: &'static str,
+}
+
+/// Disclosure rules for sulfites, FD&C Yellow No. 5, cochineal/carmine and aspartame.
+pub const DISCLOSURE_RULES: &[DisclosureRule] = &[
+    DisclosureRule {
+        code: "sulfites",
+        keywords: &["sulfit", "sulphit"],
+        accepted_wording: &[
+            "contains sulfites",
+            "contains sulphites",
+            "contains sulfiting agent",
+            "contains a sulfiting agent",
+        ],
+        required_wording: "Contains Sulfites",
+        cfr_reference: "27 CFR 4.32(e)",
+    },
+    DisclosureRule {
+        code: "fdc_yellow_5",
+        keywords: &["yellow no. 5", "yellow no 5", "yellow #5", "yellow 5", "tartrazine"],
+        accepted_wording: &["fd&c yellow no. 5", "fd&c yellow no 5", "fd&c yellow #5"],
+        required_wording: "FD&C Yellow No. 5",
+        cfr_reference: "27 CFR 4.32(c)",
+    },
+    DisclosureRule {
+        code: "cochineal_carmine",
+        keywords: &["cochineal", "carmine"],
+        accepted_wording: &["contains cochineal extract", "contains carmine"],
+        required_wording: "Contains Cochineal Extract (or Contains Carmine)",
+        cfr_reference: "27 CFR 4.32(d)",
+    },
+    DisclosureRule {
+        code: "aspartame",
+        keywords: &["aspartame", "phenylalanine", "phenylketonurics"],
+        accepted_wording: &["phenylketonurics contains phenylalanine"],
+        required_wording: "PHENYLKETONURICS: CONTAINS PHENYLALANINE",
+        cfr_reference: "21 CFR 172.804",
+    },
+];
+
+/// Words before or after an additive that claim its absence ("No sulfites
+/// added", "sulfite-free") rather than disclose it.
+const ABSENCE_PREFIXES: &[&str] = &["no ", "no added ", "without ", "without added ", "free of "];
+const ABSENCE_SUFFIXES: &[&str] = &["-free", " free"];
+
+/// Classify a disclosure statement against the known disclosure rules.
+///
+/// Returns each rule the statement refers to, paired with whether its wording
+/// is acceptable. A single statement may disclose several additives. A
+/// statement that only claims an additive's absence doesn't refer to its rule.
+pub fn classify_disclosure(statement: &str) -> Vec<(&'static DisclosureRule, bool)> {
+    let normalized = normalize_disclosure(statement);
+
+    DISCLOSURE_RULES
+        .iter()
+        .filter(|rule| {
+            rule.keywords
+                .iter()
+                .any(|kw| normalized.contains(kw) && !is_absence_claim(&normalized, kw))
+        })
+        .map(|rule| {
+            let well_formed = rule
+                .accepted_wording
+                .iter()
+                .any(|wording| normalized.contains(wording));
+            (rule, well_formed)
+        })
+        .collect()
+}
+
+/// Whether every mention of `keyword` in `normalized` is negated.
+fn is_absence_claim(normalized: &str, keyword: &str) -> bool {
+    normalized.match_indices(keyword).all(|(start, _)| {
+        let before = &normalized[..start];
+        let rest = &normalized[start + keyword.len()..];
+        let after = rest.trim_start_matches(|c: char| c.is_alphanumeric());
+        ABSENCE_PREFIXES.iter().any(|p| before.ends_with(p))
+            || ABSENCE_SUFFIXES.iter().any(|s| after.starts_with(s))
+    })
+}
+
+/// Lowercase, drop colons and collapse whitespace so OCR spacing differences
+/// don't make an otherwise correct statement look malformed.
+fn normalize_disclosure(statement: &str) -> String {
+    statement
+        .to_lowercase()
+        .replace(':', " ")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Standard net contents sizes for TTB-regulated beverages (in mL).
 pub const STANDARD_SIZES_ML: &[f64] = &[
     50.0, 100.0, 200.0, 375.0, 500.0, 750.0, 1000.0, 1750.0,
@@ -392,7 +623,15 @@ mod tests {
     #[test]
     fn test_misspelling_detection() {
         let result = validate_classification("Burbon Whiskey");
-        assert!(result.spelling_correction.is_some());
+        assert_eq!(result.spelling_correction.as_deref(), Some("Bourbon Whiskey"));
+        assert!(result.is_valid);
+    }
+
+    #[test]
+    fn test_standard_word_not_flagged_as_misspelling() {
+        let result = validate_classification("Scotch Whisky");
+        assert!(result.spelling_correction.is_none());
+        assert!(result.is_valid);
     }
 
     #[test]
@@ -430,6 +669,75 @@ mod tests {
         assert_eq!(unit.as_deref(), Some("mL"));
     }
 
+    #[test]
+    fn test_parse_bottled_by_statement() {
+        let stmt = parse_name_address_statement("Bottled by Stone Creek Vineyards, Napa, CA").unwrap();
+        assert_eq!(stmt.phrase, "Bottled by");
+        assert!(!stmt.is_importer);
+        assert_eq!(stmt.name, "Stone Creek Vineyards");
+        assert_eq!(stmt.address.as_deref(), Some("Napa, CA"));
+    }
+
+    #[test]
+    fn test_parse_prefers_longest_phrase() {
+        let stmt =
+            parse_name_address_statement("DISTILLED AND BOTTLED BY Jim Beam Brands Co., Clermont, KY")
+                .unwrap();
+        assert_eq!(stmt.phrase, "Distilled and bottled by");
+        assert_eq!(stmt.name, "Jim Beam Brands Co.");
+    }
+
+    #[test]
+    fn test_parse_imported_by_statement() {
+        let stmt = parse_name_address_statement("Imported by: Diageo North America, New York, NY").unwrap();
+        assert!(stmt.is_importer);
+        assert_eq!(stmt.name, "Diageo North America");
+    }
+
+    #[test]
+    fn test_parse_statement_without_phrase() {
+        assert!(parse_name_address_statement("Stone Creek Vineyards, Napa, CA").is_none());
+        assert!(parse_name_address_statement("Bottled by").is_none());
+    }
+
+    #[test]
+    fn test_disclosure_well_formed() {
+        let found = classify_disclosure("CONTAINS SULFITES");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0.code, "sulfites");
+        assert!(found[0].1);
+
+        let found = classify_disclosure("Phenylketonurics: Contains Phenylalanine");
+        assert_eq!(found[0].0.code, "aspartame");
+        assert!(found[0].1);
+    }
+
+    #[test]
+    fn test_disclosure_malformed() {
+        let found = classify_disclosure("Colored with Yellow 5");
+        assert_eq!(found[0].0.code, "fdc_yellow_5");
+        assert!(!found[0].1);
+
+        let found = classify_disclosure("Sulfites added");
+        assert!(!found[0].1);
+    }
+
+    #[test]
+    fn test_disclosure_absence_claims_ignored() {
+        assert!(classify_disclosure("No sulfites added").is_empty());
+        assert!(classify_disclosure("Contains no added sulfites").is_empty());
+        assert!(classify_disclosure("Sulfite-free").is_empty());
+        assert_eq!(classify_disclosure("Contains sulfites. No sulfites added by the winery").len(), 1);
+    }
+
+    #[test]
+    fn test_disclosure_multiple_additives() {
+        let found = classify_disclosure("Contains sulfites and FD&C Yellow No. 5");
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|(_, ok)| *ok));
+        assert!(classify_disclosure("Product of France").is_empty());
+    }
+
     #[test]
     fn test_net_contents_liters() {
         let (valid, value, unit) = validate_net_contents("1.75 L");
@@ -3,18 +3,42 @@ use strsim::jaro_winkler;
 use tracing::{info, warn};
 
 use crate::db::beverage_queries;
-use crate::models::label::{ExtractedLabelFields, FieldVerification, VerificationResult};
+use crate::models::beverage::{AbvSource, ColaStatus, KnownBeverage, SimilarBeverage};
+use crate::models::label::{
+    CandidateSource, ExtractedLabelFields, FieldVerification, MatchCandidate, MatchComponents,
+    MatchType, Severity, VerificationResult,
+};
+use crate::services::matching;
+use crate::services::origin;
+use crate::services::profile::ValidationProfile;
+use crate::services::rules;
+use crate::services::scoring;
 use crate::services::ttb_cola::{self, TtbColaRecord};
 use crate::services::ttb_standards;
 
-/// Threshold for fuzzy string matching (0.0 - 1.0).
-const MATCH_THRESHOLD: f64 = 0.85;
+/// Minimum pg_trgm similarity for a fuzzy brand candidate.
+const TRIGRAM_MIN_SIMILARITY: f64 = 0.3;
 
-/// TTB-mandated ABV tolerance: ±0.3 percentage points.
-const ABV_TOLERANCE: f64 = 0.3;
+/// Maximum number of fuzzy brand candidates fetched from the local cache.
+const FUZZY_CANDIDATE_LIMIT: i64 = 10;
+
+/// Candidates kept per source (local cache, TTB COLA) before ranking.
+const REPORTED_CANDIDATES_PER_SOURCE: usize = 5;
+
+/// Candidates reported in the result, across sources.
+const REPORTED_CANDIDATES: usize = 5;
 
 /// Cache staleness threshold in days (30 days).
-const CACHE_STALENESS_THRESHOLD_DAYS: i64 = 30;
+pub const CACHE_STALENESS_THRESHOLD_DAYS: i64 = 30;
+
+/// How long a TTB COLA brand search miss is trusted before TTB is queried again.
+pub const TTB_COLA_MISS_TTL_DAYS: i64 = 7;
+
+/// Category key for negative cache entries of category-less brand searches.
+const ANY_CATEGORY: &str = "";
+
+/// Most COLA detail pages fetched per lookup while looking for an active match.
+const MAX_DETAIL_FETCHES: usize = 3;
 
 /// Validate extracted label fields against expected values and TTB rules.
 ///
@@ -23,25 +47,33 @@ const CACHE_STALENESS_THRESHOLD_DAYS: i64 = 30;
 /// - Class/type validation against TTB standards of identity
 /// - ABV tolerance checking (±0.3% per 27 CFR)
 /// - Net contents format validation
+/// - Bottler/importer name and address statement (importers require country of origin)
+/// - Country of origin normalization (ISO 3166) and geographic designation checks
+/// - Allergen/additive disclosure wording and wine sulfite declaration
 /// - Same field-of-vision checks (brand, class/type, ABV must appear together)
 /// - Mandatory field presence verification
+///
+/// Thresholds and tolerances come from `profile`; its name is recorded in the result.
 pub fn verify_label(
     extracted: &ExtractedLabelFields,
     expected_brand: Option<&str>,
     expected_class: Option<&str>,
     expected_abv: Option<f64>,
+    profile: &ValidationProfile,
 ) -> VerificationResult {
     let mut field_results = Vec::new();
 
     // ── Brand Name Verification (fuzzy match) ────────────────────────
     if let Some(expected) = expected_brand {
-        let score = jaro_winkler(&extracted.brand_name.to_lowercase(), &expected.to_lowercase());
+        let components = matching::score_names(&extracted.brand_name, expected);
         field_results.push(FieldVerification {
             field_name: "brand_name".to_string(),
             expected: Some(expected.to_string()),
             extracted: extracted.brand_name.clone(),
-            matches: score >= MATCH_THRESHOLD,
-            similarity_score: score,
+            matches: components.score >= profile.brand_threshold,
+            similarity_score: components.score,
+            match_components: Some(components),
+            ..rules::BRAND_EXPECTED.base()
         });
     }
 
@@ -53,14 +85,18 @@ pub fn verify_label(
             field_name: "class_type".to_string(),
             expected: Some(expected.to_string()),
             extracted: extracted.class_type.clone(),
-            matches: score >= MATCH_THRESHOLD,
+            matches: score >= profile.class_threshold,
             similarity_score: score,
+            ..rules::CLASS_EXPECTED.base()
         });
     }
 
     // Second: validate against TTB standards of identity (27 CFR)
     if !extracted.class_type.is_empty() {
-        let classification = ttb_standards::validate_classification(&extracted.class_type);
+        let classification = ttb_standards::validate_classification_with_threshold(
+            &extracted.class_type,
+            profile.class_standard_threshold,
+        );
 
         field_results.push(FieldVerification {
             field_name: "class_type_ttb_valid".to_string(),
@@ -71,6 +107,7 @@ pub fn verify_label(
             extracted: extracted.class_type.clone(),
             matches: classification.is_valid,
             similarity_score: classification.similarity,
+            ..rules::CLASS_TTB_STANDARD.base()
         });
 
         // Flag if spelling correction detected
@@ -81,6 +118,7 @@ pub fn verify_label(
                 extracted: extracted.class_type.clone(),
                 matches: false, // Misspelling is a mismatch
                 similarity_score: classification.similarity,
+                ..rules::CLASS_SPELLING.base()
             });
         }
 
@@ -92,14 +130,15 @@ pub fn verify_label(
                 extracted: extracted.class_type.clone(),
                 matches: false, // Flagged for review
                 similarity_score: 0.0,
+                ..rules::CLASS_COMPOSITION_STATEMENT.base()
             });
         }
     }
 
-    // ── ABV Verification (±0.3% tolerance per 27 CFR) ────────────────
+    // ── ABV Verification (±0.3% tolerance per 27 CFR by default) ─────
     if let Some(expected) = expected_abv {
         let diff = (extracted.abv - expected).abs();
-        let within_tolerance = diff <= ABV_TOLERANCE;
+        let within_tolerance = diff <= profile.abv_tolerance;
         let score = if within_tolerance {
             1.0
         } else {
@@ -111,6 +150,7 @@ pub fn verify_label(
             extracted: format!("{:.1}%", extracted.abv),
             matches: within_tolerance,
             similarity_score: score,
+            ..rules::ABV_EXPECTED.base()
         });
     }
 
@@ -128,9 +168,89 @@ pub fn verify_label(
             extracted: detail,
             matches: is_valid,
             similarity_score: if is_valid { 1.0 } else { 0.0 },
+            ..rules::NET_CONTENTS_FORMAT.base()
         });
     }
 
+    // ── Name and Address Statement (27 CFR 4.35, 5.66, 7.66) ─────────
+    // Every label must identify the bottler, packer, or importer.
+    let statement = extracted
+        .name_address_statement
+        .as_deref()
+        .and_then(ttb_standards::parse_name_address_statement);
+
+    field_results.push(FieldVerification {
+        field_name: "name_address_statement".to_string(),
+        expected: Some("\"Bottled by\", \"Imported by\", etc. with name and address".to_string()),
+        extracted: extracted.name_address_statement.clone().unwrap_or_default(),
+        matches: statement.is_some(),
+        similarity_score: if statement.is_some() { 1.0 } else { 0.0 },
+        ..rules::NAME_ADDRESS_STATEMENT.base()
+    });
+
+    // Imported products must also declare their country of origin
+    if statement.as_ref().is_some_and(|s| s.is_importer) {
+        let country = extracted
+            .country_of_origin
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or_default();
+        field_results.push(FieldVerification {
+            field_name: "country_of_origin_importer".to_string(),
+            expected: Some("Country of origin required for imported products".to_string()),
+            extracted: country.to_string(),
+            matches: !country.is_empty(),
+            similarity_score: if country.is_empty() { 0.0 } else { 1.0 },
+            ..rules::ORIGIN_IMPORTER_COUNTRY.base()
+        });
+    }
+
+    // ── Country of Origin (27 CFR 5.69, 19 CFR 134) ──────────────────
+    let country = extracted
+        .country_of_origin
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+    let country_code = country.and_then(origin::normalize_country);
+
+    if let Some(country) = country {
+        field_results.push(FieldVerification {
+            field_name: "country_of_origin_recognized".to_string(),
+            expected: Some("Recognized country (ISO 3166)".to_string()),
+            extracted: match country_code {
+                Some(code) => format!("{} ({})", country, code),
+                None => country.to_string(),
+            },
+            matches: country_code.is_some(),
+            similarity_score: if country_code.is_some() { 1.0 } else { 0.0 },
+            ..rules::ORIGIN_RECOGNIZED.base()
+        });
+    }
+
+    // Geographic designations must match the country of origin.
+    // Domestic designations (Bourbon, Tennessee Whiskey) need no origin statement.
+    if let Some(designation) = origin::find_geographic_designation(&extracted.class_type) {
+        let matches = match country_code {
+            Some(code) => code == designation.required_country,
+            None => country.is_none() && designation.required_country == origin::US,
+        };
+        field_results.push(FieldVerification {
+            field_name: "geographic_designation_origin".to_string(),
+            expected: Some(format!(
+                "{} must be a product of {} ({})",
+                extracted.class_type, designation.region, designation.cfr_reference
+            )),
+            extracted: country.unwrap_or("Not stated").to_string(),
+            matches,
+            similarity_score: if matches { 1.0 } else { 0.0 },
+            cfr_citation: Some(designation.cfr_reference.to_string()),
+            ..rules::ORIGIN_GEOGRAPHIC_DESIGNATION.base()
+        });
+    }
+
+    // ── Allergen and Additive Disclosures (27 CFR 4.32) ──────────────
+    check_disclosures(extracted, &mut field_results);
+
     // ── Mandatory Field Presence (27 CFR) ────────────────────────────
     // Brand name must be present
     if extracted.brand_name.is_empty() {
@@ -140,6 +260,7 @@ pub fn verify_label(
             extracted: String::new(),
             matches: false,
             similarity_score: 0.0,
+            ..rules::FIELD_PRESENT.base()
         });
     }
 
@@ -151,6 +272,7 @@ pub fn verify_label(
             extracted: String::new(),
             matches: false,
             similarity_score: 0.0,
+            ..rules::FIELD_PRESENT.base()
         });
     }
 
@@ -162,6 +284,7 @@ pub fn verify_label(
             extracted: format!("{:.1}%", extracted.abv),
             matches: false,
             similarity_score: 0.0,
+            ..rules::FIELD_PRESENT.base()
         });
     }
 
@@ -173,6 +296,7 @@ pub fn verify_label(
             extracted: String::new(),
             matches: false,
             similarity_score: 0.0,
+            ..rules::FIELD_PRESENT.base()
         });
     }
 
@@ -195,49 +319,144 @@ pub fn verify_label(
         ),
         matches: same_fov,
         similarity_score: if same_fov { 1.0 } else { 0.0 },
+        ..rules::SAME_FIELD_OF_VISION.base()
     });
 
+    // ── OCR Ensemble Agreement ───────────────────────────────────────
+    check_ocr_agreement(extracted, &mut field_results);
+
     // ── Compute Overall Result ───────────────────────────────────────
-    let passed = field_results.iter().all(|f| f.matches);
-    let confidence_score = if field_results.is_empty() {
-        0.0
-    } else {
-        field_results.iter().map(|f| f.similarity_score).sum::<f64>() / field_results.len() as f64
-    };
+    let passed = rules::passes(&field_results);
+    let (confidence_score, score_breakdown) = scoring::model().score(&field_results);
 
     VerificationResult {
         passed,
         field_results,
         confidence_score,
+        score_breakdown,
         matched_beverage_id: None,
-        match_type: "no_match".to_string(),
+        match_type: MatchType::NoMatch,
         match_confidence: 0.0,
         abv_deviation: None,
         category_rule_applied: None,
+        candidates: Vec::new(),
+        profile_name: profile.name.clone(),
+        ocr_consensus: extracted.consensus.clone(),
         warnings: Vec::new(),
     }
 }
 
+/// Flag every field the OCR ensemble members read differently for review.
+///
+/// The similarity score is the share of members that read the kept value.
+fn check_ocr_agreement(extracted: &ExtractedLabelFields, field_results: &mut Vec<FieldVerification>) {
+    for consensus in extracted.consensus.iter().filter(|c| c.is_disputed()) {
+        field_results.push(FieldVerification {
+            field_name: format!("{}_ocr_agreement", consensus.field_name),
+            expected: Some(format!("Same reading from all {} OCR models", consensus.readings.len())),
+            extracted: consensus
+                .readings
+                .iter()
+                .map(|r| format!("{}: {}", r.source, if r.value.is_empty() { "(none)" } else { &r.value }))
+                .collect::<Vec<_>>()
+                .join("; "),
+            matches: false,
+            similarity_score: consensus.confidence,
+            ..rules::OCR_DISAGREEMENT.base()
+        });
+    }
+}
+
+/// Check disclosure statement wording and require a sulfite declaration on wine.
+///
+/// The sulfite requirement applies to wine with 10 ppm or more total sulfur
+/// dioxide; since the label doesn't state the level, a missing declaration on
+/// any wine is flagged for review (a warning, not a failure). A "No sulfites
+/// added" claim is not a declaration. The government warning text is also
+/// searched because OCR often merges the two statements.
+fn check_disclosures(extracted: &ExtractedLabelFields, field_results: &mut Vec<FieldVerification>) {
+    for statement in &extracted.disclosure_statements {
+        for (rule, well_formed) in ttb_standards::classify_disclosure(statement) {
+            field_results.push(FieldVerification {
+                field_name: format!("disclosure_{}_wording", rule.code),
+                expected: Some(format!("{} ({})", rule.required_wording, rule.cfr_reference)),
+                extracted: statement.clone(),
+                matches: well_formed,
+                similarity_score: if well_formed { 1.0 } else { 0.5 },
+                cfr_citation: Some(rule.cfr_reference.to_string()),
+                ..rules::DISCLOSURE_WORDING.base()
+            });
+        }
+    }
+
+    let is_wine = ttb_standards::validate_classification(&extracted.class_type)
+        .category
+        .as_deref()
+        == Some("wine")
+        || extracted.class_type.to_lowercase().contains("wine");
+    if !is_wine {
+        return;
+    }
+
+    let sulfites = ttb_standards::DISCLOSURE_RULES
+        .iter()
+        .find(|rule| rule.code == "sulfites")
+        .expect("sulfite disclosure rule is defined");
+    let declared = extracted
+        .disclosure_statements
+        .iter()
+        .chain(extracted.government_warning.iter())
+        .flat_map(|statement| ttb_standards::classify_disclosure(statement))
+        .any(|(rule, _)| rule.code == sulfites.code);
+
+    field_results.push(FieldVerification {
+        field_name: "sulfite_declaration".to_string(),
+        expected: Some(format!(
+            "{} on wine with 10 ppm or more sulfur dioxide ({})",
+            sulfites.required_wording, sulfites.cfr_reference
+        )),
+        extracted: if declared { "Declared".to_string() } else { "Not found".to_string() },
+        matches: declared,
+        similarity_score: if declared { 1.0 } else { 0.0 },
+        ..rules::DISCLOSURE_SULFITES.base()
+    });
+}
+
 /// Enhanced validation with database-backed beverage reference checking.
 ///
 /// This async version performs:
 /// 1. Exact database lookup by brand + class/type
 /// 2. ABV consistency check against known products
 /// 3. Category ABV range validation (wine: 5-24%, spirits: 30-95%, beer: 0.5-15%)
-/// 4. Fuzzy matching fallback (same as verify_label)
-/// 5. Recording of match history for analytics
+/// 4. Fuzzy brand lookup in the local cache (trigram candidates), then the
+///    TTB COLA public database — or, when the submitter gave a TTB ID, checks
+///    against that specific COLA instead of steps 1, 2 and 4
+/// 5. Bottler/importer cross-check against the matched product's producer
+///    and country of origin against the matched COLA
+/// 6. Recording of match history for analytics
 pub async fn verify_label_with_database(
     pool: &PgPool,
     extracted: &ExtractedLabelFields,
     expected_brand: Option<&str>,
     expected_class: Option<&str>,
     expected_abv: Option<f64>,
+    ttb_id: Option<&str>,
+    profile: &ValidationProfile,
 ) -> Result<VerificationResult, sqlx::Error> {
     // Start with base validation (non-database checks)
-    let mut result = verify_label(extracted, expected_brand, expected_class, expected_abv);
+    let mut result = verify_label(extracted, expected_brand, expected_class, expected_abv, profile);
+
+    // ── Submitted COLA (TTB ID) replaces brand-based matching ────────
+    let submitted_cola = match ttb_id {
+        Some(ttb_id) => verify_against_submitted_cola(pool, &mut result, extracted, ttb_id, profile).await,
+        None => false,
+    };
 
     // ── Database Exact Match Lookup with Staleness Check ─────────────
-    let db_match_with_staleness = if !extracted.brand_name.is_empty() && !extracted.class_type.is_empty() {
+    let db_match_with_staleness = if !submitted_cola
+        && !extracted.brand_name.is_empty()
+        && !extracted.class_type.is_empty()
+    {
         beverage_queries::find_known_beverage_with_staleness(
             pool,
             &extracted.brand_name,
@@ -252,7 +471,18 @@ pub async fn verify_label_with_database(
     if let Some((db_match, is_stale)) = db_match_with_staleness {
         // Found exact match in database
         result.matched_beverage_id = Some(db_match.id);
-        result.match_type = "exact".to_string();
+        result.match_type = MatchType::Exact;
+        result.candidates.push(MatchCandidate {
+            selected: true,
+            ..candidate_from_beverage(&db_match, extracted)
+        });
+        let (_, local_candidates) = find_fuzzy_brand_match(pool, extracted, profile).await?;
+        result
+            .candidates
+            .extend(local_candidates.into_iter().map(|c| MatchCandidate { selected: false, ..c }));
+        result
+            .candidates
+            .extend(stored_cola_candidates(pool, &db_match.brand_name, extracted).await?);
         result.match_confidence = 1.0;
 
         // Warn if cache is stale
@@ -269,135 +499,141 @@ pub async fn verify_label_with_database(
         let abv_diff = (extracted.abv - db_match.abv).abs();
         result.abv_deviation = Some(abv_diff);
 
-        if abv_diff > 1.0 {
-            // Flag: ABV differs by >1% from known product
+        if abv_diff > allowed_abv_deviation(&db_match, profile.abv_database_deviation, profile) {
+            // Flag: ABV differs from known product by more than the profile allows
             result.field_results.push(FieldVerification {
                 field_name: "abv_database_match".to_string(),
-                expected: Some(format!("{:.1}%", db_match.abv)),
+                expected: Some(expected_reference_abv(&db_match)),
                 extracted: format!("{:.1}%", extracted.abv),
                 matches: false,
                 similarity_score: (1.0 - (abv_diff / 100.0)).max(0.0),
+                ..rules::ABV_DATABASE.base()
             });
-            result.passed = false;
         } else {
             // ABV is consistent with database
             result.field_results.push(FieldVerification {
                 field_name: "abv_database_match".to_string(),
-                expected: Some(format!("{:.1}%", db_match.abv)),
+                expected: Some(expected_reference_abv(&db_match)),
                 extracted: format!("{:.1}%", extracted.abv),
                 matches: true,
                 similarity_score: 1.0 - (abv_diff / 100.0),
+                ..rules::ABV_DATABASE.base()
             });
         }
-    } else {
-        // No exact match in local cache — try TTB COLA public database (read-through cache)
-        let mut ttb_matched = false;
-
-        if !extracted.brand_name.is_empty() {
-            match ttb_cola_lookup(pool, extracted).await {
-                Ok(Some((ttb_record, cached_beverage))) => {
-                    info!(
-                        brand = %extracted.brand_name,
-                        ttb_id = %ttb_record.ttb_id,
-                        "TTB COLA lookup found match"
-                    );
-
-                    result.matched_beverage_id = cached_beverage.map(|b| b.id);
-                    result.match_type = "ttb_cola_lookup".to_string();
-
-                    let brand_sim = jaro_winkler(
-                        &extracted.brand_name.to_lowercase(),
-                        &ttb_record.brand_name.to_lowercase(),
-                    );
-                    let class_sim = jaro_winkler(
-                        &extracted.class_type.to_lowercase(),
-                        &ttb_record.class_type_desc.to_lowercase(),
-                    );
-                    result.match_confidence = brand_sim * 0.7 + class_sim * 0.3;
-
-                    // Add TTB reference verification entry
-                    result.field_results.push(FieldVerification {
-                        field_name: "ttb_cola_reference".to_string(),
-                        expected: Some(format!(
-                            "{} — {} (TTB ID: {})",
-                            ttb_record.brand_name, ttb_record.class_type_desc, ttb_record.ttb_id
-                        )),
-                        extracted: format!("{} — {}", extracted.brand_name, extracted.class_type),
-                        matches: brand_sim >= 0.80,
-                        similarity_score: result.match_confidence,
-                    });
 
-                    // Check ABV against TTB-inferred value (wider tolerance: 3.0%)
-                    if let Some(ttb_abv) = ttb_record.inferred_abv {
-                        let abv_diff = (extracted.abv - ttb_abv).abs();
-                        result.abv_deviation = Some(abv_diff);
+        check_bottler_against_producer(&mut result, extracted, &db_match, profile);
+        check_cola_status(&mut result, db_match.ttb_cola_status, &db_match.brand_name);
+    } else if !submitted_cola {
+        // No exact match — try similar brands in the local cache first (catches OCR
+        // typos without a scrape), then the TTB COLA public database (read-through cache)
+        let fuzzy = if extracted.brand_name.is_empty() {
+            None
+        } else {
+            let (fuzzy, local_candidates) = find_fuzzy_brand_match(pool, extracted, profile).await?;
+            result.candidates.extend(local_candidates);
+            fuzzy
+        };
+
+        if let Some((fuzzy_match, brand_match)) = fuzzy {
+            result.matched_beverage_id = Some(fuzzy_match.id);
+            result.match_type = MatchType::Fuzzy;
+            result
+                .candidates
+                .extend(stored_cola_candidates(pool, &fuzzy_match.brand_name, extracted).await?);
+
+            let class_similarity = jaro_winkler(
+                &extracted.class_type.to_lowercase(),
+                &fuzzy_match.class_type.to_lowercase(),
+            );
+            result.match_confidence = brand_match.score * 0.7 + class_similarity * 0.3;
 
+            result.field_results.push(FieldVerification {
+                field_name: "brand_database_fuzzy_match".to_string(),
+                expected: Some(fuzzy_match.brand_name.clone()),
+                extracted: extracted.brand_name.clone(),
+                matches: true,
+                similarity_score: brand_match.score,
+                match_components: Some(brand_match),
+                ..rules::BRAND_DATABASE_FUZZY.base()
+            });
+
+            let abv_diff = (extracted.abv - fuzzy_match.abv).abs();
+            result.abv_deviation = Some(abv_diff);
+
+            if abv_diff > allowed_abv_deviation(&fuzzy_match, profile.abv_fuzzy_deviation, profile) {
+                result.field_results.push(FieldVerification {
+                    field_name: "abv_database_fuzzy_match".to_string(),
+                    expected: Some(format!(
+                        "{} (from similar product: {})",
+                        expected_reference_abv(&fuzzy_match),
+                        fuzzy_match.class_type
+                    )),
+                    extracted: format!("{:.1}%", extracted.abv),
+                    matches: false,
+                    similarity_score: (1.0 - (abv_diff / 100.0)).max(0.0),
+                    ..rules::ABV_DATABASE_FUZZY.base()
+                });
+            }
+
+            check_bottler_against_producer(&mut result, extracted, &fuzzy_match, profile);
+            check_cola_status(&mut result, fuzzy_match.ttb_cola_status, &fuzzy_match.brand_name);
+        } else if !extracted.brand_name.is_empty() {
+            match ttb_cola_lookup(pool, extracted, profile).await {
+                Ok(lookup) => {
+                    result.candidates.extend(lookup.candidates);
+
+                    if let Some((ttb_record, cached_beverage)) = lookup.best {
+                        info!(
+                            brand = %extracted.brand_name,
+                            ttb_id = %ttb_record.ttb_id,
+                            "TTB COLA lookup found match"
+                        );
+
+                        result.matched_beverage_id = cached_beverage.map(|b| b.id);
+                        result.match_type = MatchType::TtbColaLookup;
+
+                        let brand_match =
+                            matching::score_names(&extracted.brand_name, &ttb_record.brand_name);
+                        let brand_sim = brand_match.score;
+                        let class_sim = jaro_winkler(
+                            &extracted.class_type.to_lowercase(),
+                            &ttb_record.class_type_desc.to_lowercase(),
+                        );
+                        result.match_confidence = brand_sim * 0.7 + class_sim * 0.3;
+
+                        // Add TTB reference verification entry
                         result.field_results.push(FieldVerification {
-                            field_name: "abv_ttb_cola_reference".to_string(),
+                            field_name: "ttb_cola_reference".to_string(),
                             expected: Some(format!(
-                                "{:.1}% (inferred from TTB class: {})",
-                                ttb_abv, ttb_record.class_type_desc
+                                "{} — {} (TTB ID: {})",
+                                ttb_record.brand_name, ttb_record.class_type_desc, ttb_record.ttb_id
                             )),
-                            extracted: format!("{:.1}%", extracted.abv),
-                            matches: abv_diff <= 3.0,
-                            similarity_score: (1.0 - (abv_diff / 100.0)).max(0.0),
+                            extracted: format!("{} — {}", extracted.brand_name, extracted.class_type),
+                            matches: brand_sim >= profile.ttb_brand_cutoff,
+                            similarity_score: result.match_confidence,
+                            match_components: Some(brand_match),
+                            ..rules::TTB_COLA_REFERENCE.base()
                         });
 
-                        if abv_diff > 3.0 {
-                            result.passed = false;
-                        }
+                        check_against_ttb_record(&mut result, extracted, &ttb_record, profile);
+                    } else {
+                        info!(brand = %extracted.brand_name, "TTB COLA lookup returned no match");
                     }
-
-                    ttb_matched = true;
-                }
-                Ok(None) => {
-                    info!(brand = %extracted.brand_name, "TTB COLA lookup returned no match");
                 }
                 Err(e) => {
-                    warn!(brand = %extracted.brand_name, error = %e, "TTB COLA lookup failed, falling back to fuzzy search");
+                    warn!(brand = %extracted.brand_name, error = %e, "TTB COLA lookup failed, continuing without reference match");
                     result.warnings.push(format!(
-                        "TTB COLA public database query failed: {}. Falling back to local fuzzy search.",
+                        "TTB COLA public database query failed: {}. No reference product was matched.",
                         e
                     ));
                 }
             }
         }
-
-        // Fallback: fuzzy brand-only search in local cache (if TTB COLA didn't match)
-        if !ttb_matched && !extracted.brand_name.is_empty() {
-            let brand_matches =
-                beverage_queries::find_known_beverage_by_brand(pool, &extracted.brand_name).await?;
-
-            if let Some(fuzzy_match) = brand_matches.first() {
-                result.matched_beverage_id = Some(fuzzy_match.id);
-                result.match_type = "fuzzy".to_string();
-
-                let class_similarity = jaro_winkler(
-                    &extracted.class_type.to_lowercase(),
-                    &fuzzy_match.class_type.to_lowercase(),
-                );
-                result.match_confidence = class_similarity;
-
-                let abv_diff = (extracted.abv - fuzzy_match.abv).abs();
-                result.abv_deviation = Some(abv_diff);
-
-                if abv_diff > 2.0 {
-                    result.field_results.push(FieldVerification {
-                        field_name: "abv_database_fuzzy_match".to_string(),
-                        expected: Some(format!(
-                            "{:.1}% (from similar product: {})",
-                            fuzzy_match.abv, fuzzy_match.class_type
-                        )),
-                        extracted: format!("{:.1}%", extracted.abv),
-                        matches: false,
-                        similarity_score: (1.0 - (abv_diff / 100.0)).max(0.0),
-                    });
-                    result.passed = false;
-                }
-            }
-        }
     }
 
+    // Best candidates first, across sources
+    result.candidates = rank_candidates(std::mem::take(&mut result.candidates));
+
     // ── Category ABV Range Validation ────────────────────────────────
     if let Some(category_rule) =
         beverage_queries::get_category_rule(pool, &extracted.class_type).await?
@@ -424,12 +660,13 @@ pub async fn verify_label_with_database(
                 extracted: format!("{:.1}%", extracted.abv),
                 matches: false,
                 similarity_score: 0.0,
+                cfr_citation: category_rule.cfr_reference.clone(),
+                ..rules::ABV_CATEGORY_RANGE.base()
             });
-            result.passed = false;
 
             // If no match type yet, set to category_only
-            if result.match_type == "no_match" {
-                result.match_type = "category_only".to_string();
+            if result.match_type == MatchType::NoMatch {
+                result.match_type = MatchType::CategoryOnly;
             }
         } else {
             // Check if within typical range (informational)
@@ -444,8 +681,10 @@ pub async fn verify_label_with_database(
                             typical_min, typical_max, category_rule.category
                         )),
                         extracted: format!("{:.1}% (unusual but valid)", extracted.abv),
-                        matches: true, // Valid but flagged as unusual
+                        matches: false, // Valid but unusual — informational only
                         similarity_score: 0.7,
+                        cfr_citation: category_rule.cfr_reference.clone(),
+                        ..rules::ABV_CATEGORY_TYPICAL.base()
                     });
                 }
             }
@@ -457,7 +696,11 @@ pub async fn verify_label_with_database(
     let has_major_inconsistency = result
         .field_results
         .iter()
-        .any(|f| !f.matches && (f.field_name.contains("abv_database") || f.field_name.contains("abv_category")));
+        .any(|f| {
+            !f.matches
+                && f.severity == Severity::Error
+                && (f.field_name.contains("abv_database") || f.field_name.contains("abv_category"))
+        });
 
     if has_major_inconsistency {
         result.field_results.push(FieldVerification {
@@ -469,97 +712,634 @@ pub async fn verify_label_with_database(
             extracted: format!("{} with {:.1}% ABV (inconsistent)", extracted.class_type, extracted.abv),
             matches: false,
             similarity_score: 0.0,
+            ..rules::LOGICAL_CONSISTENCY.base()
         });
     }
 
-    // Recalculate confidence score with new field results
-    result.confidence_score = if result.field_results.is_empty() {
-        0.0
-    } else {
-        result.field_results.iter().map(|f| f.similarity_score).sum::<f64>()
-            / result.field_results.len() as f64
-    };
+    // Recalculate pass/fail and confidence score with new field results
+    result.passed = rules::passes(&result.field_results);
+    (result.confidence_score, result.score_breakdown) = scoring::model().score(&result.field_results);
 
     Ok(result)
 }
 
+/// Cross-check the bottler/importer named on the label against the producer
+/// recorded for the matched known beverage.
+///
+/// Skipped when either side is missing, since many reference rows have no producer.
+fn check_bottler_against_producer(
+    result: &mut VerificationResult,
+    extracted: &ExtractedLabelFields,
+    known: &KnownBeverage,
+    profile: &ValidationProfile,
+) {
+    check_bottler_name(
+        result,
+        extracted,
+        known.producer.as_deref(),
+        "bottler_database_match",
+        &rules::BOTTLER_DATABASE,
+        profile,
+    );
+}
+
+/// Compare the label's bottler/importer name with a reference name
+/// (a known producer or a COLA applicant). Skipped when either side is missing.
+fn check_bottler_name(
+    result: &mut VerificationResult,
+    extracted: &ExtractedLabelFields,
+    reference: Option<&str>,
+    field_name: &str,
+    rule: &rules::Rule,
+    profile: &ValidationProfile,
+) {
+    let Some(producer) = reference.filter(|p| !p.trim().is_empty()) else {
+        return;
+    };
+    let Some(statement) = extracted
+        .name_address_statement
+        .as_deref()
+        .and_then(ttb_standards::parse_name_address_statement)
+    else {
+        return;
+    };
+
+    let components = matching::score_names(&statement.name, producer);
+    let score = components.score;
+    let matches = score >= profile.bottler_threshold;
+
+    result.field_results.push(FieldVerification {
+        field_name: field_name.to_string(),
+        expected: Some(producer.to_string()),
+        extracted: format!("{} {}", statement.phrase, statement.name),
+        matches,
+        similarity_score: score,
+        match_components: Some(components),
+        ..rule.base()
+    });
+}
+
+/// Reference checks against a matched COLA: ABV (approved alcohol content,
+/// else the class-inferred value), applicant vs. bottler, and country of origin.
+fn check_against_ttb_record(
+    result: &mut VerificationResult,
+    extracted: &ExtractedLabelFields,
+    record: &TtbColaRecord,
+    profile: &ValidationProfile,
+) {
+    // Approved alcohol content gets the database tolerance; inferred ABV the wider one
+    let approved_abv = record.detail.as_ref().and_then(|d| d.alcohol_content);
+    if let Some(approved) = approved_abv {
+        let abv_diff = (extracted.abv - approved).abs();
+        result.abv_deviation = Some(abv_diff);
+
+        result.field_results.push(FieldVerification {
+            field_name: "abv_ttb_cola_reference".to_string(),
+            expected: Some(format!("{:.1}% (approved COLA {})", approved, record.ttb_id)),
+            extracted: format!("{:.1}%", extracted.abv),
+            matches: abv_diff <= profile.abv_database_deviation,
+            similarity_score: (1.0 - (abv_diff / 100.0)).max(0.0),
+            ..rules::ABV_TTB_COLA.base()
+        });
+    } else if let Some(ttb_abv) = record.inferred_abv {
+        let abv_diff = (extracted.abv - ttb_abv).abs();
+        result.abv_deviation = Some(abv_diff);
+
+        result.field_results.push(FieldVerification {
+            field_name: "abv_ttb_cola_reference".to_string(),
+            expected: Some(format!(
+                "{:.1}% (inferred from TTB class: {})",
+                ttb_abv, record.class_type_desc
+            )),
+            extracted: format!("{:.1}%", extracted.abv),
+            matches: abv_diff <= profile.abv_ttb_cola_deviation,
+            similarity_score: (1.0 - (abv_diff / 100.0)).max(0.0),
+            ..rules::ABV_TTB_COLA.base()
+        });
+    }
+
+    check_bottler_name(
+        result,
+        extracted,
+        record.detail.as_ref().and_then(|d| d.applicant_name.as_deref()),
+        "bottler_ttb_cola_applicant",
+        &rules::BOTTLER_TTB_COLA,
+        profile,
+    );
+    check_origin_against_ttb_record(result, extracted, record);
+    check_cola_status(result, record.approval_status(), &format!("TTB ID {}", record.ttb_id));
+}
+
+/// Allowed ABV deviation from a reference row: `measured_deviation` when its
+/// ABV was measured, no less than the TTB COLA class tolerance when inferred.
+fn allowed_abv_deviation(beverage: &KnownBeverage, measured_deviation: f64, profile: &ValidationProfile) -> f64 {
+    match beverage.abv_source {
+        AbvSource::Measured => measured_deviation,
+        AbvSource::Inferred => measured_deviation.max(profile.abv_ttb_cola_deviation),
+    }
+}
+
+/// A reference row's ABV for `expected`, marking values inferred from a COLA class.
+fn expected_reference_abv(beverage: &KnownBeverage) -> String {
+    match beverage.abv_source {
+        AbvSource::Measured => format!("{:.1}%", beverage.abv),
+        AbvSource::Inferred => format!("{:.1}% (inferred from class)", beverage.abv),
+    }
+}
+
+/// Flag a matched COLA that is no longer a valid approval.
+///
+/// Surrendered and revoked COLAs fail `ttb_cola.status`; expired ones get a
+/// `ttb_cola.status_expired` warning. An approved COLA passes, and an unknown
+/// status (detail page not fetched, non-COLA reference) is skipped.
+fn check_cola_status(result: &mut VerificationResult, status: Option<ColaStatus>, cola: &str) {
+    let Some(status) = status else {
+        return;
+    };
+
+    let rule = match status {
+        ColaStatus::Expired => &rules::TTB_COLA_STATUS_EXPIRED,
+        _ => &rules::TTB_COLA_STATUS,
+    };
+    let matches = status.is_active();
+
+    result.field_results.push(FieldVerification {
+        field_name: "ttb_cola_status".to_string(),
+        expected: Some(ColaStatus::Approved.to_string()),
+        extracted: format!("{} ({})", status, cola),
+        matches,
+        similarity_score: if matches { 1.0 } else { 0.0 },
+        ..rule.base()
+    });
+}
+
+/// Compare the label's country of origin with the origin recorded on the matched COLA.
+///
+/// A COLA with a foreign origin marks the product as imported, so a missing
+/// country of origin on the label is a failure. Unrecognized origins on either
+/// side are skipped (the label side is already flagged by `verify_label`).
+fn check_origin_against_ttb_record(
+    result: &mut VerificationResult,
+    extracted: &ExtractedLabelFields,
+    record: &TtbColaRecord,
+) {
+    let Some(record_code) = origin::normalize_country(&record.origin_desc)
+        .or_else(|| origin::normalize_country(&record.origin_code))
+    else {
+        return;
+    };
+
+    let country = extracted
+        .country_of_origin
+        .as_deref()
+        .map(str::trim)
+        .filter(|c| !c.is_empty());
+
+    let matches = match country {
+        Some(c) => match origin::normalize_country(c) {
+            Some(code) => code == record_code,
+            None => return,
+        },
+        None => record_code == origin::US,
+    };
+
+    result.field_results.push(FieldVerification {
+        field_name: "country_of_origin_ttb_cola".to_string(),
+        expected: Some(format!(
+            "{} ({}) per TTB ID {}",
+            record.origin_desc, record_code, record.ttb_id
+        )),
+        extracted: country.unwrap_or("Not stated").to_string(),
+        matches,
+        similarity_score: if matches { 1.0 } else { 0.0 },
+        ..rules::ORIGIN_TTB_COLA.base()
+    });
+}
+
+/// Verify the label against the COLA the submitter named by TTB ID.
+///
+/// Returns true when the submitted COLA was resolved (found or confirmed
+/// missing), in which case brand-based matching is skipped. A failed TTB
+/// request only adds a warning and returns false, so the caller falls back
+/// to the usual lookup.
+async fn verify_against_submitted_cola(
+    pool: &PgPool,
+    result: &mut VerificationResult,
+    extracted: &ExtractedLabelFields,
+    ttb_id: &str,
+    profile: &ValidationProfile,
+) -> bool {
+    let lookup = match ttb_cola::get_client() {
+        Ok(client) => client.fetch_by_ttb_id(ttb_id).await,
+        Err(e) => Err(e),
+    };
+
+    let record = match lookup {
+        Ok(Some(record)) => record,
+        Ok(None) => {
+            result.field_results.push(FieldVerification {
+                field_name: "ttb_cola_submitted".to_string(),
+                expected: Some(format!("Approved COLA with TTB ID {}", ttb_id)),
+                extracted: "Not found in the TTB COLA registry".to_string(),
+                matches: false,
+                similarity_score: 0.0,
+                ..rules::TTB_COLA_SUBMITTED.base()
+            });
+            return true;
+        }
+        Err(e) => {
+            warn!(ttb_id = %ttb_id, error = %e, "Submitted COLA lookup failed, falling back to brand search");
+            result.warnings.push(format!(
+                "Could not retrieve submitted COLA {}: {}. Matched by brand instead.",
+                ttb_id, e
+            ));
+            return false;
+        }
+    };
+
+    info!(ttb_id = %record.ttb_id, brand = %record.brand_name, "Verifying against submitted COLA");
+
+    let cached = match beverage_queries::upsert_from_ttb_cola(pool, &record).await {
+        Ok(beverage) => beverage,
+        Err(e) => {
+            warn!(ttb_id = %record.ttb_id, error = %e, "Failed to cache submitted COLA (non-fatal)");
+            None
+        }
+    };
+
+    let brand_match = matching::score_names(&extracted.brand_name, &record.brand_name);
+    let class_sim = jaro_winkler(
+        &extracted.class_type.to_lowercase(),
+        &record.class_type_desc.to_lowercase(),
+    );
+
+    result.matched_beverage_id = cached.as_ref().map(|b| b.id);
+    result.match_type = MatchType::TtbColaLookup;
+    result.match_confidence = brand_match.score * 0.7 + class_sim * 0.3;
+    result.candidates = vec![MatchCandidate {
+        selected: true,
+        ..candidate_from_ttb(&record, result.matched_beverage_id, extracted)
+    }];
+    if !extracted.brand_name.is_empty() {
+        match find_fuzzy_brand_match(pool, extracted, profile).await {
+            Ok((_, local)) => result
+                .candidates
+                .extend(local.into_iter().map(|c| MatchCandidate { selected: false, ..c })),
+            Err(e) => warn!(error = %e, "Local candidate lookup failed (non-fatal)"),
+        }
+    }
+
+    result.field_results.push(FieldVerification {
+        field_name: "ttb_cola_submitted".to_string(),
+        expected: Some(format!(
+            "{} — {} (TTB ID: {})",
+            record.brand_name, record.class_type_desc, record.ttb_id
+        )),
+        extracted: format!("{} — {}", extracted.brand_name, extracted.class_type),
+        matches: brand_match.score >= profile.brand_threshold,
+        similarity_score: brand_match.score,
+        match_components: Some(brand_match),
+        ..rules::TTB_COLA_SUBMITTED.base()
+    });
+
+    check_against_ttb_record(result, extracted, &record, profile);
+    true
+}
+
+/// Find the best local cache match for a possibly misspelled brand.
+///
+/// Candidates come from the trigram index and are re-scored with
+/// `matching::score_names` (see `best_fuzzy_candidate`). Also returns the top
+/// local candidates for the result, with the chosen one marked as selected.
+async fn find_fuzzy_brand_match(
+    pool: &PgPool,
+    extracted: &ExtractedLabelFields,
+    profile: &ValidationProfile,
+) -> Result<(Option<(KnownBeverage, MatchComponents)>, Vec<MatchCandidate>), sqlx::Error> {
+    let similar = beverage_queries::find_similar_brands(
+        pool,
+        &extracted.brand_name,
+        TRIGRAM_MIN_SIMILARITY,
+        FUZZY_CANDIDATE_LIMIT,
+    )
+    .await?;
+
+    let mut candidates: Vec<MatchCandidate> = similar
+        .iter()
+        .map(|s| candidate_from_beverage(&s.beverage, extracted))
+        .collect();
+
+    let best = best_fuzzy_candidate(similar, extracted, profile.brand_threshold);
+    if let Some((beverage, _)) = &best {
+        for candidate in &mut candidates {
+            candidate.selected = candidate.beverage_id == Some(beverage.id);
+        }
+    }
+
+    Ok((best, top_candidates(candidates)))
+}
+
+/// Pick the best trigram candidate.
+///
+/// The brand score must meet `brand_threshold`; candidates are ranked by
+/// brand_score * 0.7 + class_similarity * 0.3 so that, among products of the
+/// same brand, the closest class/type wins.
+fn best_fuzzy_candidate(
+    candidates: Vec<SimilarBeverage>,
+    extracted: &ExtractedLabelFields,
+    brand_threshold: f64,
+) -> Option<(KnownBeverage, MatchComponents)> {
+    let mut best: Option<(f64, KnownBeverage, MatchComponents)> = None;
+
+    for candidate in candidates {
+        let brand_match = matching::score_names(&extracted.brand_name, &candidate.beverage.brand_name);
+        if brand_match.score < brand_threshold {
+            continue;
+        }
+
+        let class_sim = jaro_winkler(
+            &extracted.class_type.to_lowercase(),
+            &candidate.beverage.class_type.to_lowercase(),
+        );
+        let score = brand_match.score * 0.7 + class_sim * 0.3;
+
+        if best.as_ref().is_none_or(|(best_score, _, _)| score > *best_score) {
+            best = Some((score, candidate.beverage, brand_match));
+        }
+    }
+
+    best.map(|(_, beverage, brand_match)| (beverage, brand_match))
+}
+
+/// Outcome of a TTB COLA lookup.
+struct TtbColaLookup {
+    /// Best matching record and its cached beverage, if any record qualified.
+    best: Option<(TtbColaRecord, Option<KnownBeverage>)>,
+    /// Top-ranked records (including ones below the brand cutoff).
+    candidates: Vec<MatchCandidate>,
+}
+
 /// Query TTB COLA public database and cache results, returning the best match.
 ///
-/// Flow: search TTB by brand → cache all results → find best match via weighted similarity.
-/// Returns (best_ttb_record, optional_cached_beverage) or None if no good match found.
+/// Flow: check negative cache → search TTB by brand → cache all results (or
+/// the miss) → find best match via weighted similarity.
 async fn ttb_cola_lookup(
     pool: &PgPool,
     extracted: &ExtractedLabelFields,
-) -> Result<Option<(TtbColaRecord, Option<crate::models::beverage::KnownBeverage>)>, Box<dyn std::error::Error + Send + Sync>> {
+    profile: &ValidationProfile,
+) -> Result<TtbColaLookup, Box<dyn std::error::Error + Send + Sync>> {
+    let no_match = || TtbColaLookup {
+        best: None,
+        candidates: Vec::new(),
+    };
+
+    // A recent miss for this brand means TTB has nothing to add yet
+    let cached_miss = beverage_queries::find_ttb_cola_miss_with_staleness(
+        pool,
+        &extracted.brand_name,
+        ANY_CATEGORY,
+        TTB_COLA_MISS_TTL_DAYS,
+    )
+    .await
+    .unwrap_or_else(|e| {
+        warn!(brand = %extracted.brand_name, error = %e, "TTB COLA negative cache check failed (non-fatal)");
+        None
+    });
+
+    if let Some((miss, false)) = &cached_miss {
+        info!(
+            brand = %extracted.brand_name,
+            checked_at = %miss.checked_at,
+            "TTB COLA negative cache hit, skipping TTB query"
+        );
+        metrics::counter!("ttb_cola_negative_cache_hits_total").increment(1);
+        return Ok(no_match());
+    }
+
     let client = ttb_cola::get_client()?;
 
     info!(brand = %extracted.brand_name, "Cache miss — querying TTB COLA public database");
 
-    let records = client
+    let mut records = client
         .search_by_brand(&extracted.brand_name, None, 20)
         .await?;
 
     if records.is_empty() {
-        return Ok(None);
+        if let Err(e) = beverage_queries::record_ttb_cola_miss(pool, &extracted.brand_name, ANY_CATEGORY).await {
+            warn!(brand = %extracted.brand_name, error = %e, "Failed to cache TTB COLA miss (non-fatal)");
+        }
+        return Ok(no_match());
+    }
+
+    // TTB now knows the brand; drop the expired miss
+    if cached_miss.is_some() {
+        if let Err(e) = beverage_queries::purge_ttb_cola_misses(pool, Some(&extracted.brand_name), None).await {
+            warn!(brand = %extracted.brand_name, error = %e, "Failed to clear TTB COLA miss (non-fatal)");
+        }
+    }
+
+    // Fetch detail pages (approval data) best match first. A surrendered,
+    // revoked or expired COLA is only selected if no other match is active.
+    let ranked = rank_ttb_matches(&records, extracted, profile.ttb_brand_cutoff);
+    let mut best_idx = ranked.first().copied();
+    for &idx in ranked.iter().take(MAX_DETAIL_FETCHES) {
+        match client.fetch_detail(&records[idx]).await {
+            Ok(detail) => records[idx].detail = Some(detail),
+            Err(e) => warn!(
+                ttb_id = %records[idx].ttb_id,
+                error = %e,
+                "TTB COLA detail fetch failed, using search result only"
+            ),
+        }
+        if records[idx].approval_status().is_none_or(ColaStatus::is_active) {
+            best_idx = Some(idx);
+            break;
+        }
+        info!(
+            ttb_id = %records[idx].ttb_id,
+            status = ?records[idx].approval_status(),
+            "Matched COLA is not active, trying the next match"
+        );
     }
 
     info!(count = records.len(), "TTB COLA returned results, caching");
 
     // Cache all results in known_beverages
-    let cached = beverage_queries::upsert_batch_from_ttb_cola(pool, &records).await?;
+    let cached = beverage_queries::upsert_batch_from_ttb_cola(pool, &records).await?.cached;
 
-    // Find the best matching record
-    let best = find_best_ttb_match(&records, extracted);
+    // The beverage cached from a record (for matched_beverage_id); COLAs
+    // sharing a brand and class/type each have their own row
+    let cached_for = |record: &TtbColaRecord| cached.get(&record.ttb_id);
 
-    match best {
-        Some(ttb_record) => {
-            // Find the corresponding cached beverage for matched_beverage_id
-            let cached_bev = cached.into_iter().find(|b| {
-                b.brand_name.eq_ignore_ascii_case(&ttb_record.brand_name)
-                    && b.class_type.eq_ignore_ascii_case(&ttb_record.class_type_desc)
-            });
-            Ok(Some((ttb_record, cached_bev)))
+    let best = best_idx.map(|idx| records[idx].clone());
+
+    let candidates = records
+        .iter()
+        .map(|record| MatchCandidate {
+            selected: best.as_ref().is_some_and(|b| b.ttb_id == record.ttb_id),
+            ..candidate_from_ttb(record, cached_for(record).map(|b| b.id), extracted)
+        })
+        .collect();
+
+    let best = best.map(|ttb_record| {
+        let cached_bev = cached_for(&ttb_record).cloned();
+        (ttb_record, cached_bev)
+    });
+
+    Ok(TtbColaLookup {
+        best,
+        candidates: top_candidates(candidates),
+    })
+}
+
+/// Build a candidate entry for a cached beverage.
+fn candidate_from_beverage(beverage: &KnownBeverage, extracted: &ExtractedLabelFields) -> MatchCandidate {
+    let (brand_similarity, class_similarity, abv_similarity) =
+        candidate_similarities(extracted, &beverage.brand_name, &beverage.class_type, Some(beverage.abv));
+
+    MatchCandidate {
+        source: CandidateSource::LocalCache,
+        beverage_id: Some(beverage.id),
+        ttb_id: None,
+        brand_name: beverage.brand_name.clone(),
+        class_type: beverage.class_type.clone(),
+        abv: Some(beverage.abv),
+        brand_similarity,
+        class_similarity,
+        abv_similarity,
+        score: brand_similarity * 0.7 + class_similarity * 0.3,
+        selected: false,
+    }
+}
+
+/// Build a candidate entry for a TTB COLA record.
+fn candidate_from_ttb(
+    record: &TtbColaRecord,
+    beverage_id: Option<uuid::Uuid>,
+    extracted: &ExtractedLabelFields,
+) -> MatchCandidate {
+    let (brand_similarity, class_similarity, abv_similarity) = candidate_similarities(
+        extracted,
+        &record.brand_name,
+        &record.class_type_desc,
+        record.reference_abv(),
+    );
+
+    MatchCandidate {
+        source: CandidateSource::TtbCola,
+        beverage_id,
+        ttb_id: Some(record.ttb_id.clone()),
+        brand_name: record.brand_name.clone(),
+        class_type: record.class_type_desc.clone(),
+        abv: record.reference_abv(),
+        brand_similarity,
+        class_similarity,
+        abv_similarity,
+        score: brand_similarity * 0.7 + class_similarity * 0.3,
+        selected: false,
+    }
+}
+
+/// Brand, class/type and ABV similarity of a reference product to the label.
+fn candidate_similarities(
+    extracted: &ExtractedLabelFields,
+    brand_name: &str,
+    class_type: &str,
+    abv: Option<f64>,
+) -> (f64, f64, Option<f64>) {
+    let brand = matching::score_names(&extracted.brand_name, brand_name).score;
+    let class = jaro_winkler(&extracted.class_type.to_lowercase(), &class_type.to_lowercase());
+    let abv = abv.map(|abv| (1.0 - ((extracted.abv - abv).abs() / 100.0)).max(0.0));
+    (brand, class, abv)
+}
+
+/// Candidates from COLAs already stored for `brand` (no TTB request).
+async fn stored_cola_candidates(
+    pool: &PgPool,
+    brand: &str,
+    extracted: &ExtractedLabelFields,
+) -> Result<Vec<MatchCandidate>, sqlx::Error> {
+    let records = beverage_queries::find_ttb_cola_records_by_brand(pool, brand, FUZZY_CANDIDATE_LIMIT).await?;
+    Ok(top_candidates(
+        records
+            .iter()
+            .map(|(record, beverage_id)| candidate_from_ttb(record, *beverage_id, extracted))
+            .collect(),
+    ))
+}
+
+/// Merge candidates from all sources into the reported ranking.
+///
+/// A product found in several sources (a cached beverage and the COLA it was
+/// cached from) is listed once, keeping the selected entry and both ids. The
+/// best `REPORTED_CANDIDATES` are kept, plus the selected one if it ranks lower.
+fn rank_candidates(mut candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate> {
+    candidates.sort_by(|a, b| b.selected.cmp(&a.selected).then(b.score.total_cmp(&a.score)));
+
+    let mut ranked: Vec<MatchCandidate> = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let same_product = |other: &MatchCandidate| {
+            (candidate.beverage_id.is_some() && other.beverage_id == candidate.beverage_id)
+                || (candidate.ttb_id.is_some() && other.ttb_id == candidate.ttb_id)
+        };
+        match ranked.iter_mut().find(|other| same_product(other)) {
+            Some(kept) => {
+                kept.beverage_id = kept.beverage_id.or(candidate.beverage_id);
+                kept.ttb_id = kept.ttb_id.take().or(candidate.ttb_id);
+            }
+            None => ranked.push(candidate),
         }
-        None => Ok(None),
     }
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score));
+    if let Some(pos) = ranked.iter().position(|c| c.selected).filter(|&pos| pos >= REPORTED_CANDIDATES) {
+        ranked.swap(REPORTED_CANDIDATES - 1, pos);
+    }
+    ranked.truncate(REPORTED_CANDIDATES);
+    ranked
+}
+
+/// Keep the highest-scoring candidates for one source.
+fn top_candidates(mut candidates: Vec<MatchCandidate>) -> Vec<MatchCandidate> {
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+    candidates.truncate(REPORTED_CANDIDATES_PER_SOURCE);
+    candidates
 }
 
-/// Find the best TTB COLA match using weighted Jaro-Winkler similarity.
+/// Rank TTB COLA records that match the label, best first (indices into `records`).
 ///
-/// Scoring: brand_similarity * 0.7 + class_similarity * 0.3
-/// Requires brand_similarity >= 0.80 to be considered a match.
-fn find_best_ttb_match(
+/// Scoring: brand_similarity * 0.7 + class_similarity * 0.3, where brand
+/// similarity comes from `matching::score_names` and class similarity is
+/// Jaro-Winkler.
+/// Requires brand_similarity >= `brand_cutoff` to be considered a match.
+fn rank_ttb_matches(
     records: &[TtbColaRecord],
     extracted: &ExtractedLabelFields,
-) -> Option<TtbColaRecord> {
-    let mut best_score = 0.0_f64;
-    let mut best_record: Option<&TtbColaRecord> = None;
-
-    for record in records {
-        let brand_sim = jaro_winkler(
-            &extracted.brand_name.to_lowercase(),
-            &record.brand_name.to_lowercase(),
-        );
-
-        // Brand must meet minimum threshold
-        if brand_sim < 0.80 {
-            continue;
-        }
+    brand_cutoff: f64,
+) -> Vec<usize> {
+    let mut scored: Vec<(usize, f64)> = records
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, record)| {
+            let brand_sim = matching::score_names(&extracted.brand_name, &record.brand_name).score;
 
-        let class_sim = jaro_winkler(
-            &extracted.class_type.to_lowercase(),
-            &record.class_type_desc.to_lowercase(),
-        );
+            // Brand must meet minimum threshold
+            if brand_sim < brand_cutoff {
+                return None;
+            }
 
-        let score = brand_sim * 0.7 + class_sim * 0.3;
+            let class_sim = jaro_winkler(
+                &extracted.class_type.to_lowercase(),
+                &record.class_type_desc.to_lowercase(),
+            );
 
-        if score > best_score {
-            best_score = score;
-            best_record = Some(record);
-        }
-    }
+            Some((idx, brand_sim * 0.7 + class_sim * 0.3))
+        })
+        .collect();
 
-    best_record.cloned()
+    // Stable: equal scores keep TTB's (most recent first) order
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.into_iter().map(|(idx, _)| idx).collect()
 }
 
 #[cfg(test)]
@@ -574,6 +1354,9 @@ mod tests {
             net_contents: "750 mL".to_string(),
             country_of_origin: Some("USA".to_string()),
             government_warning: Some("GOVERNMENT WARNING: ...".to_string()),
+            name_address_statement: Some("Bottled by Stone Creek Vineyards, Napa, CA".to_string()),
+            disclosure_statements: vec!["Contains Sulfites".to_string()],
+            consensus: Vec::new(),
         }
     }
 
@@ -585,6 +1368,7 @@ mod tests {
             Some("Stone Creek Vineyards"),
             Some("Cabernet Sauvignon"),
             Some(13.5),
+            &ValidationProfile::default(),
         );
         // Brand, class, ABV should all match
         let brand = result.field_results.iter().find(|f| f.field_name == "brand_name").unwrap();
@@ -593,10 +1377,212 @@ mod tests {
         assert!(abv.matches);
     }
 
+    #[test]
+    fn test_brand_match_ignores_article_and_apostrophe() {
+        let mut fields = sample_fields();
+        fields.brand_name = "JACK DANIELS".to_string();
+        let result =
+            verify_label(&fields, Some("Jack Daniel's"), None, None, &ValidationProfile::default());
+        let brand = result.field_results.iter().find(|f| f.field_name == "brand_name").unwrap();
+        assert!(brand.matches);
+        let components = brand.match_components.as_ref().unwrap();
+        assert_eq!(components.normalized_extracted, "jack daniels");
+        assert_eq!(components.score, brand.similarity_score);
+    }
+
+    #[test]
+    fn test_best_ttb_match_uses_normalized_brand() {
+        let record = |ttb_id: &str, brand: &str| TtbColaRecord {
+            ttb_id: ttb_id.to_string(),
+            permit_no: String::new(),
+            serial_number: String::new(),
+            completed_date: None,
+            fanciful_name: None,
+            brand_name: brand.to_string(),
+            origin_code: String::new(),
+            origin_desc: String::new(),
+            class_type_code: String::new(),
+            class_type_desc: "TABLE RED WINE".to_string(),
+            source_url: String::new(),
+            inferred_abv: None,
+            beverage_category: "wine".to_string(),
+            detail: None,
+        };
+        let mut fields = sample_fields();
+        fields.brand_name = "Chateau Montelena".to_string();
+        let records = vec![record("1", "MONTES"), record("2", "CHÂTEAU MONTELENA WINERY")];
+        assert_eq!(rank_ttb_matches(&records, &fields, 0.80), vec![1]);
+    }
+
+    #[test]
+    fn test_profile_thresholds_applied() {
+        let mut fields = sample_fields();
+        fields.brand_name = "Stone Crest".to_string();
+        let standard = ValidationProfile::default();
+        let import = ValidationProfile {
+            name: "import".to_string(),
+            brand_threshold: 0.70,
+            ..ValidationProfile::default()
+        };
+
+        let brand_matches = |profile: &ValidationProfile| {
+            let result = verify_label(&fields, Some("Stone Creek Vineyards"), None, None, profile);
+            assert_eq!(result.profile_name, profile.name);
+            result.field_results.iter().find(|f| f.field_name == "brand_name").unwrap().matches
+        };
+        assert!(!brand_matches(&standard));
+        assert!(brand_matches(&import));
+    }
+
+    #[test]
+    fn test_fuzzy_candidate_tolerates_typo() {
+        let beverage = |brand: &str, class_type: &str| SimilarBeverage {
+            beverage: KnownBeverage {
+                brand_name: brand.to_string(),
+                class_type: class_type.to_string(),
+                abv: 13.5,
+                standard_size_ml: Some(750),
+                is_verified: true,
+                source: "manual".to_string(),
+                ..KnownBeverage::test_fixture()
+            },
+            similarity: 0.6,
+        };
+        let mut fields = sample_fields();
+        fields.brand_name = "Stone Creak Vineyards".to_string();
+
+        let candidates = vec![
+            beverage("Stone Creek Vineyards", "Chardonnay"),
+            beverage("Stone Creek Vineyards", "Cabernet Sauvignon"),
+            beverage("Stoneleigh", "Cabernet Sauvignon"),
+        ];
+        let (matched, brand_match) = best_fuzzy_candidate(candidates, &fields, 0.85).unwrap();
+        assert_eq!(matched.brand_name, "Stone Creek Vineyards");
+        assert_eq!(matched.class_type, "Cabernet Sauvignon");
+        assert!(brand_match.score >= 0.85);
+
+        let unrelated = vec![beverage("Stoneleigh", "Cabernet Sauvignon")];
+        assert!(best_fuzzy_candidate(unrelated, &fields, 0.85).is_none());
+    }
+
+    #[test]
+    fn test_ttb_candidates_ranked_with_similarities() {
+        let record = |ttb_id: &str, brand: &str, abv: Option<f64>| TtbColaRecord {
+            ttb_id: ttb_id.to_string(),
+            permit_no: String::new(),
+            serial_number: String::new(),
+            completed_date: None,
+            fanciful_name: None,
+            brand_name: brand.to_string(),
+            origin_code: String::new(),
+            origin_desc: String::new(),
+            class_type_code: String::new(),
+            class_type_desc: "TABLE RED WINE".to_string(),
+            source_url: String::new(),
+            inferred_abv: abv,
+            beverage_category: "wine".to_string(),
+            detail: None,
+        };
+        let fields = sample_fields();
+        let candidates: Vec<MatchCandidate> = (0..8)
+            .map(|i| record(&i.to_string(), "Stone Cellars", None))
+            .chain([record("best", "STONE CREEK VINEYARDS", Some(12.0))])
+            .map(|r| candidate_from_ttb(&r, None, &fields))
+            .collect();
+
+        let top = top_candidates(candidates);
+        assert_eq!(top.len(), REPORTED_CANDIDATES_PER_SOURCE);
+        assert_eq!(top[0].ttb_id.as_deref(), Some("best"));
+        assert_eq!(top[0].source, CandidateSource::TtbCola);
+        assert_eq!(top[0].brand_similarity, 1.0);
+        assert!((top[0].abv_similarity.unwrap() - 0.985).abs() < 1e-9);
+        assert!(top[1].abv_similarity.is_none());
+    }
+
+    #[test]
+    fn test_candidates_ranked_across_sources() {
+        let fields = sample_fields();
+        let beverage = |brand: &str| KnownBeverage {
+            brand_name: brand.to_string(),
+            ..KnownBeverage::test_fixture()
+        };
+        let exact = beverage("Stone Creek");
+        let record = TtbColaRecord {
+            ttb_id: "23001001000123".to_string(),
+            permit_no: String::new(),
+            serial_number: String::new(),
+            completed_date: None,
+            fanciful_name: None,
+            brand_name: "STONE CREEK".to_string(),
+            origin_code: String::new(),
+            origin_desc: String::new(),
+            class_type_code: String::new(),
+            class_type_desc: "TABLE RED WINE".to_string(),
+            source_url: String::new(),
+            inferred_abv: Some(12.0),
+            beverage_category: "wine".to_string(),
+            detail: None,
+        };
+
+        let mut candidates = vec![MatchCandidate {
+            selected: true,
+            ..candidate_from_beverage(&exact, &fields)
+        }];
+        // The same row from the trigram search, and the COLA it was cached from
+        candidates.push(candidate_from_beverage(&exact, &fields));
+        candidates.push(candidate_from_ttb(&record, Some(exact.id), &fields));
+        candidates.extend((0..6).map(|i| candidate_from_beverage(&beverage(&format!("Stone Creek {i}")), &fields)));
+
+        let ranked = rank_candidates(candidates);
+        assert_eq!(ranked.len(), REPORTED_CANDIDATES);
+        let selected: Vec<_> = ranked.iter().filter(|c| c.beverage_id == Some(exact.id)).collect();
+        assert_eq!(selected.len(), 1);
+        assert!(selected[0].selected);
+        assert_eq!(selected[0].ttb_id.as_deref(), Some("23001001000123"));
+        assert!(ranked.windows(2).all(|w| w[0].score >= w[1].score));
+    }
+
+    #[test]
+    fn test_selected_candidate_kept_below_cut() {
+        let fields = sample_fields();
+        let mut candidates: Vec<MatchCandidate> = (0..6)
+            .map(|i| {
+                candidate_from_beverage(
+                    &KnownBeverage {
+                        brand_name: format!("Stone Creek {i}"),
+                        ..KnownBeverage::test_fixture()
+                    },
+                    &fields,
+                )
+            })
+            .collect();
+        let weak = KnownBeverage {
+            brand_name: "Another Brand".to_string(),
+            ..KnownBeverage::test_fixture()
+        };
+        candidates.push(MatchCandidate {
+            selected: true,
+            ..candidate_from_beverage(&weak, &fields)
+        });
+
+        let ranked = rank_candidates(candidates);
+        assert_eq!(ranked.len(), REPORTED_CANDIDATES);
+        assert!(ranked[REPORTED_CANDIDATES - 1].selected);
+    }
+
+    #[test]
+    fn test_match_type_serialization() {
+        let result = verify_label(&sample_fields(), None, None, None, &ValidationProfile::default());
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["match_type"], "no_match");
+        assert_eq!(MatchType::TtbColaLookup.to_string(), "ttb_cola_lookup");
+        assert!(serde_json::from_str::<MatchType>("\"partial\"").is_err());
+    }
+
     #[test]
     fn test_abv_within_tolerance() {
         let fields = sample_fields();
-        let result = verify_label(&fields, None, None, Some(13.7)); // 0.2% diff
+        let result = verify_label(&fields, None, None, Some(13.7), &ValidationProfile::default()); // 0.2% diff
         let abv = result.field_results.iter().find(|f| f.field_name == "abv").unwrap();
         assert!(abv.matches); // Within ±0.3%
     }
@@ -604,7 +1590,7 @@ mod tests {
     #[test]
     fn test_abv_outside_tolerance() {
         let fields = sample_fields();
-        let result = verify_label(&fields, None, None, Some(14.0)); // 0.5% diff
+        let result = verify_label(&fields, None, None, Some(14.0), &ValidationProfile::default()); // 0.5% diff
         let abv = result.field_results.iter().find(|f| f.field_name == "abv").unwrap();
         assert!(!abv.matches); // Outside ±0.3%
     }
@@ -612,7 +1598,7 @@ mod tests {
     #[test]
     fn test_same_field_of_vision() {
         let fields = sample_fields();
-        let result = verify_label(&fields, None, None, None);
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
         let fov = result.field_results.iter().find(|f| f.field_name == "same_field_of_vision").unwrap();
         assert!(fov.matches);
     }
@@ -621,7 +1607,7 @@ mod tests {
     fn test_missing_brand_fails_fov() {
         let mut fields = sample_fields();
         fields.brand_name = String::new();
-        let result = verify_label(&fields, None, None, None);
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
         let fov = result.field_results.iter().find(|f| f.field_name == "same_field_of_vision").unwrap();
         assert!(!fov.matches);
     }
@@ -629,15 +1615,260 @@ mod tests {
     #[test]
     fn test_ttb_classification_check() {
         let fields = sample_fields();
-        let result = verify_label(&fields, None, None, None);
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
         let ttb = result.field_results.iter().find(|f| f.field_name == "class_type_ttb_valid").unwrap();
         assert!(ttb.matches); // "Cabernet Sauvignon" is a valid wine type
     }
 
+    #[test]
+    fn test_missing_name_address_statement_fails() {
+        let mut fields = sample_fields();
+        fields.name_address_statement = None;
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let stmt = result.field_results.iter().find(|f| f.field_name == "name_address_statement").unwrap();
+        assert!(!stmt.matches);
+        assert!(!result.passed);
+    }
+
+    #[test]
+    fn test_imported_by_requires_country_of_origin() {
+        let mut fields = sample_fields();
+        fields.name_address_statement = Some("Imported by Example Imports, New York, NY".to_string());
+        fields.country_of_origin = None;
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let country = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "country_of_origin_importer")
+            .unwrap();
+        assert!(!country.matches);
+
+        fields.country_of_origin = Some("France".to_string());
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let country = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "country_of_origin_importer")
+            .unwrap();
+        assert!(country.matches);
+    }
+
+    #[test]
+    fn test_geographic_designation_wrong_country() {
+        let mut fields = sample_fields();
+        fields.class_type = "Scotch Whisky".to_string();
+        fields.abv = 40.0;
+        fields.country_of_origin = Some("USA".to_string());
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let geo = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "geographic_designation_origin")
+            .unwrap();
+        assert!(!geo.matches);
+
+        fields.country_of_origin = Some("Product of Scotland".to_string());
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let geo = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "geographic_designation_origin")
+            .unwrap();
+        assert!(geo.matches);
+    }
+
+    #[test]
+    fn test_domestic_designation_without_country() {
+        let mut fields = sample_fields();
+        fields.class_type = "Kentucky Straight Bourbon Whiskey".to_string();
+        fields.country_of_origin = None;
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let geo = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "geographic_designation_origin")
+            .unwrap();
+        assert!(geo.matches);
+
+        fields.class_type = "Tequila".to_string();
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let geo = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "geographic_designation_origin")
+            .unwrap();
+        assert!(!geo.matches); // Imported designation requires a stated origin
+    }
+
+    #[test]
+    fn test_unrecognized_country_flagged() {
+        let mut fields = sample_fields();
+        fields.country_of_origin = Some("Atlantis".to_string());
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let country = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "country_of_origin_recognized")
+            .unwrap();
+        assert!(!country.matches);
+    }
+
+    #[test]
+    fn test_origin_checked_against_ttb_record() {
+        let mut fields = sample_fields();
+        fields.country_of_origin = Some("Italy".to_string());
+        let record = TtbColaRecord {
+            ttb_id: "123".to_string(),
+            permit_no: "BWN-CA-12345".to_string(),
+            serial_number: "250001".to_string(),
+            completed_date: None,
+            fanciful_name: None,
+            brand_name: "STONE CREEK VINEYARDS".to_string(),
+            origin_code: "06".to_string(),
+            origin_desc: "CALIFORNIA".to_string(),
+            class_type_code: "80".to_string(),
+            class_type_desc: "TABLE RED WINE".to_string(),
+            source_url: String::new(),
+            inferred_abv: Some(12.0),
+            beverage_category: "wine".to_string(),
+            detail: None,
+        };
+        let mut result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        check_origin_against_ttb_record(&mut result, &fields, &record);
+        let origin = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "country_of_origin_ttb_cola")
+            .unwrap();
+        assert!(!origin.matches);
+        assert!(!rules::passes(&result.field_results));
+    }
+
+    #[test]
+    fn test_wine_missing_sulfite_declaration() {
+        let mut fields = sample_fields();
+        fields.disclosure_statements.clear();
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let sulfites = result.field_results.iter().find(|f| f.field_name == "sulfite_declaration").unwrap();
+        assert!(!sulfites.matches);
+        assert_eq!(sulfites.severity, Severity::Warning);
+        assert!(sulfites.expected.as_deref().unwrap().contains("27 CFR 4.32(e)"));
+    }
+
+    #[test]
+    fn test_no_sulfites_added_is_not_a_declaration() {
+        let mut fields = sample_fields();
+        fields.disclosure_statements = vec!["No Sulfites Added".to_string()];
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let sulfites = result.field_results.iter().find(|f| f.field_name == "sulfite_declaration").unwrap();
+        assert!(!sulfites.matches);
+        assert!(!result.field_results.iter().any(|f| f.field_name == "disclosure_sulfites_wording"));
+    }
+
+    #[test]
+    fn test_malformed_disclosure_flagged() {
+        let mut fields = sample_fields();
+        fields.disclosure_statements.push("Colored with Yellow 5".to_string());
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let yellow = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "disclosure_fdc_yellow_5_wording")
+            .unwrap();
+        assert!(!yellow.matches);
+        let sulfites = result.field_results.iter().find(|f| f.field_name == "sulfite_declaration").unwrap();
+        assert!(sulfites.matches);
+    }
+
+    #[test]
+    fn test_warnings_do_not_fail_label() {
+        let mut fields = sample_fields();
+        fields.country_of_origin = Some("Atlantis".to_string()); // origin.recognized is a warning
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+        let country = result
+            .field_results
+            .iter()
+            .find(|f| f.field_name == "country_of_origin_recognized")
+            .unwrap();
+        assert_eq!(country.severity, Severity::Warning);
+        assert!(result.passed);
+    }
+
+    #[test]
+    fn test_every_result_has_rule_code() {
+        let result = verify_label(
+            &sample_fields(),
+            Some("Stone Creek"),
+            Some("Merlot"),
+            Some(12.0),
+            &ValidationProfile::default(),
+        );
+        assert!(result.field_results.iter().all(|f| !f.rule_code.is_empty()));
+        let abv = result.field_results.iter().find(|f| f.field_name == "abv").unwrap();
+        assert_eq!(abv.rule_code, "abv.expected");
+        assert!(abv.cfr_citation.is_some());
+    }
+
+    #[test]
+    fn test_inferred_reference_abv_gets_class_tolerance() {
+        let mut beverage = KnownBeverage::test_fixture();
+        let profile = ValidationProfile::default();
+
+        assert_eq!(allowed_abv_deviation(&beverage, profile.abv_database_deviation, &profile), 1.0);
+        assert_eq!(expected_reference_abv(&beverage), "12.0%");
+
+        beverage.abv_source = AbvSource::Inferred;
+        assert_eq!(allowed_abv_deviation(&beverage, profile.abv_database_deviation, &profile), 3.0);
+        assert_eq!(expected_reference_abv(&beverage), "12.0% (inferred from class)");
+    }
+
+    #[test]
+    fn test_inactive_cola_status_flagged() {
+        let status_of = |status| {
+            let mut result = verify_label(&sample_fields(), None, None, None, &ValidationProfile::default());
+            check_cola_status(&mut result, status, "TTB ID 23001001000123");
+            result.field_results.into_iter().find(|f| f.field_name == "ttb_cola_status")
+        };
+
+        assert!(status_of(None).is_none());
+        assert!(status_of(Some(ColaStatus::Approved)).unwrap().matches);
+
+        let revoked = status_of(Some(ColaStatus::Revoked)).unwrap();
+        assert!(!revoked.matches);
+        assert_eq!(revoked.rule_code, "ttb_cola.status");
+        assert_eq!(revoked.severity, Severity::Error);
+        assert_eq!(revoked.extracted, "revoked (TTB ID 23001001000123)");
+
+        let expired = status_of(Some(ColaStatus::Expired)).unwrap();
+        assert!(!expired.matches);
+        assert_eq!(expired.severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_ocr_disagreement_flagged_for_review() {
+        let mut misread = sample_fields();
+        misread.abv = 18.5;
+        let fields = crate::services::ocr_ensemble::vote(&[
+            ("llava".to_string(), sample_fields()),
+            ("llama".to_string(), misread),
+        ]);
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
+
+        let flagged: Vec<_> = result.field_results.iter().filter(|f| f.rule_code == "ocr.disagreement").collect();
+        assert_eq!(flagged.len(), 1);
+        assert_eq!(flagged[0].field_name, "abv_ocr_agreement");
+        assert_eq!(flagged[0].extracted, "llava: 13.5; llama: 18.5");
+        assert_eq!(flagged[0].similarity_score, 0.5);
+        assert_eq!(flagged[0].severity, Severity::Warning);
+        // A review item, not a failure
+        assert!(result.passed);
+        assert_eq!(result.ocr_consensus.len(), fields.consensus.len());
+    }
+
     #[test]
     fn test_net_contents_validated() {
         let fields = sample_fields();
-        let result = verify_label(&fields, None, None, None);
+        let result = verify_label(&fields, None, None, None, &ValidationProfile::default());
         let nc = result.field_results.iter().find(|f| f.field_name == "net_contents_format").unwrap();
         assert!(nc.matches);
     }
@@ -1,7 +1,9 @@
 //! Test fixtures for E2E testing with real label images
 
+#![allow(dead_code)]
+
 /// Test fixture representing expected values for a label image
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct TestLabelFixture {
     pub filename: &'static str,
     pub expected_brand: &'static str,
@@ -1,5 +1,7 @@
 //! Test helper utilities for E2E testing
 
+#![allow(dead_code)]
+
 use reqwest::multipart;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -56,7 +58,7 @@ pub async fn upload_label_image(
     brand: Option<&str>,
     class_type: Option<&str>,
     expected_abv: Option<f64>,
-) -> Result<VerifyResponse, Box<dyn std::error::Error>> {
+) -> Result<VerifyResponse, Box<dyn std::error::Error + Send + Sync>> {
     let image_bytes = std::fs::read(image_path)?;
     let filename = image_path.file_name().unwrap().to_str().unwrap();
 
@@ -99,7 +101,7 @@ pub async fn poll_job_status(
     base_url: &str,
     job_id: &str,
     timeout_secs: u64,
-) -> Result<JobStatusResponse, Box<dyn std::error::Error>> {
+) -> Result<JobStatusResponse, Box<dyn std::error::Error + Send + Sync>> {
     let max_attempts = timeout_secs * 2; // Poll every 500ms
 
     for attempt in 0..max_attempts {
@@ -137,7 +139,7 @@ pub async fn wait_for_job_completion(
     client: &reqwest::Client,
     base_url: &str,
     job_id: &str,
-) -> Result<JobStatusResponse, Box<dyn std::error::Error>> {
+) -> Result<JobStatusResponse, Box<dyn std::error::Error + Send + Sync>> {
     poll_job_status(client, base_url, job_id, 120).await
 }
 
@@ -189,7 +191,7 @@ pub fn assert_verification_result(
 /// Parse verification result from job status response
 pub fn parse_verification_result(
     job_status: &JobStatusResponse,
-) -> Result<VerificationResult, Box<dyn std::error::Error>> {
+) -> Result<VerificationResult, Box<dyn std::error::Error + Send + Sync>> {
     let result_value = job_status
         .result
         .as_ref()
@@ -4,7 +4,7 @@ use label_verify_hw::{
     db::{self, queries},
     models::job::JobStatus,
     services::{
-        encryption::EncryptionService, ocr::WorkersAIClient, queue::JobQueue, storage::R2Client,
+        encryption::EncryptionService, ocr::WorkersAiClient, queue::JobQueue, storage::R2Client,
     },
 };
 use uuid::Uuid;
@@ -49,7 +49,7 @@ async fn test_full_integration() {
 
     let queue = JobQueue::new(&config.redis_url).expect("Failed to initialize queue");
 
-    let ocr_client = WorkersAIClient::new(&config.cf_account_id, &config.cf_api_token)
+    let ocr_client = WorkersAiClient::new(&config.cf_account_id, &config.cf_api_token)
         .expect("Failed to initialize Workers AI");
 
     let state = AppState::new(db_pool.clone(), r2_client, encryption, queue, ocr_client);
@@ -67,7 +67,7 @@ async fn test_full_integration() {
         .expect("R2 upload failed");
 
     // 2. Test database job creation
-    let job = queries::create_job(&db_pool, &test_key, Some("test-user"))
+    let job = queries::create_job(&db_pool, &test_key, Some("test-user"), &Default::default())
         .await
         .expect("Failed to create job");
 
@@ -103,6 +103,8 @@ async fn test_full_integration() {
         expected_brand: Some("Test Brand".to_string()),
         expected_class: Some("Wine".to_string()),
         expected_abv: Some(13.5),
+        ttb_id: None,
+        profile: None,
     };
 
     state
@@ -176,7 +178,7 @@ async fn test_full_integration() {
 /// Test encryption/decryption round-trip
 #[test]
 fn test_encryption_roundtrip() {
-    let key = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &[0u8; 32]);
+    let key = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, [0u8; 32]);
     let encryption = EncryptionService::new(&key).expect("Failed to create encryption service");
 
     let plaintext = b"sensitive label image data";
@@ -191,6 +193,7 @@ fn test_encryption_roundtrip() {
 #[test]
 fn test_validation_logic() {
     use label_verify_hw::models::label::ExtractedLabelFields;
+    use label_verify_hw::services::profile::ValidationProfile;
     use label_verify_hw::services::validation;
 
     let extracted = ExtractedLabelFields {
@@ -200,6 +203,9 @@ fn test_validation_logic() {
         net_contents: "750ml".to_string(),
         country_of_origin: Some("USA".to_string()),
         government_warning: Some("Contains sulfites".to_string()),
+        name_address_statement: Some("Bottled by Test Winery, Napa, CA".to_string()),
+        disclosure_statements: Vec::new(),
+        consensus: Vec::new(),
     };
 
     // Test exact match
@@ -208,6 +214,7 @@ fn test_validation_logic() {
         Some("Test Wine Brand"),
         Some("Wine"),
         Some(13.5),
+        &ValidationProfile::default(),
     );
 
     assert!(result.passed);
@@ -219,8 +226,831 @@ fn test_validation_logic() {
         Some("Test Winery Brand"), // Slightly different
         Some("Wine"),
         Some(13.0), // Slightly different ABV
+        &ValidationProfile::default(),
     );
 
     // Should still pass with good confidence due to similarity
     assert!(result_fuzzy.confidence_score > 0.7);
 }
+
+/// TTB COLA read-through lookup against the recorded fixture corpus
+///
+/// Replays tests/fixtures/ttb_cola instead of querying ttbonline.gov, so the
+/// lookup and cache upsert are deterministic. Requires PostgreSQL.
+#[tokio::test]
+#[ignore] // Run with: cargo test --test integration_test -- --ignored
+async fn test_ttb_cola_replay_lookup() {
+    use label_verify_hw::db::beverage_queries;
+    use label_verify_hw::models::label::ExtractedLabelFields;
+    use label_verify_hw::models::label::MatchType;
+    use label_verify_hw::services::profile::ValidationProfile;
+    use label_verify_hw::services::ttb_cola::{self, TtbColaClient, TtbColaConfig, TtbColaMode};
+    use label_verify_hw::services::validation;
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let fixtures_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ttb_cola");
+    ttb_cola::init(
+        TtbColaClient::with_config(TtbColaConfig {
+            mode: TtbColaMode::Replay(fixtures_dir),
+            ..TtbColaConfig::default()
+        })
+        .expect("Failed to create replay client"),
+    );
+    let extracted = ExtractedLabelFields {
+        brand_name: "Stone Creek".to_string(),
+        class_type: "Table Red Wine".to_string(),
+        abv: 13.9,
+        net_contents: "750 mL".to_string(),
+        country_of_origin: None,
+        government_warning: None,
+        name_address_statement: Some("Produced and bottled by Stone Creek Cellars, Napa, CA".to_string()),
+        disclosure_statements: vec!["Contains Sulfites".to_string()],
+        consensus: Vec::new(),
+    };
+
+    // 1. Brand lookup goes to (replayed) TTB on a cache miss; reruns hit the cache
+    let result = validation::verify_label_with_database(
+        &db_pool,
+        &extracted,
+        None,
+        None,
+        None,
+        None,
+        &ValidationProfile::default(),
+    )
+    .await
+    .expect("Verification failed");
+
+    assert!(matches!(result.match_type, MatchType::TtbColaLookup | MatchType::Exact));
+    assert!(result.matched_beverage_id.is_some());
+
+    // 2. Batch upsert of replayed search results
+    let client = ttb_cola::get_client().expect("TTB COLA client");
+    let records = client
+        .search_by_brand("Stone Creek", None, 20)
+        .await
+        .expect("Replay search failed");
+    assert_eq!(records.len(), 3);
+
+    let cached = beverage_queries::upsert_batch_from_ttb_cola(&db_pool, &records)
+        .await
+        .expect("Failed to cache TTB COLA records");
+    assert_eq!(cached.cached.len(), records.len());
+    assert_eq!(cached.inserted + cached.updated, records.len());
+    assert_eq!(cached.skipped, 0);
+    assert!(cached.cached.values().all(|b| b.source == "ttb_cola"));
+    assert!(records.iter().all(|r| cached.cached.contains_key(&r.ttb_id)));
+
+    // 3. A submitted TTB ID is verified against its replayed detail page
+    let result = validation::verify_label_with_database(
+        &db_pool,
+        &extracted,
+        None,
+        None,
+        None,
+        Some("23001001000123"),
+        &ValidationProfile::default(),
+    )
+    .await
+    .expect("Verification failed");
+
+    assert_eq!(result.match_type, MatchType::TtbColaLookup);
+    assert!(result
+        .field_results
+        .iter()
+        .any(|f| f.field_name == "ttb_cola_submitted" && f.matches));
+}
+
+/// Negative caching of TTB COLA brand misses
+///
+/// Replays the recorded no-results page for "No Such Brand". Requires PostgreSQL.
+#[tokio::test]
+#[ignore] // Run with: cargo test --test integration_test -- --ignored
+async fn test_ttb_cola_negative_cache() {
+    use label_verify_hw::db::beverage_queries;
+    use label_verify_hw::models::label::{ExtractedLabelFields, MatchType};
+    use label_verify_hw::services::profile::ValidationProfile;
+    use label_verify_hw::services::ttb_cola::{self, TtbColaClient, TtbColaConfig, TtbColaMode};
+    use label_verify_hw::services::validation;
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let fixtures_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ttb_cola");
+    ttb_cola::init(
+        TtbColaClient::with_config(TtbColaConfig {
+            mode: TtbColaMode::Replay(fixtures_dir),
+            ..TtbColaConfig::default()
+        })
+        .expect("Failed to create replay client"),
+    );
+
+    beverage_queries::purge_ttb_cola_misses(&db_pool, Some("No Such Brand"), None)
+        .await
+        .expect("Failed to purge misses");
+
+    let extracted = ExtractedLabelFields {
+        brand_name: "No Such Brand".to_string(),
+        class_type: "Table Red Wine".to_string(),
+        abv: 13.0,
+        net_contents: "750 mL".to_string(),
+        country_of_origin: None,
+        government_warning: None,
+        name_address_statement: None,
+        disclosure_statements: Vec::new(),
+        consensus: Vec::new(),
+    };
+
+    // 1. The TTB miss is recorded
+    let result = validation::verify_label_with_database(
+        &db_pool,
+        &extracted,
+        None,
+        None,
+        None,
+        None,
+        &ValidationProfile::default(),
+    )
+    .await
+    .expect("Verification failed");
+    assert_ne!(result.match_type, MatchType::TtbColaLookup);
+
+    let (miss, is_stale) = beverage_queries::find_ttb_cola_miss_with_staleness(
+        &db_pool,
+        "  no such BRAND ",
+        "",
+        validation::TTB_COLA_MISS_TTL_DAYS,
+    )
+    .await
+    .expect("Failed to read negative cache")
+    .expect("Miss was not cached");
+    assert_eq!(miss.brand_key, "no such brand");
+    assert!(!is_stale);
+
+    // 2. Purging removes it
+    let purged = beverage_queries::purge_ttb_cola_misses(&db_pool, Some("No Such Brand"), None)
+        .await
+        .expect("Failed to purge misses");
+    assert_eq!(purged, 1);
+}
+
+/// COLAs sharing a brand and class/type each keep their own cached row
+///
+/// Replays a "Twin Oaks" search with two TABLE RED WINE COLAs. Requires PostgreSQL.
+#[tokio::test]
+#[ignore] // Run with: cargo test --test integration_test -- --ignored
+async fn test_ttb_cola_same_brand_and_class() {
+    use label_verify_hw::db::beverage_queries;
+    use label_verify_hw::models::label::{CandidateSource, ExtractedLabelFields, MatchType};
+    use label_verify_hw::services::profile::ValidationProfile;
+    use label_verify_hw::services::ttb_cola::{self, TtbColaClient, TtbColaConfig, TtbColaMode};
+    use label_verify_hw::services::validation;
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let fixtures_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ttb_cola");
+    ttb_cola::init(
+        TtbColaClient::with_config(TtbColaConfig {
+            mode: TtbColaMode::Replay(fixtures_dir),
+            ..TtbColaConfig::default()
+        })
+        .expect("Failed to create replay client"),
+    );
+
+    sqlx::query("DELETE FROM known_beverages WHERE LOWER(brand_name) = 'twin oaks'")
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+    beverage_queries::purge_ttb_cola_misses(&db_pool, Some("Twin Oaks"), None)
+        .await
+        .expect("Failed to purge misses");
+
+    let extracted = ExtractedLabelFields {
+        brand_name: "Twin Oaks".to_string(),
+        class_type: "Table Red Wine".to_string(),
+        abv: 12.0,
+        net_contents: "750 mL".to_string(),
+        country_of_origin: None,
+        government_warning: None,
+        name_address_statement: Some("Bottled by Twin Oaks Cellars, Lodi, CA".to_string()),
+        disclosure_statements: vec!["Contains Sulfites".to_string()],
+        consensus: Vec::new(),
+    };
+
+    let result = validation::verify_label_with_database(
+        &db_pool,
+        &extracted,
+        None,
+        None,
+        None,
+        None,
+        &ValidationProfile::default(),
+    )
+    .await
+    .expect("Verification failed");
+    assert_eq!(result.match_type, MatchType::TtbColaLookup);
+
+    // Both COLAs are reported, each with its own cached beverage
+    let colas: Vec<_> = result
+        .candidates
+        .iter()
+        .filter(|c| c.source == CandidateSource::TtbCola)
+        .collect();
+    assert_eq!(colas.len(), 2);
+    assert!(colas.iter().all(|c| c.beverage_id.is_some()));
+    assert_ne!(colas[0].beverage_id, colas[1].beverage_id);
+    assert_ne!(colas[0].ttb_id, colas[1].ttb_id);
+
+    // The matched beverage is the one cached from the selected COLA
+    let selected = result.candidates.iter().find(|c| c.selected).expect("No selected candidate");
+    let (linked_ttb_id,): (String,) = sqlx::query_as(
+        r#"
+        SELECT r.ttb_id FROM known_beverages kb
+        JOIN ttb_cola_records r ON r.id = kb.ttb_cola_record_id
+        WHERE kb.id = $1
+        "#,
+    )
+    .bind(result.matched_beverage_id.expect("No matched beverage"))
+    .fetch_one(&db_pool)
+    .await
+    .expect("Matched beverage is not linked to a COLA");
+    assert_eq!(Some(linked_ttb_id), selected.ttb_id);
+    assert_eq!(selected.beverage_id, result.matched_beverage_id);
+
+    sqlx::query("DELETE FROM known_beverages WHERE LOWER(brand_name) = 'twin oaks'")
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+}
+
+/// Test the worker's TTB COLA cache refresh against the replay fixtures
+#[tokio::test]
+#[ignore]
+async fn test_ttb_cola_cache_refresh() {
+    use label_verify_hw::services::ttb_cola::{TtbColaClient, TtbColaConfig, TtbColaMode};
+    use label_verify_hw::services::ttb_cola_refresh::{self, RefreshSettings};
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let fixtures_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ttb_cola");
+    let client = TtbColaClient::with_config(TtbColaConfig {
+        mode: TtbColaMode::Replay(fixtures_dir),
+        ..TtbColaConfig::default()
+    })
+    .expect("Failed to create replay client");
+
+    sqlx::query(
+        "DELETE FROM known_beverages WHERE LOWER(brand_name) IN ('stone creek', 'refresh test retired', 'refresh test maintenance')",
+    )
+    .execute(&db_pool)
+    .await
+    .expect("Failed to clean up");
+
+    // One COLA TTB still has, one it no longer returns and one whose page is
+    // a maintenance notice
+    let mut ids = Vec::new();
+    for (brand, ttb_id) in [
+        ("Stone Creek", "23001001000123"),
+        ("Refresh Test Retired", "99999999999999"),
+        ("Refresh Test Maintenance", "99999999999998"),
+    ] {
+        let (id,): (uuid::Uuid,) = sqlx::query_as(
+            r#"
+            INSERT INTO known_beverages
+                (brand_name, class_type, beverage_category, abv, source, source_url, last_verified_at)
+            VALUES ($1, 'TABLE RED WINE', 'wine', 12.0, 'ttb_cola', $2, NOW() - INTERVAL '60 days')
+            RETURNING id
+            "#,
+        )
+        .bind(brand)
+        .bind(format!(
+            "https://ttbonline.gov/colasonline/viewColaDetails.do?action=publicDisplaySearchBasic&ttbid={}",
+            ttb_id
+        ))
+        .fetch_one(&db_pool)
+        .await
+        .expect("Failed to insert stale row");
+        ids.push(id);
+    }
+
+    let summary = ttb_cola_refresh::refresh_stale_batch(&db_pool, &client, &RefreshSettings::default())
+        .await
+        .expect("Refresh failed");
+    assert!(summary.refreshed >= 1);
+    assert!(summary.retired >= 1);
+
+    let outcome_of = |id: uuid::Uuid| {
+        let db_pool = db_pool.clone();
+        async move {
+            sqlx::query_as::<_, (Option<String>, bool, f64)>(
+                r#"
+                SELECT ttb_cola_refresh_outcome::TEXT, retired_at IS NOT NULL, abv::FLOAT8
+                FROM known_beverages WHERE id = $1
+                "#,
+            )
+            .bind(id)
+            .fetch_one(&db_pool)
+            .await
+            .expect("Failed to read refreshed row")
+        }
+    };
+
+    let (outcome, retired, abv) = outcome_of(ids[0]).await;
+    assert_eq!(outcome.as_deref(), Some("refreshed"));
+    assert!(!retired);
+    assert!((abv - 13.9).abs() < 0.01);
+
+    let (outcome, retired, _) = outcome_of(ids[1]).await;
+    assert_eq!(outcome.as_deref(), Some("retired"));
+    assert!(retired);
+
+    let (outcome, retired, _) = outcome_of(ids[2]).await;
+    assert_eq!(outcome.as_deref(), Some("failed"));
+    assert!(!retired);
+
+    // Every row was just attempted, so a second run leaves them alone
+    let summary = ttb_cola_refresh::refresh_stale_batch(&db_pool, &client, &RefreshSettings::default())
+        .await
+        .expect("Refresh failed");
+    assert_eq!(summary.total(), 0);
+}
+
+/// Test reference data import (plan, dry-run diff, apply) and export
+#[tokio::test]
+#[ignore]
+async fn test_reference_import_round_trip() {
+    use label_verify_hw::services::reference_import::{
+        self, ExportFormat, ImportFormat, PlannedAction,
+    };
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+
+    sqlx::query("DELETE FROM known_beverages WHERE LOWER(brand_name) = 'import test brand'")
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+
+    let csv = "\
+brand_name,product_name,class_type,beverage_category,abv,source
+Import Test Brand,Reserve,Cabernet Sauvignon,wine,14.5,import_test
+import test brand,RESERVE,Cabernet Sauvignon,wine,14.5,import_test
+Import Test Brand,,Bourbon Whiskey,distilled_spirits,45,import_test
+";
+
+    // 1. First import inserts both unique rows
+    let parsed = reference_import::parse(ImportFormat::Csv, csv.as_bytes()).expect("Failed to parse");
+    let plan = reference_import::plan_import(&db_pool, parsed).await.expect("Failed to plan");
+    let summary = reference_import::apply_import(&db_pool, &plan).await.expect("Failed to apply");
+    assert_eq!(summary.inserted, 2);
+    assert_eq!(summary.duplicates, 1);
+
+    // 2. Re-importing a changed row plans an update with the diff
+    let changed = csv.replace("Bourbon Whiskey", "Straight Bourbon Whisky");
+    let parsed = reference_import::parse(ImportFormat::Csv, changed.as_bytes()).expect("Failed to parse");
+    let plan = reference_import::plan_import(&db_pool, parsed).await.expect("Failed to plan");
+    let summary = plan.summary();
+    assert_eq!((summary.inserted, summary.updated, summary.unchanged), (0, 1, 1));
+    let update = plan
+        .rows
+        .iter()
+        .find_map(|row| match &row.action {
+            PlannedAction::Update(changes) => Some(changes),
+            _ => None,
+        })
+        .expect("No update planned");
+    assert_eq!(update[0].field, "class_type");
+    assert_eq!(update[0].new, "Straight Bourbon Whisky");
+    reference_import::apply_import(&db_pool, &plan).await.expect("Failed to apply");
+
+    // 3. Export includes the imported rows and re-imports unchanged
+    let mut exported = Vec::new();
+    let count = reference_import::export(&db_pool, ExportFormat::Csv, Some("import_test"), &mut exported)
+        .await
+        .expect("Failed to export");
+    assert_eq!(count, 2);
+    let parsed = reference_import::parse(ImportFormat::Csv, exported.as_slice()).expect("Failed to parse export");
+    let plan = reference_import::plan_import(&db_pool, parsed).await.expect("Failed to plan");
+    assert_eq!(plan.summary().unchanged, 2);
+
+    // 4. COLAs are cached on their TTB ID, even when brand, product and ABV match
+    sqlx::query("DELETE FROM known_beverages WHERE brand_name = 'IMPORT TEST COLA'")
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+    sqlx::query("DELETE FROM ttb_cola_records WHERE brand_name = 'IMPORT TEST COLA'")
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+
+    let cola = |ttb_id: &str, class_type: &str| {
+        serde_json::json!({
+            "ttb_id": ttb_id,
+            "permit_no": "CA-I-9999",
+            "serial_number": "990001",
+            "completed_date": "2025-06-01",
+            "fanciful_name": null,
+            "brand_name": "IMPORT TEST COLA",
+            "origin_code": "06",
+            "origin_desc": "CALIFORNIA",
+            "class_type_code": "80",
+            "class_type_desc": class_type,
+            "source_url": format!("https://ttbonline.gov/colasonline/viewColaDetails.do?ttbid={}", ttb_id),
+            "inferred_abv": 12.5,
+            "beverage_category": "wine"
+        })
+    };
+    let colas = serde_json::json!([
+        cola("99001001000001", "TABLE WHITE WINE"),
+        cola("99001001000002", "TABLE RED WINE"),
+        cola("99001001000001", "TABLE RED WINE"),
+    ])
+    .to_string();
+    let parsed = reference_import::parse(ImportFormat::ColaJson, colas.as_bytes()).expect("Failed to parse");
+    let plan = reference_import::plan_import(&db_pool, parsed).await.expect("Failed to plan");
+    let summary = reference_import::apply_import(&db_pool, &plan).await.expect("Failed to apply");
+    assert_eq!((summary.inserted, summary.duplicates), (2, 1));
+
+    let linked: Vec<(String,)> = sqlx::query_as(
+        "SELECT r.ttb_id FROM known_beverages kb \
+         JOIN ttb_cola_records r ON r.id = kb.ttb_cola_record_id \
+         WHERE kb.brand_name = 'IMPORT TEST COLA' ORDER BY r.ttb_id",
+    )
+    .fetch_all(&db_pool)
+    .await
+    .expect("Failed to query");
+    assert_eq!(
+        linked,
+        vec![("99001001000001".to_string(),), ("99001001000002".to_string(),)]
+    );
+
+    // 5. CSV exports leave COLA rows out, and CSV imports refuse them
+    let mut exported = Vec::new();
+    reference_import::export(&db_pool, ExportFormat::Csv, Some("ttb_cola"), &mut exported)
+        .await
+        .expect("Failed to export");
+    assert!(!String::from_utf8_lossy(&exported).contains("IMPORT TEST COLA"));
+
+    let csv = "\
+brand_name,product_name,class_type,beverage_category,abv,source
+IMPORT TEST COLA,,TABLE RED WINE,wine,12.5,ttb_cola
+";
+    let parsed = reference_import::parse(ImportFormat::Csv, csv.as_bytes()).expect("Failed to parse");
+    assert!(parsed.rows.is_empty());
+    assert_eq!(parsed.invalid.len(), 1);
+
+    // 6. The cola-json export re-imports unchanged, without duplicating rows
+    let mut exported = Vec::new();
+    let count = reference_import::export(&db_pool, ExportFormat::ColaJson, None, &mut exported)
+        .await
+        .expect("Failed to export");
+    assert!(count >= 2);
+    let parsed = reference_import::parse(ImportFormat::ColaJson, exported.as_slice()).expect("Failed to parse export");
+    let plan = reference_import::plan_import(&db_pool, parsed).await.expect("Failed to plan");
+    let summary = plan.summary();
+    assert_eq!((summary.inserted, summary.updated, summary.unchanged), (0, 0, count));
+    reference_import::apply_import(&db_pool, &plan).await.expect("Failed to apply");
+
+    let (rows,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM known_beverages WHERE brand_name = 'IMPORT TEST COLA'")
+        .fetch_one(&db_pool)
+        .await
+        .expect("Failed to count");
+    assert_eq!(rows, 2);
+}
+
+/// Test bulk import of a TTB COLA search results CSV export
+#[tokio::test]
+#[ignore]
+async fn test_ttb_cola_csv_import() {
+    use label_verify_hw::services::ttb_cola::DEFAULT_BASE_URL;
+    use label_verify_hw::services::ttb_cola_csv::{self, ColaCsvReader};
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+
+    sqlx::query("DELETE FROM known_beverages WHERE brand_name LIKE 'CSV IMPORT %'")
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+    sqlx::query("DELETE FROM ttb_cola_records WHERE brand_name LIKE 'CSV IMPORT %'")
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+
+    let export = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ttb_cola_export.csv");
+    let open = || ColaCsvReader::new(std::fs::File::open(&export).expect("Missing fixture"), DEFAULT_BASE_URL);
+
+    // Rows 1 and 3 share brand, (no) fanciful name and inferred ABV but are
+    // different COLAs, so each gets its own row; the row without a TTB ID is
+    // skipped. A batch size of 2 spreads the file over several transactions.
+    let summary = ttb_cola_csv::import(&db_pool, open().expect("Bad header"), 2)
+        .await
+        .expect("Import failed");
+    assert_eq!((summary.inserted, summary.updated, summary.skipped), (4, 0, 1));
+
+    // Re-importing updates every valid row
+    let summary = ttb_cola_csv::import(&db_pool, open().expect("Bad header"), 1000)
+        .await
+        .expect("Import failed");
+    assert_eq!((summary.inserted, summary.updated, summary.skipped), (0, 4, 1));
+
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM known_beverages WHERE brand_name LIKE 'CSV IMPORT %' AND source = 'ttb_cola'",
+    )
+    .fetch_one(&db_pool)
+    .await
+    .expect("Failed to count");
+    assert_eq!(count, 4);
+
+    // Every COLA keeps its own record, linked from the beverage cached from it
+    let (records, linked, inferred): (i64, i64, i64) = sqlx::query_as(
+        r#"
+        SELECT (SELECT COUNT(*) FROM ttb_cola_records WHERE brand_name LIKE 'CSV IMPORT %'),
+               COUNT(*) FILTER (WHERE ttb_cola_record_id IS NOT NULL),
+               COUNT(*) FILTER (WHERE abv_source = 'inferred')
+        FROM known_beverages WHERE brand_name LIKE 'CSV IMPORT %'
+        "#,
+    )
+    .fetch_one(&db_pool)
+    .await
+    .expect("Failed to count records");
+    assert_eq!((records, linked, inferred), (4, 4, 4));
+
+    let record: (Option<String>, Option<String>, Option<chrono::NaiveDate>, Option<String>) = sqlx::query_as(
+        "SELECT permit_no, serial_number, completed_date, origin_desc FROM ttb_cola_records WHERE ttb_id = $1",
+    )
+    .bind("24001001000111")
+    .fetch_one(&db_pool)
+    .await
+    .expect("Record not stored");
+    assert_eq!(
+        record,
+        (
+            Some("CA-I-9001".to_string()),
+            Some("240001".to_string()),
+            chrono::NaiveDate::from_ymd_opt(2024, 1, 8),
+            Some("CALIFORNIA".to_string()),
+        )
+    );
+}
+
+/// Test storing a job's OCR extraction encrypted and loading it back
+#[tokio::test]
+#[ignore]
+async fn test_job_ocr_extraction_persisted() {
+    use label_verify_hw::models::label::ExtractedLabelFields;
+    use label_verify_hw::services::job_extraction;
+    use label_verify_hw::services::ocr::{OcrExtraction, UnreadableOutput};
+    use label_verify_hw::services::ocr_ensemble;
+    use label_verify_hw::services::prompts::{self, LABEL_FIELDS};
+    use std::time::Duration;
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+    let encryption =
+        EncryptionService::new(&config.encryption_key).expect("Failed to initialize encryption");
+
+    let job = queries::create_job(&db_pool, "test/provenance.enc", Some("test-user"), &Default::default())
+        .await
+        .expect("Failed to create job");
+    assert_eq!(job.extraction, None);
+
+    let raw_output = r#"```json {"brand_name": "Stone Creek"} ```"#;
+    let extraction = OcrExtraction {
+        fields: ExtractedLabelFields {
+            brand_name: "Stone Creek".to_string(),
+            class_type: "Table Red Wine".to_string(),
+            abv: 13.9,
+            net_contents: "750 mL".to_string(),
+            country_of_origin: None,
+            government_warning: None,
+            name_address_statement: None,
+            disclosure_statements: vec!["Contains Sulfites".to_string()],
+            consensus: Vec::new(),
+        },
+        model: prompts::DEFAULT_MODEL.to_string(),
+        prompt: prompts::registry().active(LABEL_FIELDS),
+        raw_output: raw_output.to_string(),
+        repairs: Vec::new(),
+        members: Vec::new(),
+    };
+    let mut extraction = OcrExtraction {
+        members: vec![OcrExtraction {
+            model: "@cf/meta/llama-3.2-11b-vision-instruct".to_string(),
+            raw_output: r#"{"brand_name": "Stone Creak"}"#.to_string(),
+            ..extraction.clone()
+        }],
+        ..extraction
+    };
+    extraction.fields.consensus = ocr_ensemble::vote(&[
+        ("llava".to_string(), extraction.fields.clone()),
+        ("llama".to_string(), extraction.fields.clone()),
+    ])
+    .consensus;
+    assert!(job_extraction::load(&db_pool, &encryption, job.id)
+        .await
+        .expect("Failed to load extraction")
+        .is_none());
+
+    let provenance = job_extraction::save(&db_pool, &encryption, job.id, &extraction, Duration::from_millis(1234))
+        .await
+        .expect("Failed to save extraction");
+    assert_eq!(provenance.prompt_version, extraction.prompt.version);
+
+    let job = queries::get_job(&db_pool, job.id)
+        .await
+        .expect("Failed to get job")
+        .expect("Job not found");
+    assert_eq!(job.extraction, Some(provenance.clone()));
+
+    // Stored encrypted, loaded back intact
+    let (fields, output): (Vec<u8>, Vec<u8>) =
+        sqlx::query_as("SELECT extracted_fields, ocr_raw_output FROM verification_jobs WHERE id = $1")
+            .bind(job.id)
+            .fetch_one(&db_pool)
+            .await
+            .expect("Failed to read extraction");
+    assert!(!String::from_utf8_lossy(&fields).contains("Stone Creek"));
+    assert_ne!(output, raw_output.as_bytes());
+
+    let stored = job_extraction::load(&db_pool, &encryption, job.id)
+        .await
+        .expect("Failed to load extraction")
+        .expect("Extraction not stored");
+    assert_eq!(stored.provenance, provenance);
+    assert_eq!(stored.raw_output, raw_output);
+    assert_eq!(stored.duration_ms, 1234);
+    assert_eq!(stored.fields.brand_name, "Stone Creek");
+    assert_eq!(stored.fields.disclosure_statements, vec!["Contains Sulfites"]);
+    assert_eq!(stored.fields.consensus, extraction.fields.consensus);
+    assert_eq!(stored.ensemble_output.len(), 1);
+    assert_eq!(stored.ensemble_output[0].model, "@cf/meta/llama-3.2-11b-vision-instruct");
+    assert_eq!(stored.ensemble_output[0].raw_output, r#"{"brand_name": "Stone Creak"}"#);
+
+    // Output without readable fields keeps its provenance and raw output, but
+    // no fields, so a retry runs OCR again
+    let unreadable = UnreadableOutput {
+        model: prompts::DEFAULT_MODEL.to_string(),
+        prompt: extraction.prompt,
+        raw_output: "The image is too blurry to read.".to_string(),
+        reason: "No JSON object in model output".to_string(),
+    };
+    let provenance = job_extraction::save_unreadable(&db_pool, &encryption, job.id, &unreadable, Duration::from_millis(50))
+        .await
+        .expect("Failed to save unreadable output");
+    let job = queries::get_job(&db_pool, job.id)
+        .await
+        .expect("Failed to get job")
+        .expect("Job not found");
+    assert_eq!(job.extraction, Some(provenance));
+    assert!(job_extraction::load(&db_pool, &encryption, job.id)
+        .await
+        .expect("Failed to load extraction")
+        .is_none());
+    let (output, parse_error): (Vec<u8>, Option<String>) =
+        sqlx::query_as("SELECT ocr_raw_output, ocr_parse_error FROM verification_jobs WHERE id = $1")
+            .bind(job.id)
+            .fetch_one(&db_pool)
+            .await
+            .expect("Failed to read extraction");
+    assert_eq!(encryption.decrypt(&output).expect("Failed to decrypt"), unreadable.raw_output.as_bytes());
+    assert_eq!(parse_error.as_deref(), Some("No JSON object in model output"));
+
+    sqlx::query("DELETE FROM verification_jobs WHERE id = $1")
+        .bind(job.id)
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+}
+
+/// Test that each validation of a job is kept as a revision
+#[tokio::test]
+#[ignore]
+async fn test_verification_history() {
+    use label_verify_hw::models::job::{VerificationInputs, VerificationTrigger};
+    use label_verify_hw::models::label::ExtractedLabelFields;
+    use label_verify_hw::models::verification::RevalidateRequest;
+    use label_verify_hw::services::{profile::ValidationProfile, validation};
+
+    let config = AppConfig::from_env().expect("Failed to load config");
+    let db_pool = db::init_pool(&config.database_url)
+        .await
+        .expect("Failed to connect to database");
+    db::run_migrations(&db_pool)
+        .await
+        .expect("Failed to run migrations");
+
+    let submitted = VerificationInputs {
+        expected_brand: Some("Stone Creek".to_string()),
+        expected_abv: Some(13.5),
+        profile: Some("default".to_string()),
+        ..VerificationInputs::default()
+    };
+    let job = queries::create_job(&db_pool, "test/history.enc", Some("test-user"), &submitted)
+        .await
+        .expect("Failed to create job");
+    assert_eq!(job.inputs, submitted);
+
+    let extracted = ExtractedLabelFields {
+        brand_name: "Stone Creek".to_string(),
+        class_type: "Table Red Wine".to_string(),
+        abv: 13.5,
+        net_contents: "750 mL".to_string(),
+        country_of_origin: None,
+        government_warning: None,
+        name_address_statement: Some("Produced and bottled by Stone Creek Cellars, Napa, CA".to_string()),
+        disclosure_statements: vec!["Contains Sulfites".to_string()],
+        consensus: Vec::new(),
+    };
+    let profile = ValidationProfile::default();
+    let first = validation::verify_label(&extracted, Some("Stone Creek"), None, Some(13.5), &profile);
+    let revision = queries::record_verification(&db_pool, job.id, VerificationTrigger::Worker, &submitted, &first)
+        .await
+        .expect("Failed to record verification");
+    assert_eq!(revision, 1);
+
+    // A revalidation with a new expected ABV keeps the first result
+    let inputs = RevalidateRequest {
+        expected_abv: Some(Some(11.0)),
+        ..RevalidateRequest::default()
+    }
+    .apply(job.inputs.clone());
+    assert_eq!(inputs.expected_brand.as_deref(), Some("Stone Creek"));
+    assert_eq!(inputs.profile.as_deref(), Some("default"));
+
+    // An explicit null clears a submitted value; omitted fields are kept
+    let cleared = serde_json::from_str::<RevalidateRequest>(r#"{"expected_abv": null}"#)
+        .expect("Failed to parse request")
+        .apply(job.inputs.clone());
+    assert_eq!(cleared.expected_abv, None);
+    assert_eq!(cleared.expected_brand.as_deref(), Some("Stone Creek"));
+    let second = validation::verify_label(&extracted, Some("Stone Creek"), None, inputs.expected_abv, &profile);
+    assert!(first.passed);
+    assert!(!second.passed);
+    let revision = queries::record_verification(&db_pool, job.id, VerificationTrigger::Revalidate, &inputs, &second)
+        .await
+        .expect("Failed to record verification");
+    assert_eq!(revision, 2);
+
+    let job = queries::get_job(&db_pool, job.id)
+        .await
+        .expect("Failed to get job")
+        .expect("Job not found");
+    assert_eq!(job.status, JobStatus::Completed);
+    assert_eq!(job.result.as_ref().and_then(|r| r["passed"].as_bool()), Some(false));
+    assert_eq!(job.inputs, submitted);
+
+    let history = queries::get_verification_history(&db_pool, job.id)
+        .await
+        .expect("Failed to get history");
+    assert_eq!(history.len(), 2);
+    assert_eq!((history[0].revision, history[0].trigger, history[0].passed), (1, VerificationTrigger::Worker, true));
+    assert_eq!((history[1].revision, history[1].trigger, history[1].passed), (2, VerificationTrigger::Revalidate, false));
+    assert_eq!(history[0].expected_abv, Some(13.5));
+    assert_eq!(history[1].expected_abv, Some(11.0));
+
+    sqlx::query("DELETE FROM verification_jobs WHERE id = $1")
+        .bind(job.id)
+        .execute(&db_pool)
+        .await
+        .expect("Failed to clean up");
+}